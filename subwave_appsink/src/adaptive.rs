@@ -0,0 +1,245 @@
+use gstreamer as gst;
+use gstreamer::prelude::*;
+
+/// Demuxers playbin3 plugs for HLS and DASH, legacy and `adaptivedemux2` based.
+const ADAPTIVE_DEMUXERS: [&str; 4] = ["hlsdemux", "hlsdemux2", "dashdemux", "dashdemux2"];
+
+/// Manifests larger than this are not kept; real ones are a few kilobytes.
+pub(crate) const MAX_MANIFEST_BYTES: usize = 4 * 1024 * 1024;
+
+/// One variant (an HLS `EXT-X-STREAM-INF` or a DASH video `Representation`) of an adaptive
+/// stream, as listed in its manifest.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AdaptiveVariant {
+    /// Advertised bandwidth in bits per second.
+    pub bitrate: u64,
+    pub width: Option<u32>,
+    pub height: Option<u32>,
+    /// Position in the manifest, as passed to `select_adaptive_variant`.
+    pub index: usize,
+}
+
+pub(crate) fn is_adaptive_demuxer(element: &gst::Element) -> bool {
+    element
+        .factory()
+        .is_some_and(|factory| ADAPTIVE_DEMUXERS.contains(&factory.name().as_str()))
+}
+
+/// The HLS or DASH demuxer inside `bin`, searched recursively since playbin3 keeps it in its
+/// `urisourcebin`.
+pub(crate) fn find_adaptive_demuxer(bin: &gst::Bin) -> Option<gst::Element> {
+    bin.iterate_recurse()
+        .into_iter()
+        .filter_map(Result::ok)
+        .find(is_adaptive_demuxer)
+}
+
+/// Pin `demuxer` to `bitrate` bits per second, or restore its own adaptation with 0.
+///
+/// The legacy demuxers only honour `connection-speed` (and DASH a `max-bitrate`), while the
+/// `adaptivedemux2` ones also take a `min-bitrate`, so only the properties present are set.
+pub(crate) fn limit_demuxer_bitrate(demuxer: &gst::Element, bitrate: u64) {
+    // connection-speed is in kbps; round up so a pinned rate never reads as unknown
+    set_uint_property(demuxer, "connection-speed", bitrate.div_ceil(1000));
+    set_uint_property(demuxer, "min-bitrate", bitrate);
+    set_uint_property(demuxer, "max-bitrate", bitrate);
+}
+
+fn set_uint_property(element: &gst::Element, name: &str, value: u64) {
+    let Some(pspec) = element.find_property(name) else {
+        return;
+    };
+    if pspec.value_type() == u64::static_type() {
+        element.set_property(name, value);
+    } else if pspec.value_type() == u32::static_type() {
+        element.set_property(name, value.min(u32::MAX as u64) as u32);
+    }
+}
+
+/// Variants listed in an HLS master playlist or the video representations of a DASH MPD.
+/// Anything else, including HLS media playlists, yields none.
+pub(crate) fn parse_manifest(manifest: &str) -> Vec<AdaptiveVariant> {
+    let manifest = manifest.trim_start_matches('\u{feff}').trim_start();
+    if manifest.starts_with("#EXTM3U") {
+        parse_hls_master(manifest)
+    } else if manifest.contains("<MPD") {
+        parse_dash_mpd(manifest)
+    } else {
+        Vec::new()
+    }
+}
+
+fn parse_hls_master(playlist: &str) -> Vec<AdaptiveVariant> {
+    playlist
+        .lines()
+        .filter_map(|line| line.trim().strip_prefix("#EXT-X-STREAM-INF:"))
+        .filter_map(|attributes| {
+            let attributes = hls_attributes(attributes);
+            let attribute = |name: &str| {
+                attributes
+                    .iter()
+                    .find(|(key, _)| *key == name)
+                    .map(|(_, value)| *value)
+            };
+            let bitrate = attribute("BANDWIDTH")?.parse().ok()?;
+            let (width, height) = attribute("RESOLUTION")
+                .and_then(|resolution| resolution.split_once('x'))
+                .map(|(w, h)| (w.parse().ok(), h.parse().ok()))
+                .unwrap_or_default();
+            Some((bitrate, width, height))
+        })
+        .enumerate()
+        .map(|(index, (bitrate, width, height))| AdaptiveVariant {
+            bitrate,
+            width,
+            height,
+            index,
+        })
+        .collect()
+}
+
+/// Split an HLS attribute list on the commas outside quoted strings, e.g.
+/// `BANDWIDTH=1280000,CODECS="avc1.4d401f,mp4a.40.2"`.
+fn hls_attributes(list: &str) -> Vec<(&str, &str)> {
+    let mut attributes = Vec::new();
+    let mut start = 0;
+    let mut quoted = false;
+    for (i, c) in list.char_indices().chain([(list.len(), ',')]) {
+        match c {
+            '"' => quoted = !quoted,
+            ',' if !quoted => {
+                if let Some((key, value)) = list[start..i].split_once('=') {
+                    attributes.push((key.trim(), value.trim().trim_matches('"')));
+                }
+                start = i + 1;
+            }
+            _ => {}
+        }
+    }
+    attributes
+}
+
+fn parse_dash_mpd(mpd: &str) -> Vec<AdaptiveVariant> {
+    let mut variants = Vec::new();
+    // Representations inherit mimeType, width and height from their AdaptationSet
+    let mut adaptation_set = "";
+    for tag in mpd.split('<').filter_map(|tag| tag.split('>').next()) {
+        if tag.starts_with("AdaptationSet") {
+            adaptation_set = tag;
+        } else if tag.starts_with("Representation") {
+            let attribute = |name: &str| {
+                xml_attribute(tag, name).or_else(|| xml_attribute(adaptation_set, name))
+            };
+            let is_video = attribute("mimeType").is_some_and(|mime| mime.starts_with("video/"))
+                || xml_attribute(adaptation_set, "contentType") == Some("video")
+                || attribute("width").is_some();
+            let Some(bitrate) = xml_attribute(tag, "bandwidth").and_then(|b| b.parse().ok()) else {
+                continue;
+            };
+            if is_video {
+                variants.push(AdaptiveVariant {
+                    bitrate,
+                    width: attribute("width").and_then(|w| w.parse().ok()),
+                    height: attribute("height").and_then(|h| h.parse().ok()),
+                    index: variants.len(),
+                });
+            }
+        }
+    }
+    variants
+}
+
+/// Value of attribute `name` in the contents of an XML start tag.
+fn xml_attribute<'a>(tag: &'a str, name: &str) -> Option<&'a str> {
+    tag.match_indices(name).find_map(|(i, _)| {
+        // Skip matches inside longer names, e.g. `width` in `maxWidth`
+        let preceded_by_space = tag[..i].ends_with(char::is_whitespace);
+        let rest = tag[i + name.len()..]
+            .trim_start()
+            .strip_prefix('=')?
+            .trim_start();
+        let quote = rest.chars().next().filter(|c| *c == '"' || *c == '\'')?;
+        let value = &rest[1..];
+        preceded_by_space
+            .then(|| value.split(quote).next())
+            .flatten()
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn hls_master_playlist_lists_stream_inf_variants() {
+        let playlist = "#EXTM3U\n\
+            #EXT-X-STREAM-INF:BANDWIDTH=1280000,CODECS=\"avc1.4d401f,mp4a.40.2\",RESOLUTION=640x360\n\
+            low/index.m3u8\n\
+            #EXT-X-STREAM-INF:BANDWIDTH=6000000,RESOLUTION=1920x1080,FRAME-RATE=30.000\n\
+            high/index.m3u8\n\
+            #EXT-X-STREAM-INF:BANDWIDTH=64000,CODECS=\"mp4a.40.2\"\n\
+            audio/index.m3u8\n";
+        assert_eq!(
+            parse_manifest(playlist),
+            vec![
+                AdaptiveVariant {
+                    bitrate: 1_280_000,
+                    width: Some(640),
+                    height: Some(360),
+                    index: 0,
+                },
+                AdaptiveVariant {
+                    bitrate: 6_000_000,
+                    width: Some(1920),
+                    height: Some(1080),
+                    index: 1,
+                },
+                AdaptiveVariant {
+                    bitrate: 64_000,
+                    width: None,
+                    height: None,
+                    index: 2,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn hls_media_playlist_has_no_variants() {
+        let playlist = "#EXTM3U\n#EXT-X-TARGETDURATION:6\n#EXTINF:6.0,\nsegment0.ts\n";
+        assert!(parse_manifest(playlist).is_empty());
+    }
+
+    #[test]
+    fn dash_mpd_lists_video_representations_only() {
+        let mpd = r#"<?xml version="1.0"?>
+            <MPD xmlns="urn:mpeg:dash:schema:mpd:2011">
+              <Period>
+                <AdaptationSet mimeType="video/mp4" maxWidth="1920" height="1080">
+                  <Representation id="hd" bandwidth="5000000" width="1920"/>
+                  <Representation id="sd" bandwidth="1500000" width="960" height="540"/>
+                </AdaptationSet>
+                <AdaptationSet mimeType="audio/mp4">
+                  <Representation id="aac" bandwidth="128000"/>
+                </AdaptationSet>
+              </Period>
+            </MPD>"#;
+        assert_eq!(
+            parse_manifest(mpd),
+            vec![
+                AdaptiveVariant {
+                    bitrate: 5_000_000,
+                    width: Some(1920),
+                    height: Some(1080),
+                    index: 0,
+                },
+                AdaptiveVariant {
+                    bitrate: 1_500_000,
+                    width: Some(960),
+                    height: Some(540),
+                    index: 1,
+                },
+            ]
+        );
+    }
+}
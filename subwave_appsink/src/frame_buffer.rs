@@ -0,0 +1,163 @@
+use std::cell::UnsafeCell;
+use std::fmt;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex, PoisonError};
+use std::time::Duration;
+
+use crate::render_pipeline::FrameLayout;
+
+// `middle` holds a slot index in its low bits, with FRESH set while that slot holds a frame
+// no reader has taken yet
+const INDEX: usize = 0b011;
+const FRESH: usize = 0b100;
+
+/// A decoded frame as handed from the appsink worker to the renderer.
+#[derive(Debug, Clone)]
+pub(crate) struct Frame {
+    pub(crate) data: Vec<u8>,
+    pub(crate) layout: FrameLayout,
+    /// Stream time of the frame, comparable to `position()`.
+    pub(crate) pts: Option<Duration>,
+}
+
+/// Triple buffer between the appsink worker and the draw path.
+///
+/// The worker fills the back slot and publishes it by swapping it with the middle one, and
+/// readers swap the middle slot into the front when it holds a newer frame. The worker never
+/// waits on a reader, and a frame only becomes visible once it is completely written, so its
+/// pixels, layout and timestamp always belong together. Readers take turns on `front`, which
+/// the worker never locks.
+pub(crate) struct FrameBuffer {
+    slots: [UnsafeCell<Frame>; 3],
+    middle: AtomicUsize,
+    front: Mutex<usize>,
+}
+
+// SAFETY: every slot has a single owner at a time. The writer owns the back slot, readers own
+// the front slot while holding `front`, and the middle slot is only handed over through the
+// atomic swap on `middle`.
+unsafe impl Sync for FrameBuffer {}
+
+impl fmt::Debug for FrameBuffer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("FrameBuffer").finish_non_exhaustive()
+    }
+}
+
+impl FrameBuffer {
+    /// Create a buffer with every slot holding `initial`, returning the shared reader side and
+    /// the writer for the worker thread.
+    pub(crate) fn new(initial: Frame) -> (Arc<Self>, FrameWriter) {
+        let buffer = Arc::new(Self {
+            slots: [
+                UnsafeCell::new(initial.clone()),
+                UnsafeCell::new(initial.clone()),
+                UnsafeCell::new(initial),
+            ],
+            middle: AtomicUsize::new(1),
+            front: Mutex::new(0),
+        });
+        let writer = FrameWriter {
+            buffer: Arc::clone(&buffer),
+            back: 2,
+        };
+        (buffer, writer)
+    }
+
+    /// Run `f` on the newest published frame.
+    pub(crate) fn read<R>(&self, f: impl FnOnce(&Frame) -> R) -> R {
+        // The guarded index stays valid even if a reader panicked
+        let mut front = self.front.lock().unwrap_or_else(PoisonError::into_inner);
+        if self.middle.load(Ordering::Acquire) & FRESH != 0 {
+            *front = self.middle.swap(*front, Ordering::AcqRel) & INDEX;
+        }
+        // SAFETY: the writer never touches the front slot and other readers wait on `front`
+        f(unsafe { &*self.slots[*front].get() })
+    }
+}
+
+/// Writing end of a [`FrameBuffer`], owned by the appsink worker.
+pub(crate) struct FrameWriter {
+    buffer: Arc<FrameBuffer>,
+    back: usize,
+}
+
+impl FrameWriter {
+    /// The slot to fill with the next frame. It still holds an older frame, so its allocation
+    /// is reused.
+    pub(crate) fn back_mut(&mut self) -> &mut Frame {
+        // SAFETY: only this writer touches the back slot
+        unsafe { &mut *self.buffer.slots[self.back].get() }
+    }
+
+    /// Make the back slot the newest frame and take over the slot it replaces.
+    pub(crate) fn publish(&mut self) {
+        self.back = self.buffer.middle.swap(self.back | FRESH, Ordering::AcqRel) & INDEX;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pixel_format::VideoPixelFormat;
+    use crate::tone_mapping::ColorInfo;
+
+    fn frame(len: usize) -> Frame {
+        Frame {
+            data: vec![0; len],
+            layout: FrameLayout {
+                format: VideoPixelFormat::Nv12,
+                color: ColorInfo::default(),
+                strides: [0, 0],
+            },
+            pts: None,
+        }
+    }
+
+    #[test]
+    fn reader_sees_newest_published_frame() {
+        let (buffer, mut writer) = FrameBuffer::new(frame(4));
+        assert_eq!(buffer.read(|f| f.pts), None);
+
+        for n in 1..=3u64 {
+            writer.back_mut().pts = Some(Duration::from_millis(n));
+            writer.publish();
+        }
+        assert_eq!(buffer.read(|f| f.pts), Some(Duration::from_millis(3)));
+        // Nothing new was published, so the front frame stays put
+        assert_eq!(buffer.read(|f| f.pts), Some(Duration::from_millis(3)));
+    }
+
+    #[test]
+    fn fast_writer_never_tears_frames_for_slow_reader() {
+        const FRAMES: u64 = 20_000;
+        let (buffer, mut writer) = FrameBuffer::new(frame(64 * 1024));
+
+        let producer = std::thread::spawn(move || {
+            for n in 1..=FRAMES {
+                let frame = writer.back_mut();
+                frame.data.fill(n as u8);
+                frame.layout.strides = [n as u32, n as u32];
+                frame.pts = Some(Duration::from_nanos(n));
+                writer.publish();
+            }
+        });
+
+        let mut last = 0;
+        while last < FRAMES {
+            let n = buffer.read(|frame| {
+                let n = frame.pts.map_or(0, |pts| pts.as_nanos() as u64);
+                assert!(
+                    frame.data.iter().all(|&byte| byte == n as u8),
+                    "frame {n} is torn"
+                );
+                assert_eq!(frame.layout.strides, [n as u32, n as u32]);
+                n
+            });
+            assert!(n >= last, "frame {n} went backwards from {last}");
+            last = n;
+            std::thread::sleep(Duration::from_micros(200));
+        }
+        producer.join().unwrap();
+    }
+}
@@ -0,0 +1,140 @@
+use crate::video::AppsinkVideo;
+use crate::video_player::{VideoPlayer, take_frame_upload};
+use iced::{
+    Element,
+    advanced::{
+        self, Widget, image, layout,
+        widget::{self, tree},
+    },
+};
+
+/// Video player widget which converts each frame to RGBA on the CPU and draws it as an
+/// [`iced::widget::Image`], for renderers without the wgpu primitive path [`VideoPlayer`]
+/// relies on (e.g. software GL).
+///
+/// Slower than [`VideoPlayer`] and without HDR tone mapping, but works with any renderer that
+/// can draw images. Build it from a configured [`VideoPlayer`] to keep its size, content fit
+/// and event handlers:
+///
+/// ```ignore
+/// ImageVideoPlayer::from(VideoPlayer::new(&video).on_end_of_stream(Message::Ended))
+/// ```
+pub struct ImageVideoPlayer<'a, Message, Theme = iced::Theme, Renderer = iced::Renderer> {
+    player: VideoPlayer<'a, Message, Theme, Renderer>,
+}
+
+impl<'a, Message, Theme, Renderer> ImageVideoPlayer<'a, Message, Theme, Renderer> {
+    /// Creates a new image-based video player widget for a given video.
+    pub fn new(video: &'a AppsinkVideo) -> Self {
+        VideoPlayer::new(video).into()
+    }
+}
+
+impl<'a, Message, Theme, Renderer> From<VideoPlayer<'a, Message, Theme, Renderer>>
+    for ImageVideoPlayer<'a, Message, Theme, Renderer>
+{
+    fn from(player: VideoPlayer<'a, Message, Theme, Renderer>) -> Self {
+        Self { player }
+    }
+}
+
+/// The last frame converted for display.
+#[derive(Default)]
+struct State {
+    frame: Option<image::Handle>,
+}
+
+impl<Message, Theme, Renderer> Widget<Message, Theme, Renderer>
+    for ImageVideoPlayer<'_, Message, Theme, Renderer>
+where
+    Message: Clone,
+    Renderer: image::Renderer<Handle = image::Handle>,
+{
+    fn tag(&self) -> tree::Tag {
+        tree::Tag::of::<State>()
+    }
+
+    fn state(&self) -> tree::State {
+        tree::State::new(State::default())
+    }
+
+    fn size(&self) -> iced::Size<iced::Length> {
+        iced::Size {
+            width: iced::Length::Shrink,
+            height: iced::Length::Shrink,
+        }
+    }
+
+    fn layout(
+        &mut self,
+        _tree: &mut widget::Tree,
+        _renderer: &Renderer,
+        limits: &layout::Limits,
+    ) -> layout::Node {
+        self.player.layout_node(limits)
+    }
+
+    fn draw(
+        &self,
+        tree: &widget::Tree,
+        renderer: &mut Renderer,
+        theme: &Theme,
+        style: &advanced::renderer::Style,
+        layout: advanced::Layout<'_>,
+        cursor: advanced::mouse::Cursor,
+        viewport: &iced::Rectangle,
+    ) {
+        // Nothing to show before the first frame
+        let Some(handle) = tree.state.downcast_ref::<State>().frame.clone() else {
+            return;
+        };
+        let image = iced::widget::Image::new(handle)
+            .width(iced::Length::Fill)
+            .height(iced::Length::Fill)
+            .content_fit(self.player.content_fit);
+        Widget::<Message, Theme, Renderer>::draw(
+            &image, tree, renderer, theme, style, layout, cursor, viewport,
+        );
+    }
+
+    fn update(
+        &mut self,
+        tree: &mut widget::Tree,
+        event: &iced::Event,
+        _layout: advanced::Layout<'_>,
+        _cursor: advanced::mouse::Cursor,
+        _renderer: &Renderer,
+        _clipboard: &mut dyn advanced::Clipboard,
+        shell: &mut advanced::Shell<'_, Message>,
+        _viewport: &iced::Rectangle,
+    ) {
+        self.player.handle_event(event, shell);
+
+        // Convert here rather than in `draw`, which cannot keep the result
+        let upload_frame = matches!(
+            event,
+            iced::Event::Window(iced::window::Event::RedrawRequested(_))
+        ) && take_frame_upload(&mut self.player.video.write());
+        if upload_frame {
+            match self.player.video.frame_rgba() {
+                Ok((width, height, rgba)) => {
+                    tree.state.downcast_mut::<State>().frame =
+                        Some(image::Handle::from_rgba(width, height, rgba));
+                }
+                Err(err) => log::debug!("Skipping frame conversion: {err:?}"),
+            }
+        }
+    }
+}
+
+impl<'a, Message, Theme, Renderer> From<ImageVideoPlayer<'a, Message, Theme, Renderer>>
+    for Element<'a, Message, Theme, Renderer>
+where
+    Message: 'a + Clone,
+    Theme: 'a,
+    Renderer: 'a + image::Renderer<Handle = image::Handle>,
+{
+    fn from(video_player: ImageVideoPlayer<'a, Message, Theme, Renderer>) -> Self {
+        Self::new(video_player)
+    }
+}
@@ -1,36 +1,130 @@
 use std::{
-    sync::{Arc, Mutex, atomic::AtomicBool},
+    sync::{
+        Arc, Mutex,
+        atomic::{AtomicBool, AtomicU64, Ordering},
+        mpsc,
+    },
+    task::{Context, Waker},
     time::{Duration, Instant},
 };
 
+use crate::frame_buffer::FrameBuffer;
+use crate::render_pipeline::ScalingFilter;
+use crate::tone_mapping::{HdrMetadata, ToneMappingConfig};
+use crate::video::SubtitleStyle;
+use crate::video_player::PlayerEvent;
+use crate::video_props::VideoProps;
 use gstreamer::{
     self as gst,
-    glib::object::{Cast, ObjectExt},
+    glib::{
+        self,
+        object::{Cast, ObjectExt},
+    },
     prelude::{ElementExt, ElementExtManual, GstBinExt},
 };
+use gstreamer_video as gst_video;
 use subwave_core::{
     Error,
-    video::types::{AudioTrack, Position, SubtitleTrack, VideoProperties},
+    rtsp::RtspConfig,
+    video::elements::query_seekable,
+    video::streams::{StreamIds, parse_stream_collection},
+    video::types::{
+        AudioLevels, AudioTrack, BufferingAction, DecoderPreference, DeinterlaceMode,
+        PlaybackStatus, Position, SubtitleTrack, TrickMode, VideoTrack, apply_pixel_aspect,
+        clamp_speed,
+    },
 };
 
+/// Loudness normalization aims for the ReplayGain 2.0 reference level.
+const NORMALIZATION_TARGET_LUFS: f64 = -18.0;
+/// Bound on the normalization gain either way, so near-silent intros are not blown up.
+const NORMALIZATION_MAX_GAIN_DB: f64 = 15.0;
+
+/// A coalesced seek whose AsyncDone never arrived, e.g. because the pipeline went to READY,
+/// no longer holds back the queued target after this long.
+const COALESCED_SEEK_TIMEOUT: Duration = Duration::from_secs(1);
+
+/// Audio state to restore once a muted scrub seek settles.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct ScrubMute {
+    pub(crate) prior_muted: bool,
+    pub(crate) hold_until: Instant,
+    pub(crate) seek_done: bool,
+}
+
+/// Outcome of the initial preroll, settled once by the frame worker and awaited by
+/// [`AppsinkVideo::prepare`](crate::video::AppsinkVideo::prepare).
+#[derive(Debug, Default)]
+pub(crate) struct Readiness(Mutex<ReadinessState>);
+
+#[derive(Debug, Default)]
+struct ReadinessState {
+    ready: Option<bool>,
+    waker: Option<Waker>,
+}
+
+impl Readiness {
+    /// Record whether the pipeline prerolled. Only the first call has any effect.
+    pub(crate) fn settle(&self, ready: bool) {
+        let Ok(mut state) = self.0.lock() else {
+            return;
+        };
+        if state.ready.is_none() {
+            state.ready = Some(ready);
+            if let Some(waker) = state.waker.take() {
+                waker.wake();
+            }
+        }
+    }
+
+    pub(crate) fn get(&self) -> Option<bool> {
+        self.0.lock().ok().and_then(|state| state.ready)
+    }
+
+    /// Like [`Readiness::get`], registering the task to be woken when still unsettled.
+    pub(crate) fn poll(&self, cx: &Context<'_>) -> Option<bool> {
+        let mut state = self.0.lock().ok()?;
+        if state.ready.is_none() {
+            state.waker = Some(cx.waker().clone());
+        }
+        state.ready
+    }
+}
+
 #[derive(Debug)]
 pub(crate) struct Internal {
     pub(crate) id: u64,
+    // `subwave[id]`, the target of log messages about this video
+    pub(crate) log_target: String,
+    // URI of the current track, reopened by `AppsinkVideo::reload`. None for pipelines
+    // without a `uri` property.
+    pub(crate) uri: Option<url::Url>,
 
     pub(crate) bus: gst::Bus,
     pub(crate) source: gst::Pipeline,
     pub(crate) alive: Arc<AtomicBool>,
     pub(crate) worker: Option<std::thread::JoinHandle<()>>,
+    pub(crate) readiness: Arc<Readiness>,
+    // Set by `AppsinkVideo::stop`: the pipeline is in NULL and the worker has exited
+    pub(crate) stopped: bool,
 
-    pub(crate) video_props: Arc<Mutex<VideoProperties>>,
+    pub(crate) video_props: Arc<VideoProps>,
     pub(crate) duration: Duration,
     pub(crate) speed: f64,
+    // Flags added to rate-change seeks away from 1.0
+    pub(crate) trick_mode: TrickMode,
     pub(crate) sync_av: bool,
+    pub(crate) is_live: bool,
+    pub(crate) seekable: bool, // from a Seeking query, refreshed on AsyncDone/StateChanged
 
-    pub(crate) frame: Arc<Mutex<Vec<u8>>>,
+    pub(crate) frame: Arc<FrameBuffer>, // written by the worker without blocking the draw path
     pub(crate) upload_frame: Arc<AtomicBool>,
     pub(crate) last_frame_time: Arc<Mutex<Instant>>,
+    pub(crate) frames_rendered: Arc<AtomicU64>,
+    pub(crate) frames_dropped: Arc<AtomicU64>,
     pub(crate) looping: bool,
+    // Playback runs in a SEGMENT seek, so the end posts SegmentDone instead of EOS
+    pub(crate) segment_looping: bool,
     pub(crate) is_eos: bool,
     pub(crate) restart_stream: bool,
     pub(crate) sync_av_avg: u64,
@@ -40,6 +134,11 @@ pub(crate) struct Internal {
     pub(crate) seek_position: Option<Duration>,
     pub(crate) last_valid_position: Duration,
 
+    // Coalesced seeks: a single flushing seek is in flight until its AsyncDone, and only the
+    // latest target requested meanwhile is issued after it
+    pub(crate) coalesced_seek_started: Option<Instant>,
+    pub(crate) queued_seek: Option<(Position, bool)>,
+
     // Autoplay gating: when true, do not start playback until seek completes
     pub(crate) pending_play_after_seek: bool,
     pub(crate) pending_start_position: Option<Duration>,
@@ -47,14 +146,28 @@ pub(crate) struct Internal {
     // Track explicit user pause intent to avoid overriding with autoplay
     pub(crate) user_paused: bool,
 
+    // Scrub muting: inaccurate seeks mute audio until AsyncDone and the hold window have passed
+    pub(crate) scrub_mute: bool,
+    pub(crate) scrub_mute_window: Duration,
+    pub(crate) scrub_mute_restore: Option<ScrubMute>,
+
+    // Last fill level reported by a buffering message (100 when not buffering). While
+    // buffering the pipeline is held in PAUSED and resumed once the level is back to 100%.
+    pub(crate) is_buffering: bool,
+    pub(crate) buffering_percent: i32,
+
     // Connection monitoring
     pub(crate) current_bitrate: u64, // bits per second
     pub(crate) avg_in_rate: i64,     // average input rate from queue2
+    pub(crate) stats_counter: u64,   // frames since creation, throttles stats updates
+    // Set by `set_connection_speed`; suspends the automatic update from queue2 stats
+    pub(crate) pinned_connection_speed: Option<u64>, // bits per second
 
     // Error recovery
     pub(crate) last_error_time: Option<Instant>,
     pub(crate) error_count: u32,
     pub(crate) is_reconnecting: bool,
+    pub(crate) has_error: bool, // set by a non-recoverable bus error, cleared on restart
 
     // Subtitle tracking
     pub(crate) available_subtitles: Vec<SubtitleTrack>,
@@ -65,11 +178,63 @@ pub(crate) struct Internal {
     pub(crate) available_audio_tracks: Vec<AudioTrack>,
     pub(crate) current_audio_track: i32,
 
+    // Video track tracking, for files with several angles
+    pub(crate) available_video_tracks: Vec<VideoTrack>,
+    pub(crate) current_video_track: i32,
+
     // Stream collection for playbin3
     pub(crate) stream_collection: Option<gst::StreamCollection>,
+    // Stream ids of the track lists above, by track index
+    pub(crate) stream_ids: StreamIds,
     pub(crate) selected_stream_ids: Vec<String>,
-    // HDR metadata
-    //pub(crate) hdr_metadata: Option<HdrMetadata>,
+
+    // Gapless playback: URI handed to playbin on about-to-finish, and a flag raised
+    // once it has been queued so the next stream-start can be treated as a track change
+    pub(crate) next_uri: Arc<Mutex<Option<url::Url>>>,
+    pub(crate) track_switch_pending: Arc<AtomicBool>,
+
+    // Text of the subtitle cue reaching the overlay, as plain text, and a flag raised when it
+    // changes so the widget can forward it
+    pub(crate) subtitle_text: Arc<Mutex<Option<String>>>,
+    pub(crate) subtitle_text_changed: Arc<AtomicBool>,
+    // Style applied to playsink's text overlays as they are created
+    pub(crate) subtitle_style: Arc<Mutex<SubtitleStyle>>,
+    // Manifest (HLS master playlist or DASH MPD) as received by the adaptive demuxer
+    pub(crate) adaptive_manifest: Arc<Mutex<Vec<u8>>>,
+
+    // Subscribers to the raw bus; the sync handler is installed with the first one
+    pub(crate) bus_taps: Option<Arc<Mutex<Vec<mpsc::Sender<gst::Message>>>>>,
+
+    pub(crate) deinterlace_mode: DeinterlaceMode,
+    // Output device set with `set_audio_sink_device`, None for playbin's automatic sink
+    pub(crate) audio_sink_device: Option<String>,
+    // Settings the pipeline was built with or given later, kept for `reload`
+    pub(crate) rtsp_config: RtspConfig,
+    pub(crate) decoder_preference: DecoderPreference,
+    pub(crate) clock: Option<gst::Clock>,
+    pub(crate) base_time: Option<gst::ClockTime>,
+    // Pixel aspect ratio forced onto the decoded frames, replacing the stream's own
+    pub(crate) pixel_aspect_override: Option<(i32, i32)>,
+    pub(crate) tone_mapping: ToneMappingConfig,
+    pub(crate) scaling_filter: ScalingFilter,
+
+    // HDR metadata of the negotiated caps, written by the appsink worker
+    pub(crate) hdr_metadata: Arc<Mutex<Option<HdrMetadata>>>,
+
+    // Audio normalization: the gain elements exist once the chain was first enabled.
+    // ReplayGain (track, else album) comes from tags, loudness from ebur128level.
+    pub(crate) audio_normalization: bool,
+    pub(crate) normalization_gain: Option<gst::Element>,
+    pub(crate) normalization_limiter: Option<gst::Element>,
+    pub(crate) replaygain_db: Option<f64>,
+    pub(crate) loudness_lufs: Option<f64>,
+
+    // Audio analysis: latest level/spectrum measurements, merged as they arrive
+    pub(crate) audio_analysis_bands: Option<u32>,
+    pub(crate) audio_levels: Option<AudioLevels>,
+
+    // Every tag posted for the current track, newest values winning
+    pub(crate) tags: gst::TagList,
 }
 
 impl Internal {
@@ -80,54 +245,60 @@ impl Internal {
     ) -> Result<(), Error> {
         let position = position.into();
 
-        // Check if this is a network stream
-        // For now, assume we're dealing with network streams when seeking issues arise
-        // This avoids potential property access issues
-        let is_network_stream = true; // Conservative approach for debugging
-
         // Clear any previous seek position
         self.seek_position = None;
 
         let state = self.source.state(gst::ClockTime::ZERO);
         log::debug!(
-            "Seeking to {:?}, accurate={}, network={}, state={:?}",
+            target: &self.log_target,
+            "Seeking to {:?}, accurate={}, state={:?}",
             position,
             accurate,
-            is_network_stream,
             state
         );
 
         // Check if we're in a seekable state
         if state.1 == gst::State::Null {
-            log::error!("Cannot seek: pipeline is in NULL state");
+            log::error!(target: &self.log_target, "Cannot seek: pipeline is in NULL state");
             return Err(Error::InvalidState);
         }
 
-        // For network streams, check if we can seek
-        /*
-        if is_network_stream {
-            // Query if seeking is possible
-            let mut query = gst::query::Seeking::new(gst::Format::Time);
-            if self.source.query(&mut query) {
-                let (seekable, start, end) = query.result();
-                log::debug!(
-                    "Seeking query result: seekable={}, start={:?}, end={:?}",
-                    seekable,
-                    start,
-                    end
-                );
-                if !seekable {
-                    log::error!("Stream is not seekable");
-                    return Err(Error::InvalidState);
+        if !self.seekable {
+            log::warn!(target: &self.log_target, "Cannot seek: stream is not seekable");
+            return Err(Error::InvalidState);
+        }
+
+        // Frame seeks go straight to the demuxer only when it can convert frames to time;
+        // others ignore default-format seeks, so derive the time from the framerate instead
+        let mut target = None;
+        let position = match position {
+            Position::Frame(frame) => match self
+                .source
+                .query_convert::<gst::ClockTime>(gst::format::Default::from_u64(frame))
+            {
+                Some(time) => {
+                    target = Some(Duration::from_nanos(time.nseconds()));
+                    position
                 }
-            } else {
-                log::warn!("Failed to query seeking capabilities");
-            }
+                None => {
+                    let fps = self.video_props.framerate();
+                    let time = frame_to_time(frame, fps).ok_or(Error::Framerate(fps))?;
+                    log::debug!(target: &self.log_target, "Frame {frame} at {fps} fps is {time:?}");
+                    Position::Time(time)
+                }
+            },
+            position => position,
+        };
+
+        if self.scrub_mute && !accurate {
+            self.begin_scrub_mute();
         }
-        */
 
-        // Build seek flags
+        // Build seek flags; keep the segment loop alive across user seeks
         let mut flags = gst::SeekFlags::FLUSH;
+        if self.segment_looping {
+            flags |= gst::SeekFlags::SEGMENT;
+        }
 
         if accurate {
             flags |= gst::SeekFlags::ACCURATE;
@@ -142,7 +313,7 @@ impl Internal {
                 .source
                 .seek_simple(flags, gst::ClockTime::from_nseconds(time.as_nanos() as u64)),
             Position::Frame(_) => {
-                // Frame seeking is more complex, use full seek
+                // Default-format seek, in frames
                 self.source.seek(
                     self.speed,
                     flags,
@@ -155,31 +326,109 @@ impl Internal {
         };
 
         if let Err(e) = result {
-            log::error!("Seek failed: {:?}", e);
+            log::error!(target: &self.log_target, "Seek failed: {:?}", e);
             return Err(Error::InvalidState);
         }
 
-        log::debug!("Seek initiated successfully");
+        log::debug!(target: &self.log_target, "Seek initiated successfully");
+        // Reported as the seek target until the pipeline settles at the new position
+        if let Position::Time(time) = position {
+            target = Some(time);
+        }
+        self.seek_position = target;
+        Ok(())
+    }
+
+    /// Seek now if no coalesced seek is in flight, otherwise remember `position` and issue it
+    /// from [`finish_coalesced_seek`](Self::finish_coalesced_seek), replacing any target
+    /// queued before.
+    pub(crate) fn seek_coalesced(
+        &mut self,
+        position: Position,
+        accurate: bool,
+    ) -> Result<(), Error> {
+        let in_flight = self
+            .coalesced_seek_started
+            .is_some_and(|started| started.elapsed() < COALESCED_SEEK_TIMEOUT);
+        if in_flight {
+            if let Position::Time(time) = position {
+                self.seek_position = Some(time);
+            }
+            self.queued_seek = Some((position, accurate));
+            return Ok(());
+        }
+        self.queued_seek = None;
+        self.seek(position, accurate)?;
+        self.coalesced_seek_started = Some(Instant::now());
         Ok(())
     }
 
+    /// The in-flight coalesced seek completed: issue the latest queued target, if any.
+    pub(crate) fn finish_coalesced_seek(&mut self) {
+        self.coalesced_seek_started = None;
+        if let Some((position, accurate)) = self.queued_seek.take()
+            && let Err(err) = self.seek_coalesced(position, accurate)
+        {
+            log::warn!(target: &self.log_target, "Queued seek to {position:?} failed: {err:?}");
+        }
+    }
+
+    /// Mute for the duration of an inaccurate seek. Consecutive seeks extend the hold window
+    /// but keep the mute state from before the first one.
+    fn begin_scrub_mute(&mut self) {
+        let prior_muted = match self.scrub_mute_restore {
+            Some(pending) => pending.prior_muted,
+            None => self.source.property("mute"),
+        };
+        self.source.set_property("mute", true);
+        self.scrub_mute_restore = Some(ScrubMute {
+            prior_muted,
+            hold_until: Instant::now() + self.scrub_mute_window,
+            seek_done: false,
+        });
+    }
+
+    /// Restore the pre-scrub mute state once the seek has completed (`seek_done` is passed on
+    /// AsyncDone) and the hold window has elapsed.
+    pub(crate) fn restore_scrub_mute(&mut self, seek_done: bool) {
+        let Some(pending) = self.scrub_mute_restore.as_mut() else {
+            return;
+        };
+        pending.seek_done |= seek_done;
+        if pending.seek_done && Instant::now() >= pending.hold_until {
+            self.source.set_property("mute", pending.prior_muted);
+            self.scrub_mute_restore = None;
+        }
+    }
+
     pub(crate) fn set_speed(&mut self, speed: f64) -> Result<(), Error> {
+        if self.stopped {
+            return Err(Error::InvalidState);
+        }
+        let speed = clamp_speed(speed)?;
         let Some(position) = self.source.query_position::<gst::ClockTime>() else {
             return Err(Error::Caps);
         };
+        let trick_flags = self.trick_mode.seek_flags(speed);
         if speed > 0.0 {
+            let mut flags = gst::SeekFlags::FLUSH | gst::SeekFlags::ACCURATE | trick_flags;
+            if self.segment_looping {
+                flags |= gst::SeekFlags::SEGMENT;
+            }
             self.source.seek(
                 speed,
-                gst::SeekFlags::FLUSH | gst::SeekFlags::ACCURATE,
+                flags,
                 gst::SeekType::Set,
                 position,
                 gst::SeekType::End,
                 gst::ClockTime::from_seconds(0),
             )?;
         } else {
+            // Segment looping only restarts forwards; reverse playback ends with EOS
+            self.segment_looping = false;
             self.source.seek(
                 speed,
-                gst::SeekFlags::FLUSH | gst::SeekFlags::ACCURATE,
+                gst::SeekFlags::FLUSH | gst::SeekFlags::ACCURATE | trick_flags,
                 gst::SeekType::Set,
                 gst::ClockTime::from_seconds(0),
                 gst::SeekType::Set,
@@ -190,8 +439,411 @@ impl Internal {
         Ok(())
     }
 
+    /// Change the rate in place with an instant-rate-change seek, which neither flushes nor
+    /// moves the position. Reversing direction, or a pipeline that refuses the seek, falls
+    /// back to the flushing [`Internal::set_speed`], as does a trick mode, whose flags an
+    /// instant rate change cannot switch.
+    pub(crate) fn set_speed_instant(&mut self, speed: f64) -> Result<(), Error> {
+        if self.stopped {
+            return Err(Error::InvalidState);
+        }
+        let speed = clamp_speed(speed)?;
+        if speed > 0.0
+            && self.speed > 0.0
+            && !self.trick_mode.is_enabled()
+            && self
+                .source
+                .seek(
+                    speed,
+                    gst::SeekFlags::INSTANT_RATE_CHANGE,
+                    gst::SeekType::None,
+                    gst::ClockTime::NONE,
+                    gst::SeekType::None,
+                    gst::ClockTime::NONE,
+                )
+                .is_ok()
+        {
+            self.speed = speed;
+            return Ok(());
+        }
+        log::debug!(
+            target: &self.log_target,
+            "Instant rate change to {speed} unavailable, using a flushing seek"
+        );
+        self.set_speed(speed)
+    }
+
+    /// Reset per-track state after playbin switched to the queued next URI.
+    ///
+    /// Volume and mute live on playbin and carry over on their own, but the new
+    /// segment starts at rate 1.0, so a non-default speed has to be re-applied.
+    pub(crate) fn finish_track_change(&mut self) {
+        self.uri = self
+            .source
+            .property::<Option<String>>("current-uri")
+            .and_then(|uri| url::Url::parse(&uri).ok())
+            .or(self.uri.take());
+        self.is_eos = false;
+        self.segment_looping = false;
+        self.seek_position = None;
+        self.coalesced_seek_started = None;
+        self.queued_seek = None;
+        self.last_valid_position = Duration::ZERO;
+        self.duration = self
+            .source
+            .query_duration::<gst::ClockTime>()
+            .map(|d| Duration::from_nanos(d.nseconds()))
+            .unwrap_or(Duration::ZERO);
+
+        // Loudness information belongs to the previous track
+        self.replaygain_db = None;
+        self.loudness_lufs = None;
+        self.audio_levels = None;
+        self.tags = gst::TagList::new();
+        self.apply_normalization_gain();
+
+        // The new stream will post its own collection; drop the stale track lists
+        self.stream_collection = None;
+        self.stream_ids = StreamIds::default();
+        self.available_subtitles.clear();
+        self.available_audio_tracks.clear();
+        self.available_video_tracks.clear();
+        self.current_video_track = -1;
+        self.selected_stream_ids.clear();
+
+        if (self.speed - 1.0).abs() > f64::EPSILON
+            && let Err(e) = self.set_speed(self.speed)
+        {
+            log::warn!(
+                target: &self.log_target,
+                "Failed to carry playback speed over to next track: {:?}",
+                e
+            );
+        }
+    }
+
+    /// Re-run the Seeking query; keeps the previous answer if nothing responds.
+    pub(crate) fn refresh_seekable(&mut self) {
+        if let Some(seekable) = query_seekable(&self.source) {
+            if seekable != self.seekable {
+                log::debug!(target: &self.log_target, "Seekable changed to {}", seekable);
+            }
+            self.seekable = seekable;
+        }
+    }
+
+    /// Apply `f` to the pipeline in READY, for playbin properties such as `audio-sink` and
+    /// `audio-filter` that are only picked up on the next preroll. A running pipeline is
+    /// prerolled again and returned to its previous position and state.
+    pub(crate) fn reconfigure_in_ready(
+        &mut self,
+        f: impl FnOnce(&gst::Pipeline),
+    ) -> Result<(), Error> {
+        let (_, state, _) = self.source.state(gst::ClockTime::ZERO);
+        if self.stopped || state <= gst::State::Ready {
+            f(&self.source);
+            return Ok(());
+        }
+
+        let position = self
+            .source
+            .query_position::<gst::ClockTime>()
+            .map(|pos| Duration::from_nanos(pos.nseconds()))
+            .unwrap_or(self.last_valid_position);
+        let was_playing = state == gst::State::Playing;
+
+        self.source.set_state(gst::State::Ready)?;
+        f(&self.source);
+        self.source.set_state(gst::State::Paused)?;
+        self.source.state(gst::ClockTime::from_seconds(5)).0?;
+        self.seek(position, true)?;
+        if was_playing {
+            self.source.set_state(gst::State::Playing)?;
+        }
+        Ok(())
+    }
+
+    pub(crate) fn set_subtitle_url(&mut self, url: &url::Url) -> Result<(), Error> {
+        let (_, state, _) = self.source.state(gst::ClockTime::ZERO);
+        if self.stopped || state <= gst::State::Ready {
+            self.source.set_property("suburi", url.as_str());
+            return Ok(());
+        }
+
+        if let Err(err) = self.swap_subtitle_url_in_place(url, state) {
+            log::warn!(
+                target: &self.log_target,
+                "In-place subtitle switch failed ({}), reloading through READY",
+                err
+            );
+            self.reconfigure_in_ready(|pipeline| pipeline.set_property("suburi", url.as_str()))?;
+        }
+        Ok(())
+    }
+
+    /// Re-set the current URI with `instant-uri` enabled, so playbin3 switches to a play item
+    /// carrying the new `suburi` without tearing down its sinks, then seek back.
+    fn swap_subtitle_url_in_place(
+        &mut self,
+        url: &url::Url,
+        state: gst::State,
+    ) -> Result<(), Error> {
+        if self.source.find_property("instant-uri").is_none() {
+            return Err(Error::InvalidState);
+        }
+        let uri = self
+            .source
+            .property::<Option<String>>("current-uri")
+            .or_else(|| self.source.property::<Option<String>>("uri"))
+            .ok_or(Error::Uri)?;
+        let position = self
+            .source
+            .query_position::<gst::ClockTime>()
+            .map(|pos| Duration::from_nanos(pos.nseconds()))
+            .unwrap_or(self.last_valid_position);
+
+        // Hold the picture while the new item prerolls from its start
+        if state == gst::State::Playing {
+            self.source.set_state(gst::State::Paused)?;
+        }
+        let instant_uri: bool = self.source.property("instant-uri");
+        self.source.set_property("instant-uri", true);
+        self.source.set_property("suburi", url.as_str());
+        self.source.set_property("uri", uri.as_str());
+        self.source.set_property("instant-uri", instant_uri);
+
+        let result = self
+            .source
+            .state(gst::ClockTime::from_seconds(5))
+            .0
+            .map_err(Error::from)
+            .and_then(|_| self.seek(position, true));
+        if state == gst::State::Playing {
+            self.source.set_state(gst::State::Playing)?;
+        }
+        result
+    }
+
+    /// Gain normalization should apply: ReplayGain when tagged, else the distance of the
+    /// running loudness estimate from the ReplayGain 2.0 reference level.
+    pub(crate) fn normalization_gain_db(&self) -> f64 {
+        if !self.audio_normalization {
+            return 0.0;
+        }
+        self.replaygain_db
+            .or_else(|| {
+                self.loudness_lufs
+                    .map(|lufs| NORMALIZATION_TARGET_LUFS - lufs)
+            })
+            .unwrap_or(0.0)
+            .clamp(-NORMALIZATION_MAX_GAIN_DB, NORMALIZATION_MAX_GAIN_DB)
+    }
+
+    pub(crate) fn apply_normalization_gain(&self) {
+        let db = self.normalization_gain_db();
+        if let Some(gain) = &self.normalization_gain {
+            gain.set_property("volume", 10f64.powf(db / 20.0));
+        }
+        if let Some(limiter) = &self.normalization_limiter {
+            limiter.set_property("enabled", self.audio_normalization);
+        }
+    }
+
+    /// Merge a tag message into the track's tags and pick up any ReplayGain it carries.
+    pub(crate) fn merge_tags(&mut self, tags: &gst::TagListRef) {
+        self.tags
+            .make_mut()
+            .insert(tags, gst::TagMergeMode::Replace);
+        self.update_replaygain(tags);
+    }
+
+    /// Pick up ReplayGain from a tag message; track gain wins over album gain.
+    pub(crate) fn update_replaygain(&mut self, tags: &gst::TagListRef) {
+        let gain = tags
+            .get::<gst::tags::TrackGain>()
+            .or_else(|| tags.get::<gst::tags::AlbumGain>())
+            .map(|value| value.get());
+        if gain.is_some() && gain != self.replaygain_db {
+            log::debug!(target: &self.log_target, "ReplayGain: {:?} dB", gain);
+            self.replaygain_db = gain;
+            self.apply_normalization_gain();
+        }
+    }
+
+    /// Follow the integrated loudness posted by `ebur128level`, once per interval.
+    pub(crate) fn update_loudness(&mut self, structure: &gst::StructureRef) {
+        let Ok(lufs) = structure.get::<f64>("global-loudness") else {
+            return;
+        };
+        // The meter reports -inf (or a huge negative value) until enough audio has passed
+        if lufs.is_finite() && lufs > -70.0 {
+            self.loudness_lufs = Some(lufs);
+            if self.replaygain_db.is_none() {
+                self.apply_normalization_gain();
+            }
+        }
+    }
+
+    /// Caps of the decoded video before the pixel aspect override and the scaling to square
+    /// pixels, or `None` until negotiated.
+    pub(crate) fn coded_video_info(&self) -> Option<gst_video::VideoInfo> {
+        self.source
+            .property::<Option<gst::Element>>("video-sink")?
+            .downcast::<gst::Bin>()
+            .ok()?
+            .by_name("par-override")?
+            .static_pad("sink")?
+            .current_caps()
+            .and_then(|caps| gst_video::VideoInfo::from_caps(&caps).ok())
+    }
+
+    /// Coded size of the video, falling back to the size of the frames reaching the appsink.
+    pub(crate) fn natural_size(&self) -> (i32, i32) {
+        match self.coded_video_info() {
+            Some(info) => (info.width() as i32, info.height() as i32),
+            None => self.frame_size(),
+        }
+    }
+
+    /// Coded size scaled by the overridden or stream pixel aspect ratio.
+    pub(crate) fn display_size(&self) -> (i32, i32) {
+        match self.coded_video_info() {
+            Some(info) => {
+                let par = self
+                    .pixel_aspect_override
+                    .unwrap_or((info.par().numer(), info.par().denom()));
+                apply_pixel_aspect((info.width() as i32, info.height() as i32), par)
+            }
+            None => self.frame_size(),
+        }
+    }
+
+    fn frame_size(&self) -> (i32, i32) {
+        self.video_props.size()
+    }
+
+    /// Whether playbin's `audio-filter` is, or contains, the `scaletempo` pitch corrector.
+    pub(crate) fn pitch_correction_enabled(&self) -> bool {
+        self.source
+            .property::<Option<gst::Element>>("audio-filter")
+            .is_some_and(|filter| {
+                filter.name() == "pitch-corrector"
+                    || self.audio_filter_element("pitch-corrector").is_some()
+            })
+    }
+
+    /// Element with `name` inside playbin's `audio-filter` bin.
+    pub(crate) fn audio_filter_element(&self, name: &str) -> Option<gst::Element> {
+        self.source
+            .property::<Option<gst::Element>>("audio-filter")?
+            .downcast::<gst::Bin>()
+            .ok()?
+            .by_name(name)
+    }
+
+    /// Store per-channel RMS and peak (dBFS) from a `level` message.
+    pub(crate) fn update_audio_level(&mut self, structure: &gst::StructureRef) {
+        let channels = |field: &str| -> Vec<f64> {
+            structure
+                .get::<glib::ValueArray>(field)
+                .map(|values| values.iter().filter_map(|v| v.get::<f64>().ok()).collect())
+                .unwrap_or_default()
+        };
+        let levels = self.audio_levels.get_or_insert_with(AudioLevels::default);
+        levels.rms = channels("rms");
+        levels.peak = channels("peak");
+    }
+
+    /// Store band magnitudes (dB) from a `spectrum` message.
+    pub(crate) fn update_audio_spectrum(&mut self, structure: &gst::StructureRef) {
+        let Ok(magnitude) = structure.get::<gst::List>("magnitude") else {
+            return;
+        };
+        let levels = self.audio_levels.get_or_insert_with(AudioLevels::default);
+        levels.bands = magnitude
+            .iter()
+            .filter_map(|v| v.get::<f32>().ok())
+            .collect();
+    }
+
+    pub(crate) fn set_looping(&mut self, looping: bool) {
+        self.looping = looping;
+        // Until the pipeline has prerolled this is retried on AsyncDone
+        if looping && !self.segment_looping {
+            self.start_segment_loop();
+        }
+    }
+
+    /// Re-seek the current position as a SEGMENT seek so the end of the media posts
+    /// SegmentDone, which [`loop_segment`](Self::loop_segment) answers without flushing.
+    ///
+    /// Sources that cannot seek (or a pipeline that is not prerolled yet) keep the regular
+    /// segment and loop through the EOS restart instead.
+    pub(crate) fn start_segment_loop(&mut self) {
+        if !self.seekable
+            || self.speed < 0.0
+            || self.source.state(gst::ClockTime::ZERO).1 < gst::State::Paused
+        {
+            return;
+        }
+        let position = self
+            .source
+            .query_position::<gst::ClockTime>()
+            .unwrap_or(gst::ClockTime::ZERO);
+        match self.source.seek(
+            self.speed,
+            gst::SeekFlags::FLUSH | gst::SeekFlags::SEGMENT | gst::SeekFlags::ACCURATE,
+            gst::SeekType::Set,
+            position,
+            gst::SeekType::None,
+            gst::ClockTime::NONE,
+        ) {
+            Ok(()) => {
+                log::debug!(target: &self.log_target, "Segment looping enabled at {}", position);
+                self.segment_looping = true;
+            }
+            Err(e) => {
+                log::debug!(
+                    target: &self.log_target,
+                    "Segment seek not supported, looping on EOS: {:?}",
+                    e
+                )
+            }
+        }
+    }
+
+    /// Handle SegmentDone: seek back to the start without flushing so playback continues
+    /// seamlessly. Returns false if the loop could not continue and the end should be
+    /// treated as EOS.
+    pub(crate) fn loop_segment(&mut self) -> bool {
+        if !self.looping {
+            return false;
+        }
+        match self.source.seek(
+            self.speed,
+            gst::SeekFlags::SEGMENT,
+            gst::SeekType::Set,
+            gst::ClockTime::ZERO,
+            gst::SeekType::None,
+            gst::ClockTime::NONE,
+        ) {
+            Ok(()) => true,
+            Err(e) => {
+                log::warn!(
+                    target: &self.log_target,
+                    "Segment loop seek failed, restarting stream: {:?}",
+                    e
+                );
+                self.segment_looping = false;
+                self.restart_stream = true;
+                true
+            }
+        }
+    }
+
     pub(crate) fn restart_stream(&mut self) -> Result<(), Error> {
         self.is_eos = false;
+        self.has_error = false;
         self.set_paused(false);
         self.seek(0, false)?;
         Ok(())
@@ -200,13 +852,20 @@ impl Internal {
     pub(crate) fn set_paused(&mut self, paused: bool) {
         // Record explicit user intent
         self.user_paused = paused;
-        self.source
-            .set_state(if paused {
-                gst::State::Paused
-            } else {
-                gst::State::Playing
-            })
-            .unwrap(/* state was changed in ctor; state errors caught there */);
+        if self.stopped {
+            // Applied by `AppsinkVideo::resume`
+            return;
+        }
+        // While buffering the pipeline stays paused; the buffering handler resumes it
+        if paused || !self.is_buffering {
+            self.source
+                .set_state(if paused {
+                    gst::State::Paused
+                } else {
+                    gst::State::Playing
+                })
+                .unwrap(/* state was changed in ctor; state errors caught there */);
+        }
 
         // Set restart_stream flag to make the stream restart on the next Message::NextFrame
         if self.is_eos && !paused {
@@ -218,14 +877,40 @@ impl Internal {
         self.source.state(gst::ClockTime::ZERO).1 == gst::State::Paused
     }
 
+    pub(crate) fn status(&self) -> PlaybackStatus {
+        if self.has_error {
+            PlaybackStatus::Error
+        } else if self.is_eos {
+            PlaybackStatus::Ended
+        } else if self.buffering_percent < 100 {
+            PlaybackStatus::Buffering(self.buffering_percent)
+        } else {
+            PlaybackStatus::from_gst_state(self.source.state(gst::ClockTime::ZERO).1)
+        }
+    }
+
     pub(crate) fn update_position_cache(&mut self) {
+        // While a seek is in flight the query still answers with the old position
+        if self.seek_position.is_some()
+            && matches!(
+                self.source.state(gst::ClockTime::ZERO).0,
+                Ok(gst::StateChangeSuccess::Async)
+            )
+        {
+            return;
+        }
+
         // Try to get current position
         if let Some(pos) = self.source.query_position::<gst::ClockTime>() {
             let duration = Duration::from_nanos(pos.nseconds());
             self.last_valid_position = duration;
             // Clear seek position if we have a valid position
             if self.seek_position.is_some() {
-                log::debug!("Clearing seek position, got valid position: {:?}", duration);
+                log::debug!(
+                    target: &self.log_target,
+                    "Clearing seek position, got valid position: {:?}",
+                    duration
+                );
                 self.seek_position = None;
             }
         }
@@ -244,6 +929,15 @@ impl Internal {
         }
     }
 
+    /// Count a presented frame and refresh connection stats every ~60 frames.
+    pub(crate) fn tick_connection_stats(&mut self) {
+        self.stats_counter = self.stats_counter.wrapping_add(1);
+        if self.stats_counter.is_multiple_of(60) {
+            // Every ~60 frames (roughly 1-2 seconds)
+            self.update_connection_stats();
+        }
+    }
+
     /// Monitor connection speed from queue2 buffer statistics
     pub(crate) fn update_connection_stats(&mut self) {
         // Try to find the queue2 element in our video sink
@@ -260,39 +954,57 @@ impl Internal {
                 if avg_in_signed > 0 {
                     let avg_in = avg_in_signed;
                     self.avg_in_rate = avg_in;
-                    log::trace!("Queue2 average input rate: {} bytes/sec", avg_in);
+                    log::trace!(
+                        target: &self.log_target,
+                        "Queue2 average input rate: {} bytes/sec",
+                        avg_in
+                    );
                 }
 
                 // Get current level bytes for monitoring
                 if buffer.has_property("current-level-bytes") {
                     let current_level: u32 = buffer.property("current-level-bytes");
-                    log::trace!("Queue2 current buffer level: {} bytes", current_level);
+                    log::trace!(
+                        target: &self.log_target,
+                        "Queue2 current buffer level: {} bytes",
+                        current_level
+                    );
                 }
 
-                // Update connection speed on playbin based on measured rate
-                if self.avg_in_rate > 0 {
+                // Update connection speed on playbin based on measured rate, unless the
+                // application pinned it
+                if self.avg_in_rate > 0 && self.pinned_connection_speed.is_none() {
                     // Convert bytes/sec to bits/sec
                     let bits_per_sec: u64 = self.avg_in_rate.saturating_mul(8) as u64;
-                    self.source.set_property("connection-speed", bits_per_sec);
+                    self.apply_connection_speed(bits_per_sec);
                     self.current_bitrate = bits_per_sec;
                 }
             } else {
-                log::trace!("Buffer element is not queue2, skipping stats update");
+                log::trace!(
+                    target: &self.log_target,
+                    "Buffer element is not queue2, skipping stats update"
+                );
             }
         }
     }
 
+    /// Set playbin3's `connection-speed`, which adaptive demuxers use to pick their variant.
+    pub(crate) fn apply_connection_speed(&self, bits_per_sec: u64) {
+        // The property is in kbps; round up so a non-zero speed never reads as unknown
+        self.source
+            .set_property("connection-speed", bits_per_sec.div_ceil(1000));
+    }
+
     /// Check if error should trigger reconnection attempt
-    pub(crate) fn should_retry_on_error(&mut self, error: &gst::glib::Error) -> bool {
-        // Check if this is a network-related error
-        let is_network_error = error.to_string().to_lowercase().contains("http")
-            || error.to_string().to_lowercase().contains("connection")
-            || error.to_string().to_lowercase().contains("timeout")
-            || error.to_string().to_lowercase().contains("network");
-
-        if !is_network_error {
+    pub(crate) fn should_retry_on_error(
+        &mut self,
+        error: &gst::glib::Error,
+        network_source: bool,
+    ) -> bool {
+        let Error::Network(message) = subwave_core::from_gst_error(error, network_source) else {
             return false;
-        }
+        };
+        log::debug!(target: &self.log_target, "Network error, considering reconnect: {}", message);
 
         // Implement exponential backoff
         let now = Instant::now();
@@ -302,6 +1014,7 @@ impl Internal {
 
             if time_since_error < backoff_duration {
                 log::debug!(
+                    target: &self.log_target,
                     "Skipping retry, backoff time not elapsed: {:?} remaining",
                     backoff_duration - time_since_error
                 );
@@ -314,7 +1027,7 @@ impl Internal {
 
         // Give up after 5 attempts
         if self.error_count > 5 {
-            log::error!("Max retry attempts reached, giving up");
+            log::error!(target: &self.log_target, "Max retry attempts reached, giving up");
             return false;
         }
 
@@ -326,9 +1039,16 @@ impl Internal {
         if self.is_reconnecting {
             return Ok(()); // Already reconnecting
         }
+        if self.stopped {
+            return Err(Error::InvalidState);
+        }
 
         self.is_reconnecting = true;
-        log::info!("Attempting to reconnect, attempt #{}", self.error_count);
+        log::info!(
+            target: &self.log_target,
+            "Attempting to reconnect, attempt #{}",
+            self.error_count
+        );
 
         // Get current position before reconnecting
         let current_position = self.last_valid_position;
@@ -348,7 +1068,7 @@ impl Internal {
         }
 
         self.is_reconnecting = false;
-        log::info!("Reconnection attempt completed");
+        log::info!(target: &self.log_target, "Reconnection attempt completed");
 
         Ok(())
     }
@@ -356,7 +1076,10 @@ impl Internal {
     /// Reset error state after successful playback
     pub(crate) fn reset_error_state(&mut self) {
         if self.error_count > 0 {
-            log::debug!("Resetting error state after successful playback");
+            log::debug!(
+                target: &self.log_target,
+                "Resetting error state after successful playback"
+            );
             self.error_count = 0;
             self.last_error_time = None;
         }
@@ -367,61 +1090,42 @@ impl Internal {
     pub(crate) fn query_subtitle_tracks(&mut self) -> Vec<SubtitleTrack> {
         if !self.available_subtitles.is_empty() {
             log::info!(
+                target: &self.log_target,
                 "Returning {} subtitle tracks from stream collection",
                 self.available_subtitles.len()
             );
             return self.available_subtitles.clone();
         }
 
-        log::warn!("No subtitle tracks in stream collection, returning empty");
+        log::warn!(
+            target: &self.log_target,
+            "No subtitle tracks in stream collection, returning empty"
+        );
         Vec::new()
     }
 
     /// Select a specific subtitle track
     pub(crate) fn select_subtitle_track(&mut self, track_index: Option<i32>) -> Result<(), Error> {
-        // Make sure we have a stream collection
-        let collection = match &self.stream_collection {
-            Some(c) => c,
-            None => {
-                log::error!("No stream collection available");
-                return Err(Error::InvalidState);
-            }
-        };
-
-        // Build new stream selection list
-        let mut new_selection = Vec::new();
-
-        // Find and add video stream(s)
-        for i in 0..collection.len() {
-            if let Some(stream) = collection.stream(i as u32)
-                && stream.stream_type() == gst::StreamType::VIDEO
-            {
-                // Check if this stream was previously selected
-                if let Some(stream_id) = stream.stream_id() {
-                    let stream_id_str = stream_id.to_string();
-                    if self.selected_stream_ids.contains(&stream_id_str) {
-                        new_selection.push(stream_id_str);
-                    }
-                }
-            }
+        if self.stream_collection.is_none() {
+            log::error!(target: &self.log_target, "No stream collection available");
+            return Err(Error::InvalidState);
         }
 
-        // Find and add audio stream(s)
-        let mut audio_index = 0;
-        for i in 0..collection.len() {
-            if let Some(stream) = collection.stream(i as u32)
-                && stream.stream_type() == gst::StreamType::AUDIO
-            {
-                if audio_index == self.current_audio_track {
-                    new_selection.push(
-                        stream
-                            .stream_id()
-                            .map(|id| id.to_string())
-                            .unwrap_or_else(|| String::from("unknown")),
-                    );
-                }
-                audio_index += 1;
-            }
+        // Keep the selected video stream(s)
+        let mut new_selection: Vec<String> = self
+            .stream_ids
+            .video
+            .iter()
+            .filter(|id| self.selected_stream_ids.contains(id))
+            .cloned()
+            .collect();
+
+        // And the current audio stream
+        if let Some(id) = usize::try_from(self.current_audio_track)
+            .ok()
+            .and_then(|index| self.stream_ids.audio.get(index))
+        {
+            new_selection.push(id.clone());
         }
 
         // Handle subtitle selection
@@ -430,6 +1134,7 @@ impl Internal {
                 // Validate index
                 if index < 0 || index >= self.available_subtitles.len() as i32 {
                     log::error!(
+                        target: &self.log_target,
                         "Invalid subtitle track index: {} (available: 0-{})",
                         index,
                         self.available_subtitles.len() - 1
@@ -438,35 +1143,21 @@ impl Internal {
                 }
 
                 // Find and add the subtitle stream
-                let mut subtitle_index = 0;
-                for i in 0..collection.len() {
-                    if let Some(stream) = collection.stream(i as u32)
-                        && stream.stream_type() == gst::StreamType::TEXT
-                    {
-                        if subtitle_index == index {
-                            new_selection.push(
-                                stream
-                                    .stream_id()
-                                    .map(|id| id.to_string())
-                                    .unwrap_or_else(|| String::from("unknown")),
-                            );
-                            break;
-                        }
-                        subtitle_index += 1;
-                    }
+                if let Some(id) = self.stream_ids.subtitle.get(index as usize) {
+                    new_selection.push(id.clone());
                 }
 
                 self.current_subtitle_track = Some(index);
                 self.subtitles_enabled = true;
 
-                log::info!("Selected subtitle track {}", index);
+                log::info!(target: &self.log_target, "Selected subtitle track {}", index);
             }
             None => {
                 // Don't add any subtitle streams to disable subtitles
                 self.current_subtitle_track = None;
                 self.subtitles_enabled = false;
 
-                log::info!("Disabled subtitles");
+                log::info!(target: &self.log_target, "Disabled subtitles");
             }
         }
 
@@ -480,22 +1171,34 @@ impl Internal {
         let prev_state = self.subtitles_enabled;
         self.subtitles_enabled = enabled;
 
-        log::info!("set_subtitles_enabled: {} -> {}", prev_state, enabled);
+        log::info!(
+            target: &self.log_target,
+            "set_subtitles_enabled: {} -> {}",
+            prev_state,
+            enabled
+        );
 
         if enabled {
             // Re-enable the previously selected track
             if let Some(track) = self.current_subtitle_track {
                 // Use select_subtitle_track which will handle the stream selection
                 if let Err(e) = self.select_subtitle_track(Some(track)) {
-                    log::error!("Failed to re-enable subtitle track: {:?}", e);
+                    log::error!(
+                        target: &self.log_target,
+                        "Failed to re-enable subtitle track: {:?}",
+                        e
+                    );
                 }
             } else {
-                log::warn!("Subtitles enabled but no track previously selected");
+                log::warn!(
+                    target: &self.log_target,
+                    "Subtitles enabled but no track previously selected"
+                );
             }
         } else {
             // Disable subtitles by selecting None
             if let Err(e) = self.select_subtitle_track(None) {
-                log::error!("Failed to disable subtitles: {:?}", e);
+                log::error!(target: &self.log_target, "Failed to disable subtitles: {:?}", e);
             }
         }
     }
@@ -505,6 +1208,7 @@ impl Internal {
         // For playbin3, tracks are already populated via stream collection
         if !self.available_audio_tracks.is_empty() {
             log::info!(
+                target: &self.log_target,
                 "Returning {} audio tracks from stream collection",
                 self.available_audio_tracks.len()
             );
@@ -512,24 +1216,24 @@ impl Internal {
         }
 
         // Fallback to old method for compatibility (shouldn't happen with playbin3)
-        log::warn!("No audio tracks in stream collection, falling back to old method");
+        log::warn!(
+            target: &self.log_target,
+            "No audio tracks in stream collection, falling back to old method"
+        );
         self.available_audio_tracks.clone()
     }
 
     /// Select a specific audio track
     pub(crate) fn select_audio_track(&mut self, track_index: i32) -> Result<(), Error> {
-        // Make sure we have a stream collection
-        let collection = match &self.stream_collection {
-            Some(c) => c,
-            None => {
-                log::error!("No stream collection available");
-                return Err(Error::InvalidState);
-            }
-        };
+        if self.stream_collection.is_none() {
+            log::error!(target: &self.log_target, "No stream collection available");
+            return Err(Error::InvalidState);
+        }
 
         // Validate index
         if track_index < 0 || track_index >= self.available_audio_tracks.len() as i32 {
             log::error!(
+                target: &self.log_target,
                 "Invalid audio track index: {} (available: 0-{})",
                 track_index,
                 self.available_audio_tracks.len() - 1
@@ -537,77 +1241,78 @@ impl Internal {
             return Err(Error::InvalidState);
         }
 
-        // Build new stream selection list
-        let mut new_selection = Vec::new();
-
-        // Find and add video stream(s)
-        for i in 0..collection.len() {
-            if let Some(stream) = collection.stream(i as u32)
-                && stream.stream_type() == gst::StreamType::VIDEO
-            {
-                // Check if this stream was previously selected
-                if let Some(stream_id) = stream.stream_id() {
-                    let stream_id_str = stream_id.to_string();
-                    if self.selected_stream_ids.contains(&stream_id_str) {
-                        new_selection.push(stream_id_str);
-                    }
-                }
-            }
-        }
+        // Keep the selected video stream(s)
+        let mut new_selection: Vec<String> = self
+            .stream_ids
+            .video
+            .iter()
+            .filter(|id| self.selected_stream_ids.contains(id))
+            .cloned()
+            .collect();
 
-        // Find and add the selected audio stream
-        let mut audio_index = 0;
-        for i in 0..collection.len() {
-            if let Some(stream) = collection.stream(i as u32)
-                && stream.stream_type() == gst::StreamType::AUDIO
-            {
-                if audio_index == track_index {
-                    new_selection.push(
-                        stream
-                            .stream_id()
-                            .map(|id| id.to_string())
-                            .unwrap_or_else(|| String::from("unknown")),
-                    );
-                }
-                audio_index += 1;
-            }
-        }
+        // Add the selected audio stream
+        new_selection.push(self.stream_ids.audio[track_index as usize].clone());
 
         // Add current subtitle stream if enabled
         if self.subtitles_enabled
-            && let Some(subtitle_track) = self.current_subtitle_track
+            && let Some(id) = self
+                .current_subtitle_track
+                .and_then(|index| usize::try_from(index).ok())
+                .and_then(|index| self.stream_ids.subtitle.get(index))
         {
-            let mut subtitle_index = 0;
-            for i in 0..collection.len() {
-                if let Some(stream) = collection.stream(i as u32)
-                    && stream.stream_type() == gst::StreamType::TEXT
-                {
-                    if subtitle_index == subtitle_track {
-                        new_selection.push(
-                            stream
-                                .stream_id()
-                                .map(|id| id.to_string())
-                                .unwrap_or_else(|| String::from("unknown")),
-                        );
-                        break;
-                    }
-                    subtitle_index += 1;
-                }
-            }
+            new_selection.push(id.clone());
         }
 
         self.current_audio_track = track_index;
 
-        log::info!("Selected audio track {}", track_index);
+        log::info!(target: &self.log_target, "Selected audio track {}", track_index);
 
         // Update the selected stream IDs and send the event
         self.selected_stream_ids = new_selection;
         self.send_stream_selection()
     }
 
+    /// Select a specific video track, keeping the selected audio and subtitle streams
+    pub(crate) fn select_video_track(&mut self, track_index: i32) -> Result<(), Error> {
+        if self.stream_collection.is_none() {
+            log::error!(target: &self.log_target, "No stream collection available");
+            return Err(Error::InvalidState);
+        }
+
+        let video_ids = &self.stream_ids.video;
+        let Some(target) = usize::try_from(track_index)
+            .ok()
+            .and_then(|index| video_ids.get(index))
+        else {
+            log::error!(
+                target: &self.log_target,
+                "Invalid video track index: {} ({} available)",
+                track_index,
+                video_ids.len()
+            );
+            return Err(Error::InvalidState);
+        };
+
+        let mut new_selection = vec![target.clone()];
+        new_selection.extend(
+            self.selected_stream_ids
+                .iter()
+                .filter(|id| !video_ids.contains(id))
+                .cloned(),
+        );
+
+        self.current_video_track = track_index;
+
+        log::info!(target: &self.log_target, "Selected video track {}", track_index);
+
+        self.selected_stream_ids = new_selection;
+        self.send_stream_selection()
+    }
+
     /// Process stream collection message for playbin3
     pub(crate) fn update_stream_collection(&mut self, collection: gst::StreamCollection) {
         log::info!(
+            target: &self.log_target,
             "Received stream collection with {} streams",
             collection.len()
         );
@@ -615,131 +1320,62 @@ impl Internal {
         // Store the collection
         self.stream_collection = Some(collection.clone());
 
-        // Clear existing track lists
-        self.available_audio_tracks.clear();
-        self.available_subtitles.clear();
-        self.selected_stream_ids.clear();
-
-        // Process each stream in the collection
-        for i in 0..collection.len() {
-            if let Some(stream) = collection.stream(i as u32) {
-                let stream_id = stream.stream_id();
-                let stream_type = stream.stream_type();
-
-                log::debug!(
-                    "Stream {}: id={:?}, type={:?}, flags={:?}",
-                    i,
-                    stream_id,
-                    stream_type,
-                    stream.stream_flags()
-                );
-
-                // Get stream caps and tags
-                let caps = stream.caps();
-                let tags = stream.tags();
-
-                match stream_type {
-                    gst::StreamType::AUDIO => {
-                        let mut audio_track = AudioTrack {
-                            index: self.available_audio_tracks.len() as i32,
-                            language: None,
-                            title: None,
-                            codec: None,
-                            channels: None,
-                            sample_rate: None,
-                        };
-
-                        // Extract metadata from tags if available
-                        if let Some(tags) = tags {
-                            if let Some(lang) = tags.get::<gst::tags::LanguageCode>() {
-                                audio_track.language = Some(lang.get().to_string());
-                            }
-                            if let Some(title) = tags.get::<gst::tags::Title>() {
-                                audio_track.title = Some(title.get().to_string());
-                            }
-                            if let Some(codec) = tags.get::<gst::tags::AudioCodec>() {
-                                audio_track.codec = Some(codec.get().to_string());
-                            }
-                        }
-
-                        // Extract info from caps if available
-                        if let Some(caps) = caps
-                            && let Some(s) = caps.structure(0)
-                        {
-                            if let Ok(rate) = s.get::<i32>("rate") {
-                                audio_track.sample_rate = Some(rate);
-                            }
-                            if let Ok(channels) = s.get::<i32>("channels") {
-                                audio_track.channels = Some(channels);
-                            }
-                        }
-
-                        // If stream is selected by default, track it
-                        if stream.stream_flags().contains(gst::StreamFlags::SELECT)
-                            && let Some(id) = stream_id
-                        {
-                            self.selected_stream_ids.push(id.to_string());
-                            self.current_audio_track = audio_track.index;
-                        }
-
-                        self.available_audio_tracks.push(audio_track);
-                    }
-                    gst::StreamType::TEXT => {
-                        let mut subtitle_track = SubtitleTrack {
-                            index: self.available_subtitles.len() as i32,
-                            language: None,
-                            title: None,
-                            codec: None,
-                        };
-
-                        // Extract metadata from tags if available
-                        if let Some(tags) = tags {
-                            if let Some(lang) = tags.get::<gst::tags::LanguageCode>() {
-                                subtitle_track.language = Some(lang.get().to_string());
-                            }
-                            if let Some(title) = tags.get::<gst::tags::Title>() {
-                                subtitle_track.title = Some(title.get().to_string());
-                            }
-                            if let Some(codec) = tags.get::<gst::tags::VideoCodec>() {
-                                subtitle_track.codec = Some(codec.get().to_string());
-                            } else if let Some(codec) = tags.get::<gst::tags::Codec>() {
-                                subtitle_track.codec = Some(codec.get().to_string());
-                            }
-                        }
+        let info = parse_stream_collection(&collection);
+        for (id, codec) in &info.skipped_subtitle {
+            log::info!(
+                target: &self.log_target,
+                "Skipping unsupported subtitle format {id}: codec={codec:?}"
+            );
+        }
+        for (i, stream) in (0..collection.len())
+            .filter_map(|i| collection.stream(i as u32))
+            .enumerate()
+        {
+            log::debug!(
+                target: &self.log_target,
+                "Stream {}: id={:?}, type={:?}, flags={:?}",
+                i,
+                stream.stream_id(),
+                stream.stream_type(),
+                stream.stream_flags()
+            );
+        }
 
-                        self.available_subtitles.push(subtitle_track);
-                    }
-                    gst::StreamType::VIDEO => {
-                        // Track selected video streams
-                        if stream.stream_flags().contains(gst::StreamFlags::SELECT)
-                            && let Some(id) = stream_id
-                        {
-                            self.selected_stream_ids.push(id.to_string());
-                        }
-                    }
-                    _ => {
-                        log::debug!("Ignoring stream of type {:?}", stream_type);
-                    }
-                }
-            }
+        if let Some(index) = info.ids.selected_index(&info.ids.audio) {
+            self.current_audio_track = index;
         }
+        self.current_video_track = info.ids.selected_index(&info.ids.video).unwrap_or(-1);
+        self.selected_stream_ids = info.ids.selected.clone();
+        self.available_audio_tracks = info.audio;
+        self.available_subtitles = info.subtitle;
+        self.available_video_tracks = info.video;
+        self.stream_ids = info.ids;
 
         log::info!(
-            "Found {} audio tracks, {} subtitle tracks",
+            target: &self.log_target,
+            "Found {} video tracks, {} audio tracks, {} subtitle tracks",
+            self.available_video_tracks.len(),
             self.available_audio_tracks.len(),
             self.available_subtitles.len()
         );
-        log::info!("Selected streams: {:?}", self.selected_stream_ids);
+        log::info!(target: &self.log_target, "Selected streams: {:?}", self.selected_stream_ids);
     }
 
     /// Send stream selection event for playbin3
     pub(crate) fn send_stream_selection(&mut self) -> Result<(), Error> {
         if self.selected_stream_ids.is_empty() {
-            log::warn!("No streams selected, skipping stream selection event");
+            log::warn!(
+                target: &self.log_target,
+                "No streams selected, skipping stream selection event"
+            );
             return Ok(());
         }
 
-        log::info!("Sending stream selection: {:?}", self.selected_stream_ids);
+        log::info!(
+            target: &self.log_target,
+            "Sending stream selection: {:?}",
+            self.selected_stream_ids
+        );
 
         // Create SELECT_STREAMS event
         let stream_refs: Vec<&str> = self
@@ -751,10 +1387,409 @@ impl Internal {
 
         // Send event to the pipeline
         if !self.source.send_event(event) {
-            log::error!("Failed to send SELECT_STREAMS event");
+            log::error!(target: &self.log_target, "Failed to send SELECT_STREAMS event");
             return Err(Error::InvalidState);
         }
 
         Ok(())
     }
+
+    /// Drain the bus and advance the playback state machine: end of stream and looping,
+    /// errors and reconnects, buffering, completed seeks and track changes. Returns the
+    /// resulting events in order.
+    ///
+    /// While paused or at the end of the stream, unless a loop restart is due, only seek
+    /// completions are handled so paused seeks still finish; everything else stays queued
+    /// until playback resumes. Without a widget to claim decoded frames (`headless`), each
+    /// frame is reported once here instead of until it is drawn.
+    pub(crate) fn pump(&mut self, headless: bool) -> Vec<PlayerEvent> {
+        let mut events = Vec::new();
+        let idle = !self.restart_stream && (self.is_eos || self.paused());
+        let message_types: &[gst::MessageType] = if idle {
+            &[gst::MessageType::AsyncDone]
+        } else {
+            &[
+                gst::MessageType::Error,
+                gst::MessageType::Eos,
+                gst::MessageType::SegmentDone,
+                gst::MessageType::AsyncDone,
+                gst::MessageType::StateChanged,
+                gst::MessageType::Buffering,
+                gst::MessageType::StreamCollection,
+                gst::MessageType::StreamStart,
+                gst::MessageType::DurationChanged,
+                gst::MessageType::Tag,
+                gst::MessageType::Element,
+            ]
+        };
+
+        let mut restart_stream = false;
+        if self.restart_stream {
+            restart_stream = true;
+            // Set flag to false to avoid potentially multiple seeks
+            self.restart_stream = false;
+        }
+        let mut eos_pause = false;
+
+        while let Some(msg) = self.bus.pop_filtered(message_types) {
+            match msg.view() {
+                gst::MessageView::Error(err) => {
+                    error!(target: &self.log_target, "bus returned an error: {err}");
+                    let gst_error = err.error();
+                    let network_source =
+                        subwave_core::is_network_source(err.src(), self.uri.as_ref());
+
+                    // Check if we should retry on this error
+                    if self.should_retry_on_error(&gst_error, network_source) {
+                        log::info!(
+                            target: &self.log_target,
+                            "Network error detected, scheduling reconnection attempt"
+                        );
+
+                        // Schedule reconnection on next frame
+                        // We can't reconnect immediately in the message handler
+                        self.is_reconnecting = true;
+                    } else {
+                        // Non-recoverable error, notify the application
+                        self.has_error = true;
+                        events.push(PlayerEvent::Error(gst_error));
+                    }
+                }
+                gst::MessageView::Eos(_eos) => {
+                    events.push(PlayerEvent::Eos);
+                    if self.looping {
+                        restart_stream = true;
+                    } else {
+                        eos_pause = true;
+                    }
+                }
+                gst::MessageView::SegmentDone(_) => {
+                    if !self.loop_segment() {
+                        // Looping was turned off while the segment ran
+                        events.push(PlayerEvent::Eos);
+                        eos_pause = true;
+                    }
+                }
+                gst::MessageView::AsyncDone(_) => {
+                    log::debug!(
+                        target: &self.log_target,
+                        "GStreamer AsyncDone message received - seek completed"
+                    );
+                    // Clear the cached seek position
+                    self.seek_position = None;
+                    self.restore_scrub_mute(true);
+                    self.refresh_seekable();
+                    self.finish_coalesced_seek();
+
+                    // Looping requested before preroll: switch to a segment loop now
+                    if self.looping && !self.segment_looping {
+                        self.start_segment_loop();
+                    }
+
+                    // If we are gating autoplay until seek completes, start playback now
+                    if self.pending_play_after_seek {
+                        // Optional sanity check: ensure current position is at/near target
+                        let _ = self
+                            .source
+                            .query_position::<gst::ClockTime>()
+                            .map(|pos| pos.nseconds());
+                        // Clear gating regardless; only auto-play if user did not pause
+                        self.pending_play_after_seek = false;
+                        if !self.user_paused {
+                            self.set_paused(false);
+                        } else {
+                            log::debug!(
+                                target: &self.log_target,
+                                "Autoplay gated by user pause; staying paused after seek"
+                            );
+                        }
+                    }
+                }
+                gst::MessageView::StateChanged(state_changed) => {
+                    if state_changed
+                        .src()
+                        .map(|s| s == &self.source)
+                        .unwrap_or(false)
+                    {
+                        log::debug!(
+                            target: &self.log_target,
+                            "Pipeline state changed: {:?} -> {:?}",
+                            state_changed.old(),
+                            state_changed.current()
+                        );
+                        self.refresh_seekable();
+                        events.push(PlayerEvent::StateChanged {
+                            old: state_changed.old(),
+                            current: state_changed.current(),
+                        });
+                    }
+                }
+                gst::MessageView::Buffering(buffering) => {
+                    let percent = buffering.percent();
+                    log::debug!(target: &self.log_target, "Buffering {}%", percent);
+                    let was_buffering = self.is_buffering;
+                    self.is_buffering = percent < 100;
+                    self.buffering_percent = percent;
+
+                    // Live sources cannot be held back, so they keep playing
+                    if !self.is_live
+                        && let Some(action) =
+                            BufferingAction::from_percent(was_buffering, percent, self.user_paused)
+                    {
+                        let state = match action {
+                            BufferingAction::Pause => gst::State::Paused,
+                            BufferingAction::Resume => gst::State::Playing,
+                        };
+                        if let Err(err) = self.source.set_state(state) {
+                            log::warn!(
+                                target: &self.log_target,
+                                "Failed to {action:?} pipeline for buffering: {err:?}"
+                            );
+                        }
+                    }
+                    events.push(PlayerEvent::Buffering(percent));
+                }
+                gst::MessageView::Tag(tag) => {
+                    self.merge_tags(&tag.tags());
+                }
+                gst::MessageView::Element(element) => {
+                    if let Some(structure) = element.structure() {
+                        match structure.name().as_str() {
+                            "ebur128-level" => self.update_loudness(structure),
+                            "level" => self.update_audio_level(structure),
+                            "spectrum" => self.update_audio_spectrum(structure),
+                            _ => {}
+                        }
+                    }
+                }
+                gst::MessageView::DurationChanged(_) => {
+                    if let Some(duration) = self.source.query_duration::<gst::ClockTime>() {
+                        self.duration = Duration::from_nanos(duration.nseconds());
+                        events.push(PlayerEvent::DurationChanged(self.duration));
+                    }
+                }
+                gst::MessageView::StreamStart(_) => {
+                    // Only a stream-start following about-to-finish is a track change
+                    if self.track_switch_pending.swap(false, Ordering::SeqCst) {
+                        log::info!(
+                            target: &self.log_target,
+                            "Gapless switch to next track"
+                        );
+                        self.finish_track_change();
+                        events.push(PlayerEvent::TrackChanged);
+                    }
+                }
+                gst::MessageView::StreamCollection(stream_collection) => {
+                    log::info!(
+                        target: &self.log_target,
+                        "Received StreamCollection message"
+                    );
+
+                    let collection = stream_collection.stream_collection();
+                    // Update the stream collection in our video state
+                    self.update_stream_collection(collection);
+
+                    // Send stream selection event to select default streams
+                    if let Err(e) = self.send_stream_selection() {
+                        log::error!(
+                            target: &self.log_target,
+                            "Failed to send stream selection: {:?}",
+                            e
+                        );
+                    }
+                    events.push(PlayerEvent::StreamsChanged);
+                }
+                _ => {}
+            }
+        }
+
+        // Don't run eos_pause if restart_stream is true; fixes "pausing" after restarting a stream
+        if restart_stream {
+            if let Err(err) = self.restart_stream() {
+                log::error!(
+                    target: &self.log_target,
+                    "cannot restart stream (can't seek): {err:#?}"
+                );
+            }
+        } else if eos_pause {
+            self.is_eos = true;
+            self.set_paused(true);
+        }
+
+        if self.subtitle_text_changed.swap(false, Ordering::SeqCst) {
+            let text = self
+                .subtitle_text
+                .lock()
+                .ok()
+                .and_then(|text| text.clone())
+                .unwrap_or_default();
+            events.push(PlayerEvent::SubtitleText(text));
+        }
+
+        // Unmute once a scrub's hold window runs out after its AsyncDone
+        self.restore_scrub_mute(false);
+
+        // Handle reconnection attempts after network errors
+        if self.is_reconnecting {
+            self.is_reconnecting = false;
+            if let Err(e) = self.attempt_reconnect() {
+                log::error!(
+                    target: &self.log_target,
+                    "Reconnection attempt failed: {:?}",
+                    e
+                );
+                // Notify the application about the failure
+                events.push(PlayerEvent::Error(glib::Error::new(
+                    gst::CoreError::Failed,
+                    &format!("Failed to reconnect: {:?}", e),
+                )));
+            }
+        }
+
+        // The widget clears the flag once it has uploaded the frame
+        let new_frame = if headless {
+            self.upload_frame.swap(false, Ordering::SeqCst)
+        } else {
+            self.upload_frame.load(Ordering::SeqCst)
+        };
+        if new_frame {
+            // Reset error state on successful frame
+            self.reset_error_state();
+            events.push(PlayerEvent::NewFrame);
+
+            // Update position cache when we get a new frame
+            self.update_position_cache();
+
+            // Periodically update connection stats for network streams
+            self.tick_connection_stats();
+        }
+
+        events
+    }
+}
+
+/// Plain text of a subtitle buffer, which subparse and the demuxers hand to the overlay as
+/// Pango markup: tags are dropped and entities decoded.
+pub(crate) fn plain_subtitle_text(markup: &str) -> String {
+    let mut text = String::with_capacity(markup.len());
+    let mut in_tag = false;
+    for c in markup.chars() {
+        match c {
+            '<' => in_tag = true,
+            '>' if in_tag => in_tag = false,
+            _ if !in_tag => text.push(c),
+            _ => {}
+        }
+    }
+    text.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&apos;", "'")
+        .replace("&amp;", "&")
+        .trim()
+        .to_string()
+}
+
+/// Start time of frame `frame` at a constant `fps`, or `None` if the framerate is unknown.
+fn frame_to_time(frame: u64, fps: f64) -> Option<Duration> {
+    (fps.is_finite() && fps > 0.0).then(|| Duration::from_secs_f64(frame as f64 / fps))
+}
+
+/// Index of the frame starting at `time` at a constant `fps`, the inverse of
+/// [`frame_to_time`]. Rounds to the nearest frame, since timestamps are truncated to
+/// nanoseconds.
+pub(crate) fn time_to_frame(time: Duration, fps: f64) -> Option<u64> {
+    (fps.is_finite() && fps > 0.0).then(|| (time.as_secs_f64() * fps).round() as u64)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{frame_to_time, plain_subtitle_text, time_to_frame};
+    use crate::video::AppsinkVideo;
+    use gstreamer::{self as gst, prelude::*};
+    use std::time::Duration;
+
+    /// A video on a playbin3 that is never started, for tests of the state kept around the
+    /// pipeline rather than of playback.
+    fn idle_video() -> AppsinkVideo {
+        gst::init().expect("gstreamer init");
+        let sink_bin =
+            gst::parse::bin_from_description("queue2 name=video-buffer ! appsink name=sink", true)
+                .expect("queue2 and appsink are always available");
+        let appsink = sink_bin
+            .by_name("sink")
+            .and_then(|sink| sink.downcast::<gstreamer_app::AppSink>().ok())
+            .expect("appsink");
+        let pipeline = gst::ElementFactory::make("playbin3")
+            .property("uri", "file:///nonexistent")
+            .property("video-sink", &sink_bin)
+            .build()
+            .expect("playbin3")
+            .downcast::<gst::Pipeline>()
+            .expect("playbin3 is a pipeline");
+        AppsinkVideo::from_gst_pipeline_with_state(pipeline, appsink, gst::State::Null)
+            .expect("wrap idle pipeline")
+    }
+
+    #[test]
+    fn frame_index_converts_through_framerate() {
+        assert_eq!(frame_to_time(90, 30.0), Some(Duration::from_secs(3)));
+        let ntsc = frame_to_time(1001, 30000.0 / 1001.0).unwrap();
+        assert!(ntsc.abs_diff(Duration::from_nanos(33_400_033_333)) < Duration::from_micros(1));
+        assert_eq!(frame_to_time(10, 0.0), None);
+        assert_eq!(frame_to_time(10, f64::NAN), None);
+    }
+
+    #[test]
+    fn frame_timestamps_map_back_to_their_index() {
+        let ntsc = 30000.0 / 1001.0;
+        for frame in [0, 1, 1001, 86_313] {
+            let pts = frame_to_time(frame, ntsc).unwrap();
+            // GStreamer timestamps are truncated to whole nanoseconds
+            let pts = Duration::from_nanos(pts.as_nanos() as u64);
+            assert_eq!(time_to_frame(pts, ntsc), Some(frame));
+        }
+        assert_eq!(time_to_frame(Duration::from_secs(3), 0.0), None);
+    }
+
+    #[test]
+    fn subtitle_markup_is_reduced_to_plain_text() {
+        assert_eq!(
+            plain_subtitle_text("<i>Tom &amp; Jerry</i>\n<b>&lt;3</b> "),
+            "Tom & Jerry\n<3"
+        );
+        assert_eq!(plain_subtitle_text(""), "");
+    }
+
+    #[test]
+    fn pinned_connection_speed_survives_stats_updates() {
+        let mut video = idle_video();
+        let speed = |video: &AppsinkVideo| video.read().source.property::<u64>("connection-speed");
+
+        video.set_connection_speed(2_500_000);
+        assert_eq!(speed(&video), 2500);
+        video.get_mut().avg_in_rate = 1_000_000;
+        video.get_mut().update_connection_stats();
+        assert_eq!(speed(&video), 2500);
+
+        // 0 hands control back to the measured rate
+        video.set_connection_speed(0);
+        assert_eq!(video.read().pinned_connection_speed, None);
+        assert_eq!(speed(&video), 0);
+    }
+
+    #[test]
+    fn queue2_stats_properties_match_connection_stats_reads() {
+        gst::init().expect("gstreamer init");
+        let queue2 = gst::ElementFactory::make("queue2")
+            .name("video-buffer")
+            .build()
+            .expect("queue2 is part of coreelements");
+
+        // update_connection_stats reads these with exactly these types
+        assert!(queue2.has_property("avg-in-rate"));
+        let _: i64 = queue2.property("avg-in-rate");
+        assert!(queue2.has_property("current-level-bytes"));
+        let _: u32 = queue2.property("current-level-bytes");
+    }
 }
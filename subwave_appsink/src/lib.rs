@@ -1,4 +1,11 @@
+pub mod adaptive;
+pub mod frame_buffer;
+#[cfg(feature = "image-fallback")]
+pub mod image_player;
 pub mod internal;
+pub mod pixel_format;
 pub mod render_pipeline;
+pub mod tone_mapping;
 pub mod video;
 pub mod video_player;
+pub mod video_props;
@@ -1,4 +1,6 @@
-#[derive(Debug, Clone, Copy, PartialEq)]
+use iced_wgpu::wgpu;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum VideoPixelFormat {
     Nv12,   // 8-bit 4:2:0
     P010Le, // 10-bit 4:2:0
@@ -7,6 +9,33 @@ pub enum VideoPixelFormat {
 }
 
 impl VideoPixelFormat {
+    /// Formats the appsink accepts, in order of preference. videoconvertscale passes the
+    /// decoder's format through when it is listed, so 8-bit sources stay NV12 and 10-bit
+    /// sources arrive as P010 unless the decoder can only produce NV12.
+    pub const NEGOTIATED: [VideoPixelFormat; 2] =
+        [VideoPixelFormat::Nv12, VideoPixelFormat::P010Le];
+
+    /// Name of the format in GStreamer caps.
+    pub fn gst_name(&self) -> &'static str {
+        match self {
+            VideoPixelFormat::Nv12 => "NV12",
+            VideoPixelFormat::P010Le => "P010_10LE",
+            VideoPixelFormat::P012Le => "P012_LE",
+            VideoPixelFormat::P016Le => "P016_LE",
+        }
+    }
+
+    pub fn from_gst_name(name: &str) -> Option<Self> {
+        [
+            VideoPixelFormat::Nv12,
+            VideoPixelFormat::P010Le,
+            VideoPixelFormat::P012Le,
+            VideoPixelFormat::P016Le,
+        ]
+        .into_iter()
+        .find(|format| format.gst_name() == name)
+    }
+
     pub fn bit_depth(&self) -> u8 {
         match self {
             VideoPixelFormat::Nv12 => 8,
@@ -16,24 +45,30 @@ impl VideoPixelFormat {
         }
     }
 
-    pub fn y_texture_format(&self, _device: &wgpu::Device) -> wgpu::TextureFormat {
-        match self {
-            VideoPixelFormat::Nv12 => wgpu::TextureFormat::R8Unorm,
-            VideoPixelFormat::P010Le | VideoPixelFormat::P012Le | VideoPixelFormat::P016Le => {
-                // Try different formats for HDR support
-                // First try R16Float which should be filterable
-                wgpu::TextureFormat::R16Float
-            }
+    /// True if samples are 16-bit but the device cannot sample 16-bit normalized textures,
+    /// in which case the upload keeps only the high byte of each sample.
+    pub fn needs_downconvert(&self, device: &wgpu::Device) -> bool {
+        self.bytes_per_pixel() == 2
+            && !device
+                .features()
+                .contains(wgpu::Features::TEXTURE_FORMAT_16BIT_NORM)
+    }
+
+    pub fn y_texture_format(&self, device: &wgpu::Device) -> wgpu::TextureFormat {
+        if self.bytes_per_pixel() == 1 || self.needs_downconvert(device) {
+            wgpu::TextureFormat::R8Unorm
+        } else {
+            // High-bit-depth samples are MSB-aligned, so unorm sampling yields the same
+            // normalized range as 8-bit
+            wgpu::TextureFormat::R16Unorm
         }
     }
 
-    pub fn uv_texture_format(&self, _device: &wgpu::Device) -> wgpu::TextureFormat {
-        match self {
-            VideoPixelFormat::Nv12 => wgpu::TextureFormat::Rg8Unorm,
-            VideoPixelFormat::P010Le | VideoPixelFormat::P012Le | VideoPixelFormat::P016Le => {
-                // Try Rg16Float for HDR UV data
-                wgpu::TextureFormat::Rg16Float
-            }
+    pub fn uv_texture_format(&self, device: &wgpu::Device) -> wgpu::TextureFormat {
+        if self.bytes_per_pixel() == 1 || self.needs_downconvert(device) {
+            wgpu::TextureFormat::Rg8Unorm
+        } else {
+            wgpu::TextureFormat::Rg16Unorm
         }
     }
 
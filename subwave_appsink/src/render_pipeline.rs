@@ -1,3 +1,6 @@
+use crate::frame_buffer::FrameBuffer;
+use crate::pixel_format::VideoPixelFormat;
+use crate::tone_mapping::{ColorInfo, ToneMappingConfig, VideoUniforms};
 use iced::wgpu::TextureFormat;
 use iced_wgpu::primitive::{Pipeline, Primitive};
 use iced_wgpu::wgpu;
@@ -5,7 +8,7 @@ use std::{
     collections::{BTreeMap, btree_map::Entry},
     num::NonZero,
     sync::{
-        Arc, Mutex,
+        Arc,
         atomic::{AtomicBool, AtomicUsize, Ordering},
     },
 };
@@ -17,17 +20,93 @@ struct Uniforms {
     _pad: [u8; 240],
 }
 
+/// How many times one video can be drawn in a single frame (say, in a player and a
+/// preview) before its instance buffer has to grow; each draw takes its own `Uniforms` slot.
+const INITIAL_INSTANCES_PER_FRAME: usize = 16;
+
+/// Instance buffer slots used by the next prepare and draw of a video. iced runs every
+/// prepare of a frame before its draws, in the same order, so the n-th draw of a video reads
+/// the rect written by its n-th prepare.
+#[derive(Debug, Default)]
+struct InstanceSlots {
+    prepare_index: AtomicUsize,
+    render_index: AtomicUsize,
+}
+
+impl InstanceSlots {
+    fn next_prepare(&self) -> usize {
+        self.render_index.store(0, Ordering::Relaxed);
+        self.prepare_index.fetch_add(1, Ordering::Relaxed)
+    }
+
+    fn next_render(&self) -> usize {
+        self.prepare_index.store(0, Ordering::Relaxed);
+        self.render_index.fetch_add(1, Ordering::Relaxed)
+    }
+}
+
+/// How frames are filtered when scaled to the widget size.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum ScalingFilter {
+    /// Bilinear filtering, smooth for camera footage.
+    #[default]
+    Linear,
+    /// Nearest-neighbor filtering, which keeps pixel art crisp.
+    Nearest,
+}
+
+impl ScalingFilter {
+    fn filter_mode(self) -> wgpu::FilterMode {
+        match self {
+            ScalingFilter::Linear => wgpu::FilterMode::Linear,
+            ScalingFilter::Nearest => wgpu::FilterMode::Nearest,
+        }
+    }
+}
+
+/// What the textures of a video were created for. A new track or an adaptive stream
+/// switching variants can change either, and the textures then have to be rebuilt.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct TextureKey {
+    pixel_format: VideoPixelFormat,
+    size: (u32, u32),
+}
+
+impl TextureKey {
+    fn of_frame(dimensions: (u32, u32), layout: FrameLayout) -> Self {
+        Self {
+            pixel_format: layout.format,
+            size: dimensions,
+        }
+    }
+
+    /// Whether textures created for `current` have to be rebuilt before a frame that needs
+    /// `frame` is uploaded. Row strides and colorimetry only affect the upload and shader.
+    fn needs_realloc(current: Option<TextureKey>, frame: TextureKey) -> bool {
+        current.is_some_and(|current| current != frame)
+    }
+}
+
 struct VideoEntry {
     texture_y: wgpu::Texture,
     texture_uv: wgpu::Texture,
+    // Kept to rebuild the bind groups when `instances` grows
+    view_y: wgpu::TextureView,
+    view_uv: wgpu::TextureView,
     instances: wgpu::Buffer,
-    _video_uniforms: wgpu::Buffer,
-    bg0: wgpu::BindGroup,
+    // Number of `Uniforms` slots in `instances`
+    capacity: usize,
+    // Rects written by this frame's prepares, copied over when `instances` grows
+    rects: Vec<[f32; 4]>,
+    video_uniforms: wgpu::Buffer,
+    // Last contents written to `video_uniforms`, to skip redundant writes
+    written_uniforms: Option<VideoUniforms>,
+    // One bind group per sampler, picked at draw time so switching filters is free
+    bg0_linear: wgpu::BindGroup,
+    bg0_nearest: wgpu::BindGroup,
     alive: Arc<AtomicBool>,
-    //pixel_format: VideoPixelFormat,
-    //tone_mapping_config: ToneMappingConfig,
-    prepare_index: AtomicUsize,
-    render_index: AtomicUsize,
+    key: TextureKey,
+    slots: InstanceSlots,
 }
 
 struct UploadParams<'a> {
@@ -35,14 +114,201 @@ struct UploadParams<'a> {
     queue: &'a wgpu::Queue,
     alive: &'a Arc<AtomicBool>,
     dimensions: (u32, u32),
+    layout: FrameLayout,
     frame: &'a [u8],
     format: TextureFormat,
 }
 
+/// Pixel format, colorimetry and row strides of a frame in the shared frame buffer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct FrameLayout {
+    pub(crate) format: VideoPixelFormat,
+    pub(crate) color: ColorInfo,
+    /// Row strides in bytes of the Y and interleaved UV planes.
+    pub(crate) strides: [u32; 2],
+}
+
+/// Copies the Y and interleaved UV planes of an NV12 (or P01x) buffer into `dst`, keeping the
+/// source row strides so the uploader can pass them straight through as `bytes_per_row`.
+///
+/// `offsets` and `strides` come from the buffer's `GstVideoMeta` (or the caps when the
+/// buffer carries none). Returns `false` without touching `dst` if `src` is too short.
+pub(crate) fn copy_nv12_planes(
+    src: &[u8],
+    offsets: [usize; 2],
+    strides: [u32; 2],
+    height: u32,
+    dst: &mut Vec<u8>,
+) -> bool {
+    let y_len = strides[0] as usize * height as usize;
+    let uv_len = strides[1] as usize * height.div_ceil(2) as usize;
+
+    let (Some(y), Some(uv)) = (
+        src.get(offsets[0]..offsets[0] + y_len),
+        src.get(offsets[1]..offsets[1] + uv_len),
+    ) else {
+        return false;
+    };
+
+    dst.resize(y_len + uv_len, 0);
+    dst[..y_len].copy_from_slice(y);
+    dst[y_len..].copy_from_slice(uv);
+    true
+}
+
+/// Keeps the high byte of every little-endian 16-bit sample, for devices that cannot sample
+/// 16-bit normalized textures. Returns the 8-bit planes and their strides.
+pub(crate) fn downconvert_to_8bit(
+    frame: &[u8],
+    strides: [u32; 2],
+    rows: [u32; 2],
+) -> (Vec<u8>, [u32; 2]) {
+    let y_len = (strides[0] * rows[0]) as usize;
+    let uv_len = (strides[1] * rows[1]) as usize;
+    let high_bytes = |plane: &[u8]| {
+        plane
+            .chunks_exact(2)
+            .map(|sample| sample[1])
+            .collect::<Vec<_>>()
+    };
+
+    let mut planes = high_bytes(&frame[..y_len]);
+    planes.extend(high_bytes(&frame[y_len..y_len + uv_len]));
+    (planes, [strides[0] / 2, strides[1] / 2])
+}
+
+/// Converts a frame as held in the frame buffer (Y plane, then the UV plane, at
+/// `layout.strides`) to tightly packed RGBA8, with the matrix and range of `layout.color`.
+///
+/// 16-bit samples are converted at full precision rather than truncated first. Odd
+/// dimensions share the last chroma sample, and any sample that falls outside `frame` is
+/// treated as black rather than panicking. HDR frames are not tone mapped.
+pub(crate) fn frame_to_rgba(
+    frame: &[u8],
+    width: usize,
+    height: usize,
+    layout: FrameLayout,
+) -> Vec<u8> {
+    let bytes = layout.format.bytes_per_pixel();
+    let strides = layout.strides.map(|stride| stride as usize);
+    let uv_offset = strides[0] * height;
+    let last_uv_col = width.div_ceil(2).saturating_sub(1);
+    let last_uv_row = height.div_ceil(2).saturating_sub(1);
+
+    // Samples on the 8-bit scale; 16-bit samples are MSB-aligned
+    let sample = |offset: usize| -> Option<f32> {
+        match frame.get(offset..offset + bytes)? {
+            &[value] => Some(value as f32),
+            &[low, high] => Some(u16::from_le_bytes([low, high]) as f32 / 256.0),
+            _ => None,
+        }
+    };
+    let (kr, kb) = layout.color.matrix.kr_kb();
+    let kg = 1.0 - kr - kb;
+    let (black, luma_range, chroma_range) = if layout.color.full_range {
+        (0.0, 255.0, 255.0)
+    } else {
+        (16.0, 219.0, 224.0)
+    };
+
+    let mut rgba = Vec::with_capacity(width * height * 4);
+    for y in 0..height {
+        let luma_row = y * strides[0];
+        let uv_row = uv_offset + (y / 2).min(last_uv_row) * strides[1];
+        for x in 0..width {
+            let luma = sample(luma_row + x * bytes).map_or(0.0, |v| (v - black) / luma_range);
+            let uv = uv_row + (x / 2).min(last_uv_col) * 2 * bytes;
+            let (cb, cr) = match (sample(uv), sample(uv + bytes)) {
+                (Some(u), Some(v)) => ((u - 128.0) / chroma_range, (v - 128.0) / chroma_range),
+                _ => (0.0, 0.0),
+            };
+
+            let r = luma + 2.0 * (1.0 - kr) * cr;
+            let b = luma + 2.0 * (1.0 - kb) * cb;
+            let g = (luma - kr * r - kb * b) / kg;
+            rgba.extend([r, g, b].map(|c| (c * 255.0).round().clamp(0.0, 255.0) as u8));
+            rgba.push(255);
+        }
+    }
+    rgba
+}
+
+/// A video's instance buffer, holding one `Uniforms` slot per draw in a frame.
+fn create_instance_buffer(device: &wgpu::Device, capacity: usize) -> wgpu::Buffer {
+    device.create_buffer(&wgpu::BufferDescriptor {
+        label: Some("subwave uniform buffer"),
+        size: (capacity * std::mem::size_of::<Uniforms>()) as u64,
+        usage: wgpu::BufferUsages::COPY_DST | wgpu::BufferUsages::UNIFORM,
+        mapped_at_creation: false,
+    })
+}
+
+fn write_instance(queue: &wgpu::Queue, instances: &wgpu::Buffer, slot: usize, rect: [f32; 4]) {
+    let uniforms = Uniforms {
+        rect,
+        _pad: [0; 240],
+    };
+    queue.write_buffer(
+        instances,
+        (slot * std::mem::size_of::<Uniforms>()) as u64,
+        unsafe {
+            std::slice::from_raw_parts(
+                &uniforms as *const _ as *const u8,
+                std::mem::size_of::<Uniforms>(),
+            )
+        },
+    );
+}
+
+fn video_bind_group(
+    device: &wgpu::Device,
+    layout: &wgpu::BindGroupLayout,
+    sampler: &wgpu::Sampler,
+    [view_y, view_uv]: [&wgpu::TextureView; 2],
+    instances: &wgpu::Buffer,
+    video_uniforms: &wgpu::Buffer,
+) -> wgpu::BindGroup {
+    device.create_bind_group(&wgpu::BindGroupDescriptor {
+        label: Some("subwave bind group"),
+        layout,
+        entries: &[
+            wgpu::BindGroupEntry {
+                binding: 0,
+                resource: wgpu::BindingResource::TextureView(view_y),
+            },
+            wgpu::BindGroupEntry {
+                binding: 1,
+                resource: wgpu::BindingResource::TextureView(view_uv),
+            },
+            wgpu::BindGroupEntry {
+                binding: 2,
+                resource: wgpu::BindingResource::Sampler(sampler),
+            },
+            wgpu::BindGroupEntry {
+                binding: 3,
+                resource: wgpu::BindingResource::Buffer(wgpu::BufferBinding {
+                    buffer: instances,
+                    offset: 0,
+                    size: Some(NonZero::new(std::mem::size_of::<Uniforms>() as _).unwrap()),
+                }),
+            },
+            wgpu::BindGroupEntry {
+                binding: 4,
+                resource: wgpu::BindingResource::Buffer(wgpu::BufferBinding {
+                    buffer: video_uniforms,
+                    offset: 0,
+                    size: None,
+                }),
+            },
+        ],
+    })
+}
+
 pub(crate) struct VideoRenderPipeline {
     render_pipeline: wgpu::RenderPipeline,
     bg0_layout: wgpu::BindGroupLayout,
-    sampler: wgpu::Sampler,
+    sampler_linear: wgpu::Sampler,
+    sampler_nearest: wgpu::Sampler,
     videos: BTreeMap<u64, VideoEntry>,
 }
 
@@ -164,25 +430,28 @@ impl VideoRenderPipeline {
             multiview: None,
         });
 
-        let sampler = device.create_sampler(&wgpu::SamplerDescriptor {
-            label: Some("subwave sampler"),
-            address_mode_u: wgpu::AddressMode::ClampToEdge,
-            address_mode_v: wgpu::AddressMode::ClampToEdge,
-            address_mode_w: wgpu::AddressMode::ClampToEdge,
-            mag_filter: wgpu::FilterMode::Linear,
-            min_filter: wgpu::FilterMode::Linear,
-            mipmap_filter: wgpu::FilterMode::Nearest,
-            lod_min_clamp: 0.0,
-            lod_max_clamp: 1.0,
-            compare: None,
-            anisotropy_clamp: 1,
-            border_color: None,
-        });
+        let create_sampler = |filter: ScalingFilter| {
+            device.create_sampler(&wgpu::SamplerDescriptor {
+                label: Some("subwave sampler"),
+                address_mode_u: wgpu::AddressMode::ClampToEdge,
+                address_mode_v: wgpu::AddressMode::ClampToEdge,
+                address_mode_w: wgpu::AddressMode::ClampToEdge,
+                mag_filter: filter.filter_mode(),
+                min_filter: filter.filter_mode(),
+                mipmap_filter: wgpu::FilterMode::Nearest,
+                lod_min_clamp: 0.0,
+                lod_max_clamp: 1.0,
+                compare: None,
+                anisotropy_clamp: 1,
+                border_color: None,
+            })
+        };
 
         VideoRenderPipeline {
             render_pipeline,
             bg0_layout,
-            sampler,
+            sampler_linear: create_sampler(ScalingFilter::Linear),
+            sampler_nearest: create_sampler(ScalingFilter::Nearest),
             videos: BTreeMap::new(),
         }
     }
@@ -193,18 +462,56 @@ impl VideoRenderPipeline {
             queue,
             alive,
             dimensions: (width, height),
+            layout,
             frame,
             format: _format,
         } = params;
+        let FrameLayout {
+            format: pixel_format,
+            strides,
+            ..
+        } = layout;
+
+        let (uv_width, uv_height) = (width.div_ceil(2), height.div_ceil(2));
+        let y_len = (strides[0] * height) as usize;
+        let uv_len = (strides[1] * uv_height) as usize;
+        if frame.len() < y_len + uv_len {
+            log::warn!(
+                "Frame buffer too small for {}x{} {:?} with strides {}/{}: {} bytes",
+                width,
+                height,
+                pixel_format,
+                strides[0],
+                strides[1],
+                frame.len()
+            );
+            return;
+        }
+
+        let key = TextureKey::of_frame((width, height), layout);
+        if TextureKey::needs_realloc(self.videos.get(&video_id).map(|entry| entry.key), key) {
+            log::debug!("Frame format changed to {key:?}, rebuilding textures");
+            self.reset_textures(video_id);
+        }
+
+        let downconverted = pixel_format
+            .needs_downconvert(device)
+            .then(|| downconvert_to_8bit(frame, strides, [height, uv_height]));
+        let (frame, [y_stride, uv_stride]) = match &downconverted {
+            Some((planes, strides)) => (planes.as_slice(), *strides),
+            None => (frame, strides),
+        };
+        let y_len = (y_stride * height) as usize;
+        let uv_len = (uv_stride * uv_height) as usize;
 
         if let Entry::Vacant(entry) = self.videos.entry(video_id) {
-            // For now we assume NV12 input from appsink: Y plane (R8) and interleaved UV plane (RG8)
-            // In the future, detect caps and pick from pixel_format.rs
-            let y_format = wgpu::TextureFormat::R8Unorm;
-            let uv_format = wgpu::TextureFormat::Rg8Unorm;
+            // Y plane and interleaved UV plane: R8/RG8 for NV12, R16/RG16 for P01x
+            let y_format = pixel_format.y_texture_format(device);
+            let uv_format = pixel_format.uv_texture_format(device);
 
             log::debug!(
-                "Creating textures for NV12: Y={:?}, UV={:?}, frame={}x{}",
+                "Creating textures for {:?}: Y={:?}, UV={:?}, frame={}x{}",
+                pixel_format,
                 y_format,
                 uv_format,
                 width,
@@ -212,7 +519,7 @@ impl VideoRenderPipeline {
             );
 
             let texture_y = device.create_texture(&wgpu::TextureDescriptor {
-                label: Some("subwave texture Y (R8)"),
+                label: Some("subwave texture Y"),
                 size: wgpu::Extent3d {
                     width,
                     height,
@@ -227,10 +534,10 @@ impl VideoRenderPipeline {
             });
 
             let texture_uv = device.create_texture(&wgpu::TextureDescriptor {
-                label: Some("subwave texture UV (RG8)"),
+                label: Some("subwave texture UV"),
                 size: wgpu::Extent3d {
-                    width: width / 2,
-                    height: height / 2,
+                    width: uv_width,
+                    height: uv_height,
                     depth_or_array_layers: 1,
                 },
                 mip_level_count: 1,
@@ -265,77 +572,42 @@ impl VideoRenderPipeline {
                 usage: None,
             });
 
-            let instances = device.create_buffer(&wgpu::BufferDescriptor {
-                label: Some("subwave uniform buffer"),
-                size: 256 * std::mem::size_of::<Uniforms>() as u64, // max 256 video players per frame
-                usage: wgpu::BufferUsages::COPY_DST | wgpu::BufferUsages::UNIFORM,
-                mapped_at_creation: false,
-            });
+            let instances = create_instance_buffer(device, INITIAL_INSTANCES_PER_FRAME);
 
-            // Create video uniforms buffer for HDR parameters
-            // VideoUniforms struct in shader:
-            // - color_matrix_r: vec4<f32> (16 bytes)
-            // - color_matrix_g: vec4<f32> (16 bytes)
-            // - color_matrix_b: vec4<f32> (16 bytes)
-            // - range_y: vec2<f32> (8 bytes)
-            // - range_uv: vec2<f32> (8 bytes)
-            // - tone_map_params: vec4<f32> (16 bytes)
-            // - algorithm_params: vec4<f32> (16 bytes)
-            // - transfer_func_info: vec4<f32> (16 bytes)
-            // Total: 112 bytes
+            // Color conversion and tone mapping parameters, written in prepare()
             let video_uniforms = device.create_buffer(&wgpu::BufferDescriptor {
                 label: Some("subwave video uniforms"),
-                size: 112, // Size accounting for WGSL alignment rules
+                size: std::mem::size_of::<VideoUniforms>() as u64,
                 usage: wgpu::BufferUsages::COPY_DST | wgpu::BufferUsages::UNIFORM,
                 mapped_at_creation: false,
             });
 
-            let bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
-                label: Some("subwave bind group"),
-                layout: &self.bg0_layout,
-                entries: &[
-                    wgpu::BindGroupEntry {
-                        binding: 0,
-                        resource: wgpu::BindingResource::TextureView(&view_y),
-                    },
-                    wgpu::BindGroupEntry {
-                        binding: 1,
-                        resource: wgpu::BindingResource::TextureView(&view_uv),
-                    },
-                    wgpu::BindGroupEntry {
-                        binding: 2,
-                        resource: wgpu::BindingResource::Sampler(&self.sampler),
-                    },
-                    wgpu::BindGroupEntry {
-                        binding: 3,
-                        resource: wgpu::BindingResource::Buffer(wgpu::BufferBinding {
-                            buffer: &instances,
-                            offset: 0,
-                            size: Some(NonZero::new(std::mem::size_of::<Uniforms>() as _).unwrap()),
-                        }),
-                    },
-                    wgpu::BindGroupEntry {
-                        binding: 4,
-                        resource: wgpu::BindingResource::Buffer(wgpu::BufferBinding {
-                            buffer: &video_uniforms,
-                            offset: 0,
-                            size: None,
-                        }),
-                    },
-                ],
-            });
+            let create_bind_group = |sampler: &wgpu::Sampler| {
+                video_bind_group(
+                    device,
+                    &self.bg0_layout,
+                    sampler,
+                    [&view_y, &view_uv],
+                    &instances,
+                    &video_uniforms,
+                )
+            };
 
             entry.insert(VideoEntry {
                 texture_y,
                 texture_uv,
+                written_uniforms: None,
+                bg0_linear: create_bind_group(&self.sampler_linear),
+                bg0_nearest: create_bind_group(&self.sampler_nearest),
+                view_y,
+                view_uv,
                 instances,
-                _video_uniforms: video_uniforms,
-                bg0: bind_group,
+                capacity: INITIAL_INSTANCES_PER_FRAME,
+                rects: Vec::new(),
+                video_uniforms,
                 alive: Arc::clone(alive),
-                //pixel_format,
-                //tone_mapping_config: tone_mapping_config.clone(),
-                prepare_index: AtomicUsize::new(0),
-                render_index: AtomicUsize::new(0),
+                key,
+                slots: InstanceSlots::default(),
             });
         }
 
@@ -345,7 +617,7 @@ impl VideoRenderPipeline {
             ..
         } = self.videos.get(&video_id).unwrap();
 
-        // Write Y plane (R8), bytes_per_row = width bytes
+        // Write Y plane, rows are `y_stride` bytes apart in the source buffer
         queue.write_texture(
             wgpu::TexelCopyTextureInfo {
                 texture: texture_y,
@@ -353,10 +625,10 @@ impl VideoRenderPipeline {
                 origin: wgpu::Origin3d::ZERO,
                 aspect: wgpu::TextureAspect::All,
             },
-            &frame[..(width * height) as usize],
+            &frame[..y_len],
             wgpu::TexelCopyBufferLayout {
                 offset: 0,
-                bytes_per_row: Some(width),
+                bytes_per_row: Some(y_stride),
                 rows_per_image: Some(height),
             },
             wgpu::Extent3d {
@@ -366,7 +638,7 @@ impl VideoRenderPipeline {
             },
         );
 
-        // Write interleaved UV plane (RG8), bytes_per_row = (width/2) * 2 = width
+        // Write interleaved UV plane, rows are `uv_stride` bytes apart
         queue.write_texture(
             wgpu::TexelCopyTextureInfo {
                 texture: texture_uv,
@@ -374,15 +646,15 @@ impl VideoRenderPipeline {
                 origin: wgpu::Origin3d::ZERO,
                 aspect: wgpu::TextureAspect::All,
             },
-            &frame[(width * height) as usize..],
+            &frame[y_len..y_len + uv_len],
             wgpu::TexelCopyBufferLayout {
                 offset: 0,
-                bytes_per_row: Some(width),
-                rows_per_image: Some(height / 2),
+                bytes_per_row: Some(uv_stride),
+                rows_per_image: Some(uv_height),
             },
             wgpu::Extent3d {
-                width: width / 2,
-                height: height / 2,
+                width: uv_width,
+                height: uv_height,
                 depth_or_array_layers: 1,
             },
         );
@@ -395,38 +667,74 @@ impl VideoRenderPipeline {
             .filter_map(|(id, entry)| (!entry.alive.load(Ordering::SeqCst)).then_some(*id))
             .collect();
         for id in ids {
-            if let Some(video) = self.videos.remove(&id) {
-                video.texture_y.destroy();
-                video.texture_uv.destroy();
-                video.instances.destroy();
-            }
+            self.reset_textures(id);
         }
     }
 
-    fn prepare(&mut self, queue: &wgpu::Queue, video_id: u64, bounds: &iced::Rectangle) {
+    /// Drop the GPU resources of a video; the next upload creates them afresh.
+    fn reset_textures(&mut self, video_id: u64) {
+        if let Some(video) = self.videos.remove(&video_id) {
+            video.texture_y.destroy();
+            video.texture_uv.destroy();
+            video.instances.destroy();
+            video.video_uniforms.destroy();
+        }
+    }
+
+    fn prepare(
+        &mut self,
+        device: &wgpu::Device,
+        queue: &wgpu::Queue,
+        video_id: u64,
+        bounds: &iced::Rectangle,
+        video_uniforms: VideoUniforms,
+    ) {
         if let Some(video) = self.videos.get_mut(&video_id) {
-            let uniforms = Uniforms {
-                rect: [
-                    bounds.x,
-                    bounds.y,
-                    bounds.x + bounds.width,
-                    bounds.y + bounds.height,
-                ],
-                _pad: [0; 240],
-            };
-            queue.write_buffer(
-                &video.instances,
-                (video.prepare_index.load(Ordering::Relaxed) * std::mem::size_of::<Uniforms>())
-                    as u64,
-                unsafe {
-                    std::slice::from_raw_parts(
-                        &uniforms as *const _ as *const u8,
-                        std::mem::size_of::<Uniforms>(),
+            if video.written_uniforms != Some(video_uniforms) {
+                queue.write_buffer(&video.video_uniforms, 0, video_uniforms.as_bytes());
+                video.written_uniforms = Some(video_uniforms);
+            }
+
+            let slot = video.slots.next_prepare();
+            video.rects.truncate(slot);
+            video.rects.push([
+                bounds.x,
+                bounds.y,
+                bounds.x + bounds.width,
+                bounds.y + bounds.height,
+            ]);
+
+            if slot < video.capacity {
+                write_instance(queue, &video.instances, slot, video.rects[slot]);
+            } else {
+                let capacity = (slot + 1).next_power_of_two();
+                log::debug!(
+                    "Video {video_id} drawn {} times this frame, growing to {capacity}",
+                    slot + 1
+                );
+                // The old buffer is dropped rather than destroyed, since this frame's
+                // earlier writes to it are still queued
+                video.instances = create_instance_buffer(device, capacity);
+                video.capacity = capacity;
+                for (slot, rect) in video.rects.iter().enumerate() {
+                    write_instance(queue, &video.instances, slot, *rect);
+                }
+                let create_bind_group = |sampler: &wgpu::Sampler| {
+                    video_bind_group(
+                        device,
+                        &self.bg0_layout,
+                        sampler,
+                        [&video.view_y, &video.view_uv],
+                        &video.instances,
+                        &video.video_uniforms,
                     )
-                },
-            );
-            video.prepare_index.fetch_add(1, Ordering::Relaxed);
-            video.render_index.store(0, Ordering::Relaxed);
+                };
+                let bind_groups = (
+                    create_bind_group(&self.sampler_linear),
+                    create_bind_group(&self.sampler_nearest),
+                );
+                (video.bg0_linear, video.bg0_nearest) = bind_groups;
+            }
         }
 
         self.cleanup();
@@ -438,8 +746,19 @@ impl VideoRenderPipeline {
         encoder: &mut wgpu::CommandEncoder,
         clip: &iced::Rectangle<u32>,
         video_id: u64,
+        scaling_filter: ScalingFilter,
     ) {
         if let Some(video) = self.videos.get(&video_id) {
+            let slot = video.slots.next_render();
+            if slot >= video.capacity {
+                // Only a draw without a matching prepare gets here
+                log::warn!(
+                    "Skipping draw {} of video {video_id}, which was not prepared",
+                    slot + 1
+                );
+                return;
+            }
+
             let mut pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
                 label: Some("subwave render pass"),
                 color_attachments: &[Some(wgpu::RenderPassColorAttachment {
@@ -457,19 +776,17 @@ impl VideoRenderPipeline {
             });
 
             pass.set_pipeline(&self.render_pipeline);
+            let bind_group = match scaling_filter {
+                ScalingFilter::Linear => &video.bg0_linear,
+                ScalingFilter::Nearest => &video.bg0_nearest,
+            };
             pass.set_bind_group(
                 0,
-                &video.bg0,
-                &[
-                    (video.render_index.load(Ordering::Relaxed) * std::mem::size_of::<Uniforms>())
-                        as u32,
-                ],
+                bind_group,
+                &[(slot * std::mem::size_of::<Uniforms>()) as u32],
             );
             pass.set_scissor_rect(clip.x as _, clip.y as _, clip.width as _, clip.height as _);
             pass.draw(0..6, 0..1);
-
-            video.prepare_index.store(0, Ordering::Relaxed);
-            video.render_index.fetch_add(1, Ordering::Relaxed);
         }
     }
 }
@@ -478,17 +795,19 @@ impl VideoRenderPipeline {
 pub(crate) struct VideoPrimitive {
     video_id: u64,
     alive: Arc<AtomicBool>,
-    frame: Arc<Mutex<Vec<u8>>>,
+    frame: Arc<FrameBuffer>,
     size: (u32, u32),
     upload_frame: bool,
     format: TextureFormat,
+    tone_mapping: ToneMappingConfig,
+    scaling_filter: ScalingFilter,
 }
 
 impl VideoPrimitive {
     pub fn new(
         video_id: u64,
         alive: Arc<AtomicBool>,
-        frame: Arc<Mutex<Vec<u8>>>,
+        frame: Arc<FrameBuffer>,
         size: (u32, u32),
         upload_frame: bool,
         format: TextureFormat,
@@ -500,8 +819,22 @@ impl VideoPrimitive {
             size,
             upload_frame,
             format,
+            tone_mapping: ToneMappingConfig::default(),
+            scaling_filter: ScalingFilter::default(),
         }
     }
+
+    /// Set how HDR frames are mapped to SDR when drawn.
+    pub fn with_tone_mapping(mut self, tone_mapping: ToneMappingConfig) -> Self {
+        self.tone_mapping = tone_mapping;
+        self
+    }
+
+    /// Set how the frame is filtered when scaled.
+    pub fn with_scaling_filter(mut self, scaling_filter: ScalingFilter) -> Self {
+        self.scaling_filter = scaling_filter;
+        self
+    }
 }
 
 impl Primitive for VideoPrimitive {
@@ -515,9 +848,8 @@ impl Primitive for VideoPrimitive {
         bounds: &iced::Rectangle,
         viewport: &iced_wgpu::graphics::Viewport,
     ) {
-        if self.upload_frame {
-            let frame = self.frame.lock().expect("lock frame mutex");
-            if !frame.is_empty() {
+        let color = self.frame.read(|frame| {
+            if self.upload_frame && !frame.data.is_empty() {
                 renderer.upload(
                     self.video_id,
                     UploadParams {
@@ -525,14 +857,17 @@ impl Primitive for VideoPrimitive {
                         queue,
                         alive: &self.alive,
                         dimensions: self.size,
-                        frame: &frame,
+                        layout: frame.layout,
+                        frame: &frame.data,
                         format: self.format,
                     },
                 );
             }
-        }
+            frame.layout.color
+        });
 
         renderer.prepare(
+            device,
             queue,
             self.video_id,
             &(*bounds
@@ -540,6 +875,7 @@ impl Primitive for VideoPrimitive {
                     viewport.logical_size().width as _,
                     viewport.logical_size().height as _,
                 )),
+            VideoUniforms::new(color, &self.tone_mapping),
         );
     }
 
@@ -550,6 +886,308 @@ impl Primitive for VideoPrimitive {
         target: &wgpu::TextureView,
         clip_bounds: &iced::Rectangle<u32>,
     ) {
-        renderer.draw(target, encoder, clip_bounds, self.video_id);
+        renderer.draw(
+            target,
+            encoder,
+            clip_bounds,
+            self.video_id,
+            self.scaling_filter,
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tone_mapping::YuvMatrix;
+
+    #[test]
+    fn copy_nv12_planes_keeps_rows_aligned_for_padded_strides() {
+        // 1366 is not a multiple of 4, so GStreamer pads each row to 1368 bytes
+        let (width, height) = (1366u32, 768u32);
+        let stride = 1368u32;
+        let y_len = (stride * height) as usize;
+        let uv_len = (stride * height / 2) as usize;
+
+        // Tag the first byte of every row with its row index and fill the padding with 0xff
+        let mut src = vec![0u8; y_len + uv_len];
+        for (row, chunk) in src.chunks_mut(stride as usize).enumerate() {
+            chunk[..width as usize].fill(0);
+            chunk[0] = (row % 251) as u8;
+            chunk[width as usize..].fill(0xff);
+        }
+
+        let mut dst = Vec::new();
+        assert!(copy_nv12_planes(
+            &src,
+            [0, y_len],
+            [stride, stride],
+            height,
+            &mut dst
+        ));
+        assert_eq!(dst.len(), y_len + uv_len);
+
+        // Every row must start exactly `stride` bytes after the previous one; reading with
+        // `width` as the row pitch would drift by two bytes per row and shear the image.
+        for row in 0..(height + height / 2) as usize {
+            let start = row * stride as usize;
+            assert_eq!(dst[start], (row % 251) as u8, "row {row} is misaligned");
+            assert_eq!(dst[start + width as usize], 0xff);
+        }
+    }
+
+    #[test]
+    fn copy_nv12_planes_rejects_short_buffers() {
+        let mut dst = vec![1, 2, 3];
+        assert!(!copy_nv12_planes(&[0u8; 16], [0, 8], [8, 8], 4, &mut dst));
+        assert_eq!(dst, vec![1, 2, 3]);
+    }
+
+    fn nv12_frame(format: VideoPixelFormat, color: ColorInfo, width: u32) -> FrameLayout {
+        let stride = width.div_ceil(2) * 2 * format.bytes_per_pixel() as u32;
+        FrameLayout {
+            format,
+            color,
+            strides: [stride, stride],
+        }
+    }
+
+    #[test]
+    fn frame_to_rgba_handles_odd_and_truncated_frames() {
+        let (width, height) = (721, 481);
+        for format in [VideoPixelFormat::Nv12, VideoPixelFormat::P010Le] {
+            let layout = nv12_frame(format, ColorInfo::default(), width);
+            let len =
+                (layout.strides[0] * height + layout.strides[1] * height.div_ceil(2)) as usize;
+            let frame = vec![128u8; len];
+            let (width, height) = (width as usize, height as usize);
+            assert_eq!(
+                frame_to_rgba(&frame, width, height, layout).len(),
+                width * height * 4
+            );
+
+            // Drop the last chroma row entirely
+            let truncated = &frame[..len - layout.strides[1] as usize];
+            assert_eq!(
+                frame_to_rgba(truncated, width, height, layout).len(),
+                width * height * 4
+            );
+        }
+    }
+
+    #[test]
+    fn frame_to_rgba_follows_the_matrix_and_range() {
+        // Within a rounding step of pure red
+        let is_red = |color: ColorInfo, [y, u, v]: [u8; 3]| {
+            let frame = [y, y, y, y, u, v];
+            let rgba = frame_to_rgba(&frame, 2, 2, nv12_frame(VideoPixelFormat::Nv12, color, 2));
+            rgba[..4]
+                .iter()
+                .zip([255u8, 0, 0, 255])
+                .all(|(got, want)| got.abs_diff(want) <= 1)
+        };
+        let bt601 = ColorInfo {
+            matrix: YuvMatrix::Bt601,
+            ..ColorInfo::default()
+        };
+        let bt709 = ColorInfo {
+            matrix: YuvMatrix::Bt709,
+            ..ColorInfo::default()
+        };
+        let bt709_full = ColorInfo {
+            full_range: true,
+            ..bt709
+        };
+        // Pure red in each encoding
+        assert!(is_red(bt601, [81, 90, 240]));
+        assert!(is_red(bt709, [63, 102, 240]));
+        assert!(is_red(bt709_full, [54, 99, 255]));
+        // The BT.709 encoding read as BT.601 is visibly off
+        assert!(!is_red(bt601, [63, 102, 240]));
+
+        // A 10-bit grey keeps its low bits instead of dropping to the 8-bit step below
+        let grey = (503u16 << 6).to_le_bytes();
+        let chroma = (512u16 << 6).to_le_bytes();
+        let frame = [grey, grey, grey, grey, chroma, chroma].concat();
+        let layout = nv12_frame(VideoPixelFormat::P010Le, bt709, 2);
+        let rgba = frame_to_rgba(&frame, 2, 2, layout);
+        // (503 / 4 - 16) / 219 * 255 = 127.8, where the high byte alone gives 126.9
+        assert_eq!(rgba[..4], [128, 128, 128, 255]);
+    }
+
+    #[test]
+    fn resolution_switch_rebuilds_textures() {
+        let layout = nv12_frame(VideoPixelFormat::Nv12, ColorInfo::default(), 1280);
+        let hd = TextureKey::of_frame((1280, 720), layout);
+        // The first frame creates the textures, there is nothing to rebuild
+        assert!(!TextureKey::needs_realloc(None, hd));
+        assert!(!TextureKey::needs_realloc(Some(hd), hd));
+
+        // Padded rows and new colorimetry reuse the textures
+        let padded = FrameLayout {
+            strides: [1536, 1536],
+            color: ColorInfo {
+                full_range: true,
+                ..ColorInfo::default()
+            },
+            ..layout
+        };
+        let padded = TextureKey::of_frame((1280, 720), padded);
+        assert!(!TextureKey::needs_realloc(Some(hd), padded));
+
+        // An adaptive stream switching variants, or a 10-bit track after an 8-bit one
+        let full_hd = TextureKey::of_frame((1920, 1080), layout);
+        assert!(TextureKey::needs_realloc(Some(hd), full_hd));
+        let hdr = FrameLayout {
+            format: VideoPixelFormat::P010Le,
+            strides: [2560, 2560],
+            ..layout
+        };
+        let hdr = TextureKey::of_frame((1280, 720), hdr);
+        assert!(TextureKey::needs_realloc(Some(hd), hdr));
+    }
+
+    #[test]
+    fn instance_slots_are_per_video_and_restart_every_frame() {
+        // A 16 feed video wall, each feed drawn once per frame, over two frames
+        let wall: BTreeMap<u64, InstanceSlots> =
+            (0..16).map(|id| (id, InstanceSlots::default())).collect();
+        for _frame in 0..2 {
+            for slots in wall.values() {
+                assert_eq!(slots.next_prepare(), 0);
+            }
+            for slots in wall.values() {
+                assert_eq!(slots.next_render(), 0);
+            }
+        }
+
+        // One feed drawn 16 times in a frame gets 16 distinct slots, read back in order
+        let slots = &wall[&0];
+        let prepared: Vec<_> = (0..16).map(|_| slots.next_prepare()).collect();
+        let rendered: Vec<_> = (0..16).map(|_| slots.next_render()).collect();
+        assert_eq!(prepared, (0..16).collect::<Vec<_>>());
+        assert_eq!(rendered, prepared);
+    }
+
+    #[test]
+    fn instance_slots_follow_repeated_draws_of_one_video() {
+        let slots = InstanceSlots::default();
+        assert_eq!(slots.next_prepare(), 0);
+        assert_eq!(slots.next_prepare(), 1);
+        assert_eq!(slots.next_render(), 0);
+        assert_eq!(slots.next_render(), 1);
+
+        // Draws past the initial capacity keep their own slots, the buffer grows to fit
+        let prepared: Vec<_> = (0..INITIAL_INSTANCES_PER_FRAME + 4)
+            .map(|_| slots.next_prepare())
+            .collect();
+        assert_eq!(
+            prepared,
+            (0..INITIAL_INSTANCES_PER_FRAME + 4).collect::<Vec<_>>()
+        );
+        assert_eq!(slots.next_render(), 0);
+    }
+
+    #[test]
+    #[ignore = "needs a wgpu adapter; run with --ignored"]
+    fn video_wall_gets_an_instance_slot_per_draw() {
+        let instance = wgpu::Instance::default();
+        let adapter = iced::futures::executor::block_on(
+            instance.request_adapter(&wgpu::RequestAdapterOptions::default()),
+        )
+        .expect("no wgpu adapter");
+        let (device, queue) = iced::futures::executor::block_on(
+            adapter.request_device(&wgpu::DeviceDescriptor::default()),
+        )
+        .expect("no wgpu device");
+        let format = wgpu::TextureFormat::Bgra8Unorm;
+        let mut pipeline = VideoRenderPipeline::new(&device, format);
+
+        // A 20 feed video wall, one entry per video, plus feed 0 drawn 20 times on its own
+        let layout = nv12_frame(VideoPixelFormat::Nv12, ColorInfo::default(), 2);
+        let frame = [16u8, 16, 16, 16, 128, 128];
+        let alive = Arc::new(AtomicBool::new(true));
+        let feeds = INITIAL_INSTANCES_PER_FRAME as u64 + 4;
+        for id in 0..feeds {
+            pipeline.upload(
+                id,
+                UploadParams {
+                    device: &device,
+                    queue: &queue,
+                    alive: &alive,
+                    dimensions: (2, 2),
+                    layout,
+                    frame: &frame,
+                    format,
+                },
+            );
+        }
+        assert_eq!(pipeline.videos.len(), feeds as usize);
+
+        let uniforms = VideoUniforms::new(ColorInfo::default(), &ToneMappingConfig::default());
+        let rect = |n: usize| {
+            iced::Rectangle::new(iced::Point::new(n as f32, 0.0), iced::Size::new(1.0, 1.0))
+        };
+        let repeats = INITIAL_INSTANCES_PER_FRAME + 4;
+        for id in 0..feeds {
+            pipeline.prepare(&device, &queue, id, &rect(0), uniforms);
+        }
+        for n in 1..repeats {
+            pipeline.prepare(&device, &queue, 0, &rect(n), uniforms);
+        }
+
+        // Every feed keeps the initial buffer, the repeated one grew and kept its rects
+        for (id, video) in &pipeline.videos {
+            if *id == 0 {
+                assert!(video.capacity >= repeats);
+                assert_eq!(video.rects.len(), repeats);
+                assert!(
+                    video
+                        .rects
+                        .iter()
+                        .enumerate()
+                        .all(|(n, rect)| rect[0] == n as f32)
+                );
+            } else {
+                assert_eq!(video.capacity, INITIAL_INSTANCES_PER_FRAME);
+                assert_eq!(video.rects.len(), 1);
+            }
+        }
+
+        let target = device.create_texture(&wgpu::TextureDescriptor {
+            label: None,
+            size: wgpu::Extent3d {
+                width: 64,
+                height: 64,
+                depth_or_array_layers: 1,
+            },
+            mip_level_count: 1,
+            sample_count: 1,
+            dimension: wgpu::TextureDimension::D2,
+            format,
+            usage: wgpu::TextureUsages::RENDER_ATTACHMENT,
+            view_formats: &[],
+        });
+        let view = target.create_view(&wgpu::TextureViewDescriptor::default());
+        let clip = iced::Rectangle {
+            x: 0,
+            y: 0,
+            width: 64,
+            height: 64,
+        };
+        let mut encoder = device.create_command_encoder(&wgpu::CommandEncoderDescriptor::default());
+        for id in 0..feeds {
+            pipeline.draw(&view, &mut encoder, &clip, id, ScalingFilter::Linear);
+        }
+        for _ in 1..repeats {
+            pipeline.draw(&view, &mut encoder, &clip, 0, ScalingFilter::Linear);
+        }
+        queue.submit([encoder.finish()]);
+
+        // Every draw read a slot of its own, none fell past the end of the buffer
+        for (id, video) in &pipeline.videos {
+            let drawn = if *id == 0 { repeats } else { 1 };
+            assert_eq!(video.slots.render_index.load(Ordering::Relaxed), drawn);
+        }
     }
 }
@@ -19,6 +19,21 @@ var s: sampler;
 @group(0) @binding(3)
 var<uniform> uniforms: Uniforms;
 
+// Filled from the negotiated colorimetry and ToneMappingConfig, see tone_mapping.rs
+struct VideoUniforms {
+    color_matrix_r: vec4<f32>,
+    color_matrix_g: vec4<f32>,
+    color_matrix_b: vec4<f32>,
+    range_y: vec2<f32>,
+    range_uv: vec2<f32>,
+    tone_map_params: vec4<f32>,
+    algorithm_params: vec4<f32>,
+    transfer_func_info: vec4<f32>,
+}
+
+@group(0) @binding(4)
+var<uniform> video: VideoUniforms;
+
 @vertex
 fn vs_main(@builtin(vertex_index) in_vertex_index: u32) -> VertexOutput {
     var quad = array<vec4<f32>, 6>(
@@ -36,28 +51,107 @@ fn vs_main(@builtin(vertex_index) in_vertex_index: u32) -> VertexOutput {
     return out;
 }
 
+fn sample_yuv(uv: vec2<f32>) -> vec3<f32> {
+    let y = (textureSample(tex_y, s, uv).r - video.range_y.x) * video.range_y.y;
+    let c = (textureSample(tex_uv, s, uv).rg - vec2<f32>(video.range_uv.x)) * video.range_uv.y;
+    return vec3<f32>(y, c);
+}
+
+fn srgb_to_linear(rgb: vec3<f32>) -> vec3<f32> {
+    let threshold = rgb <= vec3<f32>(0.04045);
+    let hi = pow((rgb + vec3<f32>(0.055)) / vec3<f32>(1.055), vec3<f32>(2.4));
+    let lo = rgb * vec3<f32>(1.0 / 12.92);
+    return select(hi, lo, threshold);
+}
+
+// SMPTE ST 2084 EOTF, returns absolute luminance in cd/m²
+fn pq_to_nits(e: vec3<f32>) -> vec3<f32> {
+    let m1 = 0.1593017578125;
+    let m2 = 78.84375;
+    let c1 = 0.8359375;
+    let c2 = 18.8515625;
+    let c3 = 18.6875;
+    let p = pow(max(e, vec3<f32>(0.0)), vec3<f32>(1.0 / m2));
+    let n = max(p - vec3<f32>(c1), vec3<f32>(0.0)) / (vec3<f32>(c2) - c3 * p);
+    return 10000.0 * pow(n, vec3<f32>(1.0 / m1));
+}
+
+// ARIB STD-B67 inverse OETF followed by the BT.2100 OOTF (system gamma 1.2)
+fn hlg_to_nits(e: vec3<f32>, peak: f32) -> vec3<f32> {
+    let a = 0.17883277;
+    let b = 0.28466892;
+    let c = 0.55991073;
+    let scene = select(
+        (exp((e - vec3<f32>(c)) / a) + vec3<f32>(b)) / 12.0,
+        e * e / 3.0,
+        e <= vec3<f32>(0.5),
+    );
+    let luma = dot(scene, vec3<f32>(0.2627, 0.6780, 0.0593));
+    return peak * pow(max(luma, 1e-6), 0.2) * scene;
+}
+
+fn bt2020_to_bt709(rgb: vec3<f32>) -> vec3<f32> {
+    return vec3<f32>(
+        dot(rgb, vec3<f32>(1.6605, -0.5876, -0.0728)),
+        dot(rgb, vec3<f32>(-0.1246, 1.1329, -0.0083)),
+        dot(rgb, vec3<f32>(-0.0182, -0.1006, 1.1187)),
+    );
+}
+
+fn hable(x: vec3<f32>) -> vec3<f32> {
+    let a = 0.15;
+    let b = 0.50;
+    let c = 0.10;
+    let d = 0.20;
+    let e = 0.02;
+    let f = 0.30;
+    return (x * (a * x + c * b) + d * e) / (x * (a * x + b) + d * f) - e / f;
+}
+
+// `x` and `peak` are relative to the target luminance, so 1.0 is SDR white
+fn tone_map(x: vec3<f32>, peak: f32) -> vec3<f32> {
+    let algorithm = u32(video.algorithm_params.x);
+    if algorithm == 1u {
+        return x * (vec3<f32>(1.0) + x / (peak * peak)) / (vec3<f32>(1.0) + x);
+    }
+    if algorithm == 2u {
+        return hable(x) / hable(vec3<f32>(peak));
+    }
+    return min(x, vec3<f32>(1.0));
+}
+
 @fragment
 fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
-    let yuv2r = vec3<f32>(1.164, 0.0, 1.596);
-    let yuv2g = vec3<f32>(1.164, -0.391, -0.813);
-    let yuv2b = vec3<f32>(1.164, 2.018, 0.0);
+    let yuv = sample_yuv(in.uv);
+    let encoded = clamp(
+        vec3<f32>(
+            dot(yuv, video.color_matrix_r.xyz),
+            dot(yuv, video.color_matrix_g.xyz),
+            dot(yuv, video.color_matrix_b.xyz),
+        ),
+        vec3<f32>(0.0),
+        vec3<f32>(1.0),
+    );
 
-    var yuv = vec3<f32>(0.0);
-    yuv.x = textureSample(tex_y, s, in.uv).r - 0.0625;
-    yuv.y = textureSample(tex_uv, s, in.uv).r - 0.5;
-    yuv.z = textureSample(tex_uv, s, in.uv).g - 0.5;
+    let transfer = u32(video.transfer_func_info.x);
+    if transfer == 0u {
+        return vec4<f32>(srgb_to_linear(encoded), 1.0);
+    }
 
-    var rgb = vec3<f32>(0.0);
-    rgb.x = dot(yuv, yuv2r);
-    rgb.y = dot(yuv, yuv2g);
-    rgb.z = dot(yuv, yuv2b);
+    var nits: vec3<f32>;
+    if transfer == 1u {
+        nits = pq_to_nits(encoded);
+    } else {
+        nits = hlg_to_nits(encoded, video.tone_map_params.x);
+    }
 
-    let threshold = rgb <= vec3<f32>(0.04045);
-    let hi = pow((rgb + vec3<f32>(0.055)) / vec3<f32>(1.055), vec3<f32>(2.4));
-    let lo = rgb * vec3<f32>(1.0 / 12.92);
-    rgb = select(hi, lo, threshold);
+    var rgb = nits / video.tone_map_params.y;
+    if video.transfer_func_info.y > 0.5 {
+        rgb = max(bt2020_to_bt709(rgb), vec3<f32>(0.0));
+    }
+    rgb = tone_map(rgb, video.tone_map_params.z);
 
-    return vec4<f32>(rgb, 1.0);
+    return vec4<f32>(clamp(rgb, vec3<f32>(0.0), vec3<f32>(1.0)), 1.0);
 }
 // Provenance: Derived from iced_video_player (MIT OR Apache-2.0)
 // Upstream repository: https://github.com/jazzfool/iced_video_player
@@ -0,0 +1,306 @@
+use gstreamer as gst;
+use gstreamer_video as gst_video;
+
+/// Curve used to compress HDR highlights into the SDR range.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ToneMapAlgo {
+    /// Hard-clip everything above the target luminance.
+    Clip,
+    /// Extended Reinhard, reaching white exactly at the source peak.
+    Reinhard,
+    /// Hable (Uncharted 2) filmic curve, normalized to the source peak.
+    #[default]
+    Hable,
+}
+
+impl ToneMapAlgo {
+    fn shader_id(self) -> f32 {
+        match self {
+            ToneMapAlgo::Clip => 0.0,
+            ToneMapAlgo::Reinhard => 1.0,
+            ToneMapAlgo::Hable => 2.0,
+        }
+    }
+}
+
+/// How PQ and HLG content is mapped to SDR by the appsink renderer. SDR content is unaffected.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ToneMappingConfig {
+    /// Luminance in cd/m² that is displayed as SDR white. Defaults to 203, the BT.2408
+    /// reference white; lower values brighten the picture.
+    pub target_nits: f32,
+    pub algorithm: ToneMapAlgo,
+}
+
+impl Default for ToneMappingConfig {
+    fn default() -> Self {
+        Self {
+            target_nits: 203.0,
+            algorithm: ToneMapAlgo::default(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub(crate) enum YuvMatrix {
+    Bt601,
+    #[default]
+    Bt709,
+    Bt2020,
+}
+
+impl YuvMatrix {
+    /// Luma coefficients (Kr, Kb).
+    pub(crate) fn kr_kb(self) -> (f32, f32) {
+        match self {
+            YuvMatrix::Bt601 => (0.299, 0.114),
+            YuvMatrix::Bt709 => (0.2126, 0.0722),
+            YuvMatrix::Bt2020 => (0.2627, 0.0593),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub(crate) enum Transfer {
+    #[default]
+    Sdr,
+    /// SMPTE ST 2084
+    Pq,
+    /// ARIB STD-B67
+    Hlg,
+}
+
+/// Colorimetry of the decoded frames, as negotiated on the appsink caps.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub(crate) struct ColorInfo {
+    pub(crate) matrix: YuvMatrix,
+    pub(crate) transfer: Transfer,
+    pub(crate) bt2020_primaries: bool,
+    pub(crate) full_range: bool,
+}
+
+impl ColorInfo {
+    /// `VideoInfo::from_caps` already fills in GStreamer's defaults (BT.601 for SD, BT.709
+    /// for HD) when the caps carry no colorimetry, so unknown values fall back to BT.709.
+    pub(crate) fn from_colorimetry(colorimetry: &gst_video::VideoColorimetry) -> Self {
+        let matrix = match colorimetry.matrix() {
+            gst_video::VideoColorMatrix::Bt601 | gst_video::VideoColorMatrix::Smpte240m => {
+                YuvMatrix::Bt601
+            }
+            gst_video::VideoColorMatrix::Bt2020 => YuvMatrix::Bt2020,
+            _ => YuvMatrix::Bt709,
+        };
+        let transfer = match colorimetry.transfer() {
+            gst_video::VideoTransferFunction::Smpte2084 => Transfer::Pq,
+            gst_video::VideoTransferFunction::AribStdB67 => Transfer::Hlg,
+            _ => Transfer::Sdr,
+        };
+        Self {
+            matrix,
+            transfer,
+            bt2020_primaries: colorimetry.primaries() == gst_video::VideoColorPrimaries::Bt2020,
+            full_range: colorimetry.range() == gst_video::VideoColorRange::Range0_255,
+        }
+    }
+}
+
+/// HDR signalling of the current stream: its colorimetry plus the static metadata (SMPTE
+/// ST 2086 mastering display and CTA-861.3 light levels) carried on the caps, when present.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct HdrMetadata {
+    /// Maximum content light level in cd/m².
+    pub max_cll: Option<u32>,
+    /// Maximum frame-average light level in cd/m².
+    pub max_fall: Option<u32>,
+    /// Luminance range `(min, max)` of the mastering display in cd/m².
+    pub mastering_luminance: Option<(f64, f64)>,
+    pub primaries: gst_video::VideoColorPrimaries,
+    pub transfer: gst_video::VideoTransferFunction,
+}
+
+impl HdrMetadata {
+    /// Metadata of PQ and HLG streams; SDR transfers yield `None`.
+    pub(crate) fn from_caps(
+        caps: &gst::CapsRef,
+        colorimetry: &gst_video::VideoColorimetry,
+    ) -> Option<Self> {
+        let transfer = colorimetry.transfer();
+        if !matches!(
+            transfer,
+            gst_video::VideoTransferFunction::Smpte2084
+                | gst_video::VideoTransferFunction::AribStdB67
+        ) {
+            return None;
+        }
+        let field = |name: &str| caps.structure(0).and_then(|s| s.get::<String>(name).ok());
+        let (max_cll, max_fall) = field("content-light-level")
+            .map(|level| parse_content_light_level(&level))
+            .unwrap_or_default();
+        Some(Self {
+            max_cll,
+            max_fall,
+            mastering_luminance: field("mastering-display-info")
+                .and_then(|info| parse_mastering_luminance(&info)),
+            primaries: colorimetry.primaries(),
+            transfer,
+        })
+    }
+}
+
+/// `max_cll:max_fall` as GStreamer serializes `content-light-level`. Zero means unknown.
+fn parse_content_light_level(level: &str) -> (Option<u32>, Option<u32>) {
+    let mut values = level
+        .split(':')
+        .map(|value| value.trim().parse::<u32>().ok().filter(|v| *v > 0));
+    (values.next().flatten(), values.next().flatten())
+}
+
+/// Luminance range from `mastering-display-info`: the display primaries and white point
+/// followed by its maximum and minimum luminance, in units of 0.0001 cd/m².
+fn parse_mastering_luminance(info: &str) -> Option<(f64, f64)> {
+    let values = info
+        .split(':')
+        .map(|value| value.trim().parse::<u32>().ok())
+        .collect::<Option<Vec<_>>>()?;
+    let [_, _, _, _, _, _, _, _, max, min] = values[..] else {
+        return None;
+    };
+    (max > 0).then(|| (min as f64 / 10_000.0, max as f64 / 10_000.0))
+}
+
+/// Peak luminance assumed for PQ and HLG sources, in cd/m². HDR10 is usually mastered at
+/// 1000 nits and HLG's nominal display peak is the same.
+const SOURCE_PEAK_NITS: f32 = 1000.0;
+
+/// Mirror of `VideoUniforms` in shader.wgsl (binding 4), 112 bytes with no padding.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) struct VideoUniforms {
+    // Rows of the YCbCr -> R'G'B' matrix, applied to (Y, Cb, Cr) after range expansion
+    color_matrix_r: [f32; 4],
+    color_matrix_g: [f32; 4],
+    color_matrix_b: [f32; 4],
+    // (offset, scale) for luma and chroma samples
+    range_y: [f32; 2],
+    range_uv: [f32; 2],
+    // (source peak nits, target nits, peak relative to target, unused)
+    tone_map_params: [f32; 4],
+    // (algorithm id, unused...)
+    algorithm_params: [f32; 4],
+    // (transfer id: 0 SDR / 1 PQ / 2 HLG, BT.2020 primaries flag, unused, unused)
+    transfer_func_info: [f32; 4],
+}
+
+impl VideoUniforms {
+    pub(crate) fn new(color: ColorInfo, config: &ToneMappingConfig) -> Self {
+        let (kr, kb) = color.matrix.kr_kb();
+        let kg = 1.0 - kr - kb;
+
+        // Samples are normalized to 0..1 (high-bit-depth formats are MSB-aligned), so the
+        // limited-range offsets are the same for 8 and 10-bit content
+        let (range_y, range_uv) = if color.full_range {
+            ([0.0, 1.0], [0.5, 1.0])
+        } else {
+            ([16.0 / 255.0, 255.0 / 219.0], [0.5, 255.0 / 224.0])
+        };
+
+        let target_nits = config.target_nits.max(1.0);
+        let transfer = match color.transfer {
+            Transfer::Sdr => 0.0,
+            Transfer::Pq => 1.0,
+            Transfer::Hlg => 2.0,
+        };
+
+        Self {
+            color_matrix_r: [1.0, 0.0, 2.0 * (1.0 - kr), 0.0],
+            color_matrix_g: [
+                1.0,
+                -2.0 * kb * (1.0 - kb) / kg,
+                -2.0 * kr * (1.0 - kr) / kg,
+                0.0,
+            ],
+            color_matrix_b: [1.0, 2.0 * (1.0 - kb), 0.0, 0.0],
+            range_y,
+            range_uv,
+            tone_map_params: [
+                SOURCE_PEAK_NITS,
+                target_nits,
+                (SOURCE_PEAK_NITS / target_nits).max(1.0),
+                0.0,
+            ],
+            algorithm_params: [config.algorithm.shader_id(), 0.0, 0.0, 0.0],
+            transfer_func_info: [
+                transfer,
+                if color.bt2020_primaries { 1.0 } else { 0.0 },
+                0.0,
+                0.0,
+            ],
+        }
+    }
+
+    pub(crate) fn as_bytes(&self) -> &[u8] {
+        // SAFETY: repr(C) struct made only of f32 arrays, so it has no padding
+        unsafe {
+            std::slice::from_raw_parts(
+                self as *const Self as *const u8,
+                std::mem::size_of::<Self>(),
+            )
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn video_uniforms_match_shader_layout() {
+        assert_eq!(std::mem::size_of::<VideoUniforms>(), 112);
+        let uniforms = VideoUniforms::new(ColorInfo::default(), &ToneMappingConfig::default());
+        assert_eq!(uniforms.as_bytes().len(), 112);
+    }
+
+    #[test]
+    fn bt709_matrix_matches_reference_coefficients() {
+        let uniforms = VideoUniforms::new(ColorInfo::default(), &ToneMappingConfig::default());
+        let close = |a: f32, b: f32| (a - b).abs() < 1e-3;
+        assert!(close(uniforms.color_matrix_r[2], 1.5748));
+        assert!(close(uniforms.color_matrix_g[1], -0.1873));
+        assert!(close(uniforms.color_matrix_g[2], -0.4681));
+        assert!(close(uniforms.color_matrix_b[1], 1.8556));
+    }
+
+    #[test]
+    fn hdr10_static_metadata_is_parsed() {
+        assert_eq!(
+            parse_content_light_level("1000:400"),
+            (Some(1000), Some(400))
+        );
+        assert_eq!(parse_content_light_level("0:0"), (None, None));
+        assert_eq!(parse_content_light_level("bogus"), (None, None));
+
+        // BT.2020 primaries and D65, mastered between 0.005 and 1000 cd/m²
+        let info = "35400:14600:8500:39850:6550:2300:15635:16450:10000000:50";
+        assert_eq!(parse_mastering_luminance(info), Some((0.005, 1000.0)));
+        assert_eq!(parse_mastering_luminance("1:2:3"), None);
+        assert_eq!(parse_mastering_luminance(""), None);
+    }
+
+    #[test]
+    fn pq_source_peak_is_relative_to_target() {
+        let color = ColorInfo {
+            matrix: YuvMatrix::Bt2020,
+            transfer: Transfer::Pq,
+            bt2020_primaries: true,
+            full_range: false,
+        };
+        let config = ToneMappingConfig {
+            target_nits: 100.0,
+            algorithm: ToneMapAlgo::Reinhard,
+        };
+        let uniforms = VideoUniforms::new(color, &config);
+        assert_eq!(uniforms.tone_map_params[2], 10.0);
+        assert_eq!(uniforms.algorithm_params[0], 1.0);
+        assert_eq!(uniforms.transfer_func_info, [1.0, 1.0, 0.0, 0.0]);
+    }
+}
@@ -1,28 +1,305 @@
-use crate::internal::Internal;
+use crate::adaptive::{
+    AdaptiveVariant, MAX_MANIFEST_BYTES, find_adaptive_demuxer, is_adaptive_demuxer,
+    limit_demuxer_bitrate, parse_manifest,
+};
+use crate::frame_buffer::{Frame, FrameBuffer};
+use crate::internal::{Internal, Readiness, plain_subtitle_text, time_to_frame};
+use crate::pixel_format::VideoPixelFormat;
+use crate::render_pipeline::{FrameLayout, ScalingFilter, copy_nv12_planes, frame_to_rgba};
+use crate::tone_mapping::{ColorInfo, HdrMetadata, ToneMappingConfig};
+use crate::video_player::PlayerEvent;
+use crate::video_props::VideoProps;
 use gstreamer as gst;
 use gstreamer::prelude::*;
 use gstreamer_app as gst_app;
+use gstreamer_video as gst_video;
+use std::future::Future;
+use std::num::NonZeroU8;
 use std::ops::{Deref, DerefMut};
+use std::pin::Pin;
 use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
-use std::sync::{Arc, Mutex, RwLock};
+use std::sync::{Arc, Mutex, RwLock, mpsc};
+use std::task::{Context, Poll};
 use std::time::{Duration, Instant};
 use subwave_core::Error;
-use subwave_core::video::types::{AudioTrack, Position, SubtitleTrack, VideoProperties};
+use subwave_core::audio::make_audio_sink;
+use subwave_core::gstplayflags::gst_play_flags::GstPlayFlags;
+use subwave_core::rtsp::RtspConfig;
+use subwave_core::video::elements::{
+    adopt_clock, find_factory_by_klass, lock_base_time, pipeline_clock, query_seekable,
+};
+use subwave_core::video::streams::StreamIds;
+use subwave_core::video::thumbnail::Thumbnail;
+use subwave_core::video::types::{
+    AudioLevels, AudioTrack, BufferConfig, DecoderPreference, DeinterlaceMode, FrameStats,
+    MediaMetadata, PlaybackStatus, Position, SubtitleTrack, TrickMode, VideoProperties, VideoTrack,
+    next_video_id, video_log_target,
+};
 use subwave_core::video::video_trait::Video;
 
+/// How often `level` and `spectrum` post while audio analysis is enabled.
+const AUDIO_ANALYSIS_INTERVAL: Duration = Duration::from_millis(50);
+
 /// A multimedia video loaded from a URI (e.g., a local file path or HTTP stream).
 #[derive(Debug)]
 pub struct AppsinkVideo(pub(crate) RwLock<Internal>);
 
+/// Queueing behaviour of the appsink that hands decoded frames to the renderer.
+///
+/// The default suits playback: frames are shown in sync with the clock and, once eight are
+/// queued, the oldest is dropped. `sync: false` with a high `max_buffers` and `drop: false`
+/// never loses a frame, e.g. for stepping through footage in an editor, at the cost of
+/// latency and memory; live sources want a shallow queue instead.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SinkConfig {
+    /// Frames queued before the appsink drops or blocks (`max-buffers`, 0 is unlimited).
+    pub max_buffers: u32,
+    /// Drop the oldest frame when the queue is full instead of blocking upstream (`drop`).
+    pub drop: bool,
+    /// Release frames in sync with the pipeline clock (`sync`).
+    pub sync: bool,
+}
+
+impl Default for SinkConfig {
+    fn default() -> Self {
+        Self {
+            max_buffers: 8,
+            drop: true,
+            sync: true,
+        }
+    }
+}
+
+impl SinkConfig {
+    fn apply_to_appsink(&self, appsink: &gst_app::AppSink) {
+        appsink.set_property("max-buffers", self.max_buffers);
+        appsink.set_property("drop", self.drop);
+        appsink.set_property("sync", self.sync);
+    }
+
+    fn from_appsink(appsink: &gst_app::AppSink) -> Self {
+        Self {
+            max_buffers: appsink.property("max-buffers"),
+            drop: appsink.property("drop"),
+            sync: appsink.property("sync"),
+        }
+    }
+}
+
+/// Look of text subtitles rendered by playbin3's overlay. Bitmap subtitles (PGS, DVB) are
+/// drawn as authored.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SubtitleStyle {
+    /// Pango font description such as `"Sans Bold 36"`; `None` restores playbin3's default,
+    /// which is small on 4K screens.
+    pub font_desc: Option<String>,
+    /// Text color as ARGB (`0xAARRGGBB`).
+    pub color: u32,
+    /// Outline color as ARGB.
+    pub outline_color: u32,
+    /// Draw a darkened box behind the text.
+    pub shaded_background: bool,
+}
+
+impl Default for SubtitleStyle {
+    /// The overlay's own defaults: white text with a black outline.
+    fn default() -> Self {
+        Self {
+            font_desc: None,
+            color: 0xFFFF_FFFF,
+            outline_color: 0xFF00_0000,
+            shaded_background: false,
+        }
+    }
+}
+
+impl SubtitleStyle {
+    /// Whether `element` is a text overlay this style applies to.
+    fn applies_to(element: &gst::Element) -> bool {
+        element.has_property("shaded-background") && element.has_property("outline-color")
+    }
+
+    fn apply_to_overlay(&self, overlay: &gst::Element) {
+        overlay.set_property("color", self.color);
+        overlay.set_property("outline-color", self.outline_color);
+        overlay.set_property("shaded-background", self.shaded_background);
+    }
+}
+
+/// Everything set on a video through its API, carried over to the pipeline
+/// [`AppsinkVideo::reload`] builds. A [`new_with_setup`](AppsinkVideo::new_with_setup)
+/// closure has run by then and cannot be repeated.
+///
+/// Playbin properties go onto the new pipeline before it prerolls; the rest is restored
+/// once it has been swapped in.
+struct UserConfig {
+    http_headers: Option<gst::Context>,
+    buffer_duration: i64,
+    buffer_size: i32,
+    suburi: Option<String>,
+    play_flags: GstPlayFlags,
+    sink_config: Option<SinkConfig>,
+    volume: f64,
+    muted: bool,
+    audio_sink_device: Option<String>,
+    rtsp: RtspConfig,
+    decoder_preference: DecoderPreference,
+    clock: Option<gst::Clock>,
+    base_time: Option<gst::ClockTime>,
+    next_uri: Option<url::Url>,
+    pitch_correction: bool,
+    // Whether the normalization chain is in the audio filter, and whether it is applied
+    normalization_chain: bool,
+    audio_normalization: bool,
+    audio_analysis_bands: Option<u32>,
+    deinterlace_mode: DeinterlaceMode,
+    pixel_aspect: Option<(i32, i32)>,
+    subtitle_style: SubtitleStyle,
+    connection_speed: Option<u64>,
+    tone_mapping: ToneMappingConfig,
+    scaling_filter: ScalingFilter,
+    scrub_mute: bool,
+    scrub_mute_window: Duration,
+    trick_mode: TrickMode,
+    bus_taps: Option<Arc<Mutex<Vec<mpsc::Sender<gst::Message>>>>>,
+}
+
+impl UserConfig {
+    fn capture(video: &AppsinkVideo) -> Self {
+        let muted = video.muted();
+        let subtitle_style = video.subtitle_style();
+        let inner = video.read();
+        Self {
+            http_headers: inner.source.context("http-headers"),
+            buffer_duration: inner.source.property("buffer-duration"),
+            buffer_size: inner.source.property("buffer-size"),
+            suburi: inner.source.property("suburi"),
+            play_flags: inner.source.property("flags"),
+            sink_config: inner
+                .source
+                .property::<Option<gst::Element>>("video-sink")
+                .and_then(|sink| sink.downcast::<gst::Bin>().ok())
+                .and_then(|bin| bin.by_name("subwave_appsink"))
+                .and_then(|sink| sink.downcast::<gst_app::AppSink>().ok())
+                .map(|appsink| SinkConfig::from_appsink(&appsink)),
+            volume: inner.source.property("volume"),
+            muted,
+            audio_sink_device: inner.audio_sink_device.clone(),
+            rtsp: inner.rtsp_config,
+            decoder_preference: inner.decoder_preference,
+            clock: inner.clock.clone(),
+            base_time: inner.base_time,
+            next_uri: inner.next_uri.lock().expect("lock next uri").clone(),
+            pitch_correction: inner.pitch_correction_enabled(),
+            normalization_chain: inner.normalization_gain.is_some(),
+            audio_normalization: inner.audio_normalization,
+            audio_analysis_bands: inner.audio_analysis_bands,
+            deinterlace_mode: inner.deinterlace_mode,
+            pixel_aspect: inner.pixel_aspect_override,
+            subtitle_style,
+            connection_speed: inner.pinned_connection_speed,
+            tone_mapping: inner.tone_mapping,
+            scaling_filter: inner.scaling_filter,
+            scrub_mute: inner.scrub_mute,
+            scrub_mute_window: inner.scrub_mute_window,
+            trick_mode: inner.trick_mode,
+            bus_taps: inner.bus_taps.clone(),
+        }
+    }
+
+    /// Set the playbin properties that are only picked up before the first preroll.
+    fn apply_to_playbin(
+        &self,
+        pipeline: &gst::Pipeline,
+        video_sink: &gst_app::AppSink,
+        log_target: &str,
+    ) -> Result<(), Error> {
+        if let Some(headers) = &self.http_headers {
+            pipeline.set_context(headers);
+        }
+        pipeline.set_property("buffer-duration", self.buffer_duration);
+        pipeline.set_property("buffer-size", self.buffer_size);
+        pipeline.set_property("suburi", self.suburi.as_deref());
+        pipeline.set_property("flags", self.play_flags);
+        pipeline.set_property("volume", self.volume);
+        pipeline.set_property("mute", self.muted);
+        if let Some(sink_config) = self.sink_config {
+            sink_config.apply_to_appsink(video_sink);
+        }
+        if let Some(device) = &self.audio_sink_device {
+            pipeline.set_property("audio-sink", &make_audio_sink(device)?);
+        }
+        self.decoder_preference
+            .apply_to_playbin(pipeline.upcast_ref());
+        if let Some(clock) = &self.clock {
+            pipeline.use_clock(Some(clock));
+        }
+        if let Some(base_time) = self.base_time {
+            lock_base_time(pipeline, base_time);
+        }
+        if self.normalization_chain || self.audio_analysis_bands.is_some() || !self.pitch_correction
+        {
+            let filter = AppsinkVideo::build_audio_filter(
+                self.pitch_correction,
+                self.normalization_chain,
+                self.audio_analysis_bands,
+                log_target,
+            )?;
+            pipeline.set_property("audio-filter", &filter);
+        }
+        Ok(())
+    }
+
+    /// Restore the rest on `video` once its new pipeline has been swapped in.
+    fn restore(self, video: &mut AppsinkVideo) -> Result<(), Error> {
+        if self.deinterlace_mode != DeinterlaceMode::default() {
+            video.set_deinterlace(self.deinterlace_mode)?;
+        }
+        if self.pixel_aspect.is_some() {
+            video.set_pixel_aspect_ratio(self.pixel_aspect)?;
+        }
+        video.set_subtitle_style(self.subtitle_style);
+        if let Some(bits_per_sec) = self.connection_speed {
+            video.set_connection_speed(bits_per_sec);
+        }
+
+        let mut inner = video.get_mut();
+        inner.audio_sink_device = self.audio_sink_device;
+        inner.rtsp_config = self.rtsp;
+        inner.decoder_preference = self.decoder_preference;
+        inner.clock = self.clock;
+        inner.base_time = self.base_time;
+        *inner.next_uri.lock().expect("lock next uri") = self.next_uri;
+        inner.normalization_gain = inner.audio_filter_element("normalization-gain");
+        inner.normalization_limiter = inner.audio_filter_element("normalization-limiter");
+        inner.audio_normalization = self.audio_normalization;
+        inner.audio_analysis_bands = self.audio_analysis_bands;
+        inner.apply_normalization_gain();
+        inner.tone_mapping = self.tone_mapping;
+        inner.scaling_filter = self.scaling_filter;
+        inner.scrub_mute = self.scrub_mute;
+        inner.scrub_mute_window = self.scrub_mute_window;
+        inner.trick_mode = self.trick_mode;
+        if let Some(taps) = self.bus_taps {
+            AppsinkVideo::install_bus_taps(&inner.bus, Arc::clone(&taps));
+            inner.bus_taps = Some(taps);
+        }
+        Ok(())
+    }
+}
+
 impl AppsinkVideo {
     fn build_pipeline_with_headers_vec(
         uri: &url::Url,
         headers: Option<&[(String, String)]>,
+        buffering: &BufferConfig,
+        rtsp: &RtspConfig,
+        log_target: &str,
     ) -> Result<(gst::Pipeline, gst_app::AppSink), Error> {
-        let video_sink_bin = match Self::build_video_sink() {
+        let video_sink_bin = match Self::build_video_sink(log_target) {
             Ok(sink) => sink,
             Err(_) => gst::parse::bin_from_description(
-                "videoconvertscale n-threads=0 ! appsink name=subwave_appsink drop=true caps=\"video/x-raw,format=(string){NV12},pixel-aspect-ratio=1/1\"",
+                "videoconvertscale n-threads=0 ! appsink name=subwave_appsink drop=true caps=\"video/x-raw,format=(string){NV12,P010_10LE},pixel-aspect-ratio=1/1\"",
                 true,
             )?
             .upcast(),
@@ -38,20 +315,40 @@ impl AppsinkVideo {
             .downcast::<gst::Pipeline>()
             .map_err(|_| Error::Cast)?;
 
-        // Apply http-headers context before any state transitions
+        buffering.apply_to_playbin(pipeline.upcast_ref());
+
+        if subwave_core::rtsp::is_rtsp_uri(uri) {
+            log::info!(
+                target: log_target,
+                "RTSP source detected, latency={}ms protocols={:?}",
+                rtsp.latency_ms,
+                rtsp.protocols
+            );
+            subwave_core::rtsp::set_rtsp_config_on_pipeline(&pipeline, *rtsp);
+        }
+
+        // Apply http-headers context before any state transitions. souphttpsrc is created
+        // before it can query the context, so also copy it onto the source properties
         if let Some(h) = headers {
             subwave_core::http::set_http_headers_on_pipeline(&pipeline, h);
         }
+        subwave_core::http::apply_http_headers_on_source_setup(&pipeline);
 
         // Add scaletempo for pitch correction during variable playback speed
-        if let Ok(scaletempo) = gst::ElementFactory::make("scaletempo")
-            .name("pitch-corrector")
-            .build()
-        {
-            pipeline.set_property("audio-filter", &scaletempo);
-            log::info!("Enabled pitch correction for variable playback speed");
-        } else {
-            log::warn!("scaletempo element not available - pitch correction disabled");
+        match Self::build_audio_filter(true, false, None, log_target) {
+            Ok(filter) => {
+                pipeline.set_property("audio-filter", &filter);
+                log::info!(
+                    target: log_target,
+                    "Enabled pitch correction for variable playback speed"
+                );
+            }
+            Err(_) => {
+                log::warn!(
+                    target: log_target,
+                    "scaletempo element not available - pitch correction disabled"
+                )
+            }
         }
 
         let video_sink_opt: Option<gst::Element> = pipeline.property("video-sink");
@@ -65,11 +362,200 @@ impl AppsinkVideo {
             .ok_or(Error::Cast)?
             .downcast::<gst_app::AppSink>()
             .map_err(|_| Error::Cast)?;
+        SinkConfig::default().apply_to_appsink(&video_sink);
 
         Ok((pipeline, video_sink))
     }
+
+    /// Copy the text of each subtitle buffer entering playsink's `subtitleoverlay`, for
+    /// embedded and external (`suburi`) text subtitles alike. The overlay keeps rendering
+    /// them; a gap or flush clears the text.
+    fn watch_subtitle_text(
+        pipeline: &gst::Pipeline,
+        text: Arc<Mutex<Option<String>>>,
+        changed: Arc<AtomicBool>,
+    ) {
+        pipeline.connect_deep_element_added(move |_pipeline, _bin, element| {
+            let is_overlay = element
+                .factory()
+                .is_some_and(|factory| factory.name() == "subtitleoverlay");
+            let Some(pad) = is_overlay
+                .then(|| element.static_pad("subtitle_sink"))
+                .flatten()
+            else {
+                return;
+            };
+            let text = Arc::clone(&text);
+            let changed = Arc::clone(&changed);
+            pad.add_probe(
+                gst::PadProbeType::BUFFER | gst::PadProbeType::EVENT_DOWNSTREAM,
+                move |_pad, info| {
+                    let cue = match &info.data {
+                        Some(gst::PadProbeData::Buffer(buffer)) => {
+                            let Ok(map) = buffer.map_readable() else {
+                                return gst::PadProbeReturn::Ok;
+                            };
+                            let cue = plain_subtitle_text(&String::from_utf8_lossy(&map));
+                            (!cue.is_empty()).then_some(cue)
+                        }
+                        Some(gst::PadProbeData::Event(event))
+                            if matches!(
+                                event.type_(),
+                                gst::EventType::Gap | gst::EventType::FlushStop
+                            ) =>
+                        {
+                            None
+                        }
+                        _ => return gst::PadProbeReturn::Ok,
+                    };
+                    if let Ok(mut current) = text.lock()
+                        && *current != cue
+                    {
+                        *current = cue;
+                        changed.store(true, Ordering::SeqCst);
+                    }
+                    gst::PadProbeReturn::Ok
+                },
+            );
+        });
+    }
+
+    /// Style every text overlay playsink creates, including the ones made anew when the
+    /// subtitle track changes, with the current `style`.
+    fn watch_subtitle_overlays(pipeline: &gst::Pipeline, style: Arc<Mutex<SubtitleStyle>>) {
+        pipeline.connect_deep_element_added(move |_pipeline, _bin, element| {
+            if SubtitleStyle::applies_to(element)
+                && let Ok(style) = style.lock()
+            {
+                style.apply_to_overlay(element);
+            }
+        });
+    }
+
+    /// Keep a copy of the manifest each HLS or DASH demuxer reads from its sink pad, which
+    /// lists the variants the demuxer can switch between.
+    fn watch_adaptive_manifest(pipeline: &gst::Pipeline, manifest: Arc<Mutex<Vec<u8>>>) {
+        pipeline.connect_deep_element_added(move |_pipeline, _bin, element| {
+            let Some(pad) = is_adaptive_demuxer(element)
+                .then(|| element.static_pad("sink"))
+                .flatten()
+            else {
+                return;
+            };
+            if let Ok(mut manifest) = manifest.lock() {
+                manifest.clear();
+            }
+            let manifest = Arc::clone(&manifest);
+            pad.add_probe(gst::PadProbeType::BUFFER, move |_pad, info| {
+                if let Some(gst::PadProbeData::Buffer(buffer)) = &info.data
+                    && let Ok(map) = buffer.map_readable()
+                    && let Ok(mut manifest) = manifest.lock()
+                    && manifest.len() + map.len() <= MAX_MANIFEST_BYTES
+                {
+                    manifest.extend_from_slice(&map);
+                }
+                gst::PadProbeReturn::Ok
+            });
+        });
+    }
+
+    /// Audio filter for playbin: `scaletempo` (or `identity` without pitch correction) alone,
+    /// or a bin of `[scaletempo] ! audioconvert ! [normalization] ! [analysis] ! audioconvert`
+    /// where normalization is `[ebur128level] ! volume ! [rglimiter]` and analysis is
+    /// `level ! spectrum`.
+    ///
+    /// The loudness meter sits before the gain so it measures the source, while the analysis
+    /// elements come last so meters show what is actually heard.
+    fn build_audio_filter(
+        pitch_correction: bool,
+        normalization: bool,
+        analysis_bands: Option<u32>,
+        log_target: &str,
+    ) -> Result<gst::Element, Error> {
+        let scaletempo = if pitch_correction {
+            Some(
+                gst::ElementFactory::make("scaletempo")
+                    .name("pitch-corrector")
+                    .build()?,
+            )
+        } else {
+            None
+        };
+        if !normalization && analysis_bands.is_none() {
+            return match scaletempo {
+                Some(scaletempo) => Ok(scaletempo),
+                None => Ok(gst::ElementFactory::make("identity").build()?),
+            };
+        }
+
+        let optional = |factory: &str, name: &str| {
+            let element = gst::ElementFactory::make(factory).name(name).build().ok();
+            if element.is_none() {
+                log::warn!(target: log_target, "{} not available - {} disabled", factory, name);
+            }
+            element
+        };
+        let mut elements: Vec<gst::Element> = scaletempo.into_iter().collect();
+        elements.push(gst::ElementFactory::make("audioconvert").build()?);
+        if normalization {
+            elements.extend(optional("ebur128level", "loudness-meter"));
+            elements.push(
+                gst::ElementFactory::make("volume")
+                    .name("normalization-gain")
+                    .build()?,
+            );
+            elements.extend(optional("rglimiter", "normalization-limiter"));
+        }
+        if let Some(bands) = analysis_bands {
+            let interval = AUDIO_ANALYSIS_INTERVAL.as_nanos() as u64;
+            elements.push(
+                gst::ElementFactory::make("level")
+                    .name("audio-level")
+                    .property("interval", interval)
+                    .property("post-messages", true)
+                    .build()?,
+            );
+            elements.push(
+                gst::ElementFactory::make("spectrum")
+                    .name("audio-spectrum")
+                    .property("bands", bands)
+                    .property("interval", interval)
+                    .property("post-messages", true)
+                    .build()?,
+            );
+        }
+        elements.push(gst::ElementFactory::make("audioconvert").build()?);
+
+        let bin = gst::Bin::builder().name("audio-filter").build();
+        bin.add_many(&elements)?;
+        gst::Element::link_many(&elements)?;
+        let sink = elements[0].static_pad("sink").ok_or(Error::Cast)?;
+        let src = elements[elements.len() - 1]
+            .static_pad("src")
+            .ok_or(Error::Cast)?;
+        bin.add_pad(&gst::GhostPad::with_target(&sink)?)?;
+        bin.add_pad(&gst::GhostPad::with_target(&src)?)?;
+        Ok(bin.upcast())
+    }
+
+    /// Caps accepted by the appsink: [`VideoPixelFormat::NEGOTIATED`] in system memory.
+    /// 10-bit content falls back to NV12 if P010 cannot be negotiated.
+    ///
+    /// DMABuf is deliberately not offered. Importing the fd needs Vulkan external-memory
+    /// extensions on the wgpu device, which iced creates without them, so DMABuf frames
+    /// could only be mapped back and copied like system memory.
+    fn appsink_caps() -> gst::Caps {
+        gst::Caps::builder("video/x-raw")
+            .field(
+                "format",
+                gst::List::new(VideoPixelFormat::NEGOTIATED.map(|format| format.gst_name())),
+            )
+            .field("pixel-aspect-ratio", gst::Fraction::new(1, 1))
+            .build()
+    }
+
     /// Creates a video sink bin with proper buffering for network streams
-    fn build_video_sink() -> Result<gst::Element, Error> {
+    fn build_video_sink(log_target: &str) -> Result<gst::Element, Error> {
         let bin = gst::Bin::builder().name("video-sink-bin").build();
 
         // Insert a buffering queue to decouple upstream reconfiguration (e.g., enabling subtitles)
@@ -88,75 +574,110 @@ impl AppsinkVideo {
         //        Error::Cast
         //    })?;
 
+        // Deinterlacer stays in the bin for the lifetime of the pipeline; its mode is switched at
+        // runtime (see `set_deinterlace`), which avoids relinking the bin while data is flowing.
+        let deinterlace = match gst::ElementFactory::make("deinterlace")
+            .name("deinterlacer")
+            .build()
+        {
+            Ok(element) => {
+                element.set_property_from_str("mode", DeinterlaceMode::default().as_gst_nick());
+                Some(element)
+            }
+            Err(e) => {
+                log::warn!(
+                    target: log_target,
+                    "deinterlace element not available - deinterlacing disabled: {:?}",
+                    e
+                );
+                None
+            }
+        };
+
+        // Rewrites the pixel-aspect-ratio of the incoming caps when the stream's is wrong (see
+        // `set_pixel_aspect_ratio`); a bare `video/x-raw` leaves the caps untouched
+        let par_override = match gst::ElementFactory::make("capssetter")
+            .name("par-override")
+            .property("caps", gst::Caps::new_empty_simple("video/x-raw"))
+            .build()
+        {
+            Ok(element) => Some(element),
+            Err(e) => {
+                log::warn!(
+                    target: log_target,
+                    "capssetter element not available - aspect override disabled: {:?}",
+                    e
+                );
+                None
+            }
+        };
+
         let videoconvertscale = gst::ElementFactory::make("videoconvertscale")
             .property("n-threads", 0u32) // Use multiple threads for conversion
             //.property("add-borders", true)
             //.property("disable-passthrough", false)
             .build()
             .map_err(|e| {
-                log::error!("Failed to create videoconvertscale: {:?}", e);
+                log::error!(target: log_target, "Failed to create videoconvertscale: {:?}", e);
                 Error::Cast
             })?;
 
+        // Queueing properties are set from `SinkConfig` once the pipeline is built
         let appsink = gst::ElementFactory::make("appsink")
             .name("subwave_appsink")
-            .property("drop", true)
-            .property("max-buffers", 8u32)
-            .property("sync", true)
             .property("enable-last-sample", false)
-            .property(
-                "caps",
-                gst::Caps::builder("video/x-raw")
-                    .field("format", gst::List::new(["NV12"]))
-                    .field("pixel-aspect-ratio", gst::Fraction::new(1, 1))
-                    .build(),
-            )
+            .property("caps", Self::appsink_caps())
             .build()
             .map_err(|e| {
-                log::error!("Failed to create appsink: {:?}", e);
+                log::error!(target: log_target, "Failed to create appsink: {:?}", e);
                 Error::Cast
             })?;
 
+        let elements: Vec<&gst::Element> = deinterlace
+            .iter()
+            .chain(par_override.iter())
+            .chain([&videoconvertscale, &appsink])
+            .collect();
+
         // Add elements to bin
-        bin.add_many([(&videoconvertscale), &appsink])
-            .map_err(|e| {
-                log::error!("Failed to add elements to bin: {:?}", e);
-                Error::Cast
-            })?;
+        bin.add_many(elements.iter().copied()).map_err(|e| {
+            log::error!(target: log_target, "Failed to add elements to bin: {:?}", e);
+            Error::Cast
+        })?;
 
-        // Link elements: queue2 -> convert/scale -> appsink
-        gst::Element::link_many([(&videoconvertscale), &appsink]).map_err(|e| {
-            log::error!("Failed to link elements: {:?}", e);
+        // Link elements: deinterlace -> PAR override -> convert/scale -> appsink
+        gst::Element::link_many(elements.iter().copied()).map_err(|e| {
+            log::error!(target: log_target, "Failed to link elements: {:?}", e);
             Error::Cast
         })?;
 
-        // Create ghost pad targeting the queue2 sink so upstream can feed into the buffer
-        let sink_pad = videoconvertscale.static_pad("sink").ok_or_else(|| {
-            log::error!("Failed to get sink pad from queue2");
+        // Create ghost pad targeting the first element so upstream can feed into the chain
+        let sink_pad = elements[0].static_pad("sink").ok_or_else(|| {
+            log::error!(target: log_target, "Failed to get sink pad from {}", elements[0].name());
             Error::Cast
         })?;
 
         let ghost_pad = gst::GhostPad::with_target(&sink_pad).map_err(|e| {
-            log::error!("Failed to create ghost pad: {:?}", e);
+            log::error!(target: log_target, "Failed to create ghost pad: {:?}", e);
             Error::Cast
         })?;
 
         ghost_pad.set_active(true).map_err(|e| {
-            log::error!("Failed to activate ghost pad: {:?}", e);
+            log::error!(target: log_target, "Failed to activate ghost pad: {:?}", e);
             Error::Cast
         })?;
 
         bin.add_pad(&ghost_pad).map_err(|e| {
-            log::error!("Failed to add ghost pad to bin: {:?}", e);
+            log::error!(target: log_target, "Failed to add ghost pad to bin: {:?}", e);
             Error::Cast
         })?;
 
-        log::debug!("Successfully created video sink bin");
+        log::debug!(target: log_target, "Successfully created video sink bin");
         Ok(bin.upcast())
     }
 
     /// Creates a new video based on an existing GStreamer pipeline and appsink.
-    /// Expects an `appsink` plugin with `caps=video/x-raw,format=NV12`.
+    /// Expects an `appsink` plugin with `caps=video/x-raw,format={NV12,P010_10LE}`.
     ///
     /// **Note:** Many functions of [`Video`] assume a `playbin` pipeline.
     /// Non-`playbin` pipelines given here may not have full functionality.
@@ -173,10 +694,28 @@ impl AppsinkVideo {
         pipeline: gst::Pipeline,
         video_sink: gst_app::AppSink,
         initial_state: gst::State,
+    ) -> Result<Self, Error> {
+        Self::from_gst_pipeline_unprepared(
+            next_video_id(),
+            pipeline,
+            video_sink,
+            initial_state,
+            true,
+        )
+    }
+
+    /// Shared constructor for the video with `id`. Without `wait_for_state` the pipeline is
+    /// left to preroll in the background and the duration, seekability and liveness found
+    /// here are only guesses; [`Prepare`] refreshes them once [`Readiness`] settles.
+    fn from_gst_pipeline_unprepared(
+        id: u64,
+        pipeline: gst::Pipeline,
+        video_sink: gst_app::AppSink,
+        initial_state: gst::State,
+        wait_for_state: bool,
     ) -> Result<Self, Error> {
         gst::init()?;
-        static NEXT_ID: AtomicU64 = AtomicU64::new(0);
-        let id = NEXT_ID.fetch_add(1, Ordering::SeqCst);
+        let log_target = video_log_target(id);
 
         // We need to ensure we stop the pipeline if we hit an error,
         // or else there may be audio left playing in the background.
@@ -191,18 +730,39 @@ impl AppsinkVideo {
 
         let pad = video_sink.pads().first().cloned().unwrap();
 
-        log::debug!("Setting pipeline to {:?} state", initial_state);
+        // Connected before the first state change, which is when playsink creates its overlay
+        let subtitle_style = Arc::new(Mutex::new(SubtitleStyle::default()));
+        Self::watch_subtitle_overlays(&pipeline, Arc::clone(&subtitle_style));
+        let adaptive_manifest = Arc::new(Mutex::new(Vec::new()));
+        Self::watch_adaptive_manifest(&pipeline, Arc::clone(&adaptive_manifest));
+        let subtitle_text = Arc::new(Mutex::new(None));
+        let subtitle_text_changed = Arc::new(AtomicBool::new(false));
+        Self::watch_subtitle_text(
+            &pipeline,
+            Arc::clone(&subtitle_text),
+            Arc::clone(&subtitle_text_changed),
+        );
+
+        log::debug!(target: &log_target, "Setting pipeline to {:?} state", initial_state);
+        // Live sources skip preroll and report NoPreroll when asked to pause
+        let mut is_live = false;
         match pipeline.set_state(initial_state) {
             Ok(state_change) => {
-                log::debug!("State change result: {:?}", state_change);
+                log::debug!(target: &log_target, "State change result: {:?}", state_change);
+                is_live |= state_change == gst::StateChangeSuccess::NoPreroll;
             }
             Err(e) => {
-                log::error!("Failed to set pipeline state {:?}: {:?}", initial_state, e);
+                log::error!(
+                    target: &log_target,
+                    "Failed to set pipeline state {:?}: {:?}",
+                    initial_state,
+                    e
+                );
 
                 // Get more details about the error
                 if let Some(bus) = pipeline.bus() {
                     while let Some(msg) = bus.pop() {
-                        log::error!("Bus message: {:?}", msg);
+                        log::error!(target: &log_target, "Bus message: {:?}", msg);
                     }
                 }
 
@@ -211,36 +771,57 @@ impl AppsinkVideo {
         }
 
         // wait for up to 5 seconds until the decoder gets the source capabilities
-        log::debug!("Waiting for pipeline to reach {:?} state", initial_state);
-        let state_result = pipeline.state(gst::ClockTime::from_seconds(5));
-        match state_result {
-            (Ok(state_change), current, pending) => {
-                log::debug!(
-                    "Pipeline state: current={:?}, pending={:?}, change={:?}",
-                    current,
-                    pending,
-                    state_change
-                );
-            }
-            (Err(e), current, pending) => {
-                log::error!(
-                    "Pipeline state error: current={:?}, pending={:?}, error={:?}",
-                    current,
-                    pending,
-                    e
-                );
-                cleanup!(Err(e))?;
+        if wait_for_state {
+            log::debug!(
+                target: &log_target,
+                "Waiting for pipeline to reach {:?} state",
+                initial_state
+            );
+            let state_result = pipeline.state(gst::ClockTime::from_seconds(5));
+            match state_result {
+                (Ok(state_change), current, pending) => {
+                    log::debug!(
+                        target: &log_target,
+                        "Pipeline state: current={:?}, pending={:?}, change={:?}",
+                        current,
+                        pending,
+                        state_change
+                    );
+                    is_live |= state_change == gst::StateChangeSuccess::NoPreroll;
+                }
+                (Err(e), current, pending) => {
+                    log::error!(
+                        target: &log_target,
+                        "Pipeline state error: current={:?}, pending={:?}, error={:?}",
+                        current,
+                        pending,
+                        e
+                    );
+                    cleanup!(Err(e))?;
+                }
             }
         }
 
         // For playbin3 with complex pipelines, caps might not be available immediately
         // We'll start with defaults and update them when we get the first sample
-        log::info!("Deferring video caps extraction until first sample arrives");
-        let (mut width, mut height, mut framerate, has_video) = (1920, 1080, 30.0, true);
+        log::info!(
+            target: &log_target,
+            "Deferring video caps extraction until first sample arrives"
+        );
+        // Playbins with the VIDEO flag cleared (audio-only playback) never link the appsink
+        let has_video = !pipeline.has_property("flags")
+            || pipeline
+                .property::<GstPlayFlags>("flags")
+                .contains(GstPlayFlags::VIDEO);
+        let (mut width, mut height, mut framerate) = if has_video {
+            (1920, 1080, 30.0)
+        } else {
+            (0, 0, 0.0)
+        };
 
         // Try to get initial caps if available
         if let Some(caps) = pad.current_caps() {
-            log::debug!("Initial caps available: {:?}", caps);
+            log::debug!(target: &log_target, "Initial caps available: {:?}", caps);
             if let Some(s) = caps.structure(0)
                 && let (Ok(w), Ok(h), Ok(fr)) = (
                     s.get::<i32>("width"),
@@ -248,10 +829,11 @@ impl AppsinkVideo {
                     s.get::<gst::Fraction>("framerate"),
                 )
             {
-                width = ((w + 4 - 1) / 4) * 4;
+                width = w;
                 height = h;
                 framerate = fr.numer() as f64 / fr.denom() as f64;
                 log::info!(
+                    target: &log_target,
                     "Got initial video properties: {}x{} @ {}fps",
                     width,
                     height,
@@ -259,7 +841,10 @@ impl AppsinkVideo {
                 );
             }
         } else {
-            log::debug!("No initial caps available, will update on first sample");
+            log::debug!(
+                target: &log_target,
+                "No initial caps available, will update on first sample"
+            );
         }
 
         if has_video
@@ -281,43 +866,120 @@ impl AppsinkVideo {
 
         // For network streams, duration might not be available immediately
         if duration.as_secs() == 0 {
-            log::info!("Duration not available yet, will update later");
+            log::info!(target: &log_target, "Duration not available yet, will update later");
+        }
+
+        // Live sources answer the query once prerolled; until then assume only they can't seek
+        let seekable = query_seekable(&pipeline).unwrap_or(!is_live);
+
+        // Some live HTTP sources still preroll; treat an unseekable stream with no duration as live
+        if !is_live && duration.is_zero() {
+            is_live = !seekable;
+        }
+        if is_live {
+            log::info!(target: &log_target, "Detected live source");
         }
 
         let sync_av = pipeline.has_property("av-offset");
+        let uri = pipeline
+            .has_property("uri")
+            .then(|| pipeline.property::<Option<String>>("uri"))
+            .flatten()
+            .and_then(|uri| url::Url::parse(&uri).ok());
+
+        // Gapless playback: when playbin is about to run out of data, hand it the queued URI
+        // so it can preroll the next track without tearing down the pipeline.
+        let next_uri: Arc<Mutex<Option<url::Url>>> = Arc::new(Mutex::new(None));
+        let track_switch_pending = Arc::new(AtomicBool::new(false));
+        if pipeline.has_property("uri") {
+            let next_uri_ref = Arc::clone(&next_uri);
+            let track_switch_pending_ref = Arc::clone(&track_switch_pending);
+            let log_target_ref = log_target.clone();
+            pipeline.connect("about-to-finish", false, move |values| {
+                let playbin = values[0].get::<gst::Element>().ok()?;
+                let next = next_uri_ref.lock().ok()?.take()?;
+                log::info!(target: &log_target_ref, "About to finish, queueing next URI: {}", next);
+                playbin.set_property("uri", next.as_str());
+                track_switch_pending_ref.store(true, Ordering::SeqCst);
+                None
+            });
+        }
 
-        // NV12 = 12bpp
-        let frame = Arc::new(Mutex::new(vec![
-            0u8;
-            (width as usize * height as usize * 3)
-                .div_ceil(2)
-        ]));
+        // NV12 = 12bpp, tightly packed until the first sample tells us the real strides
+        let strides = [width as u32, (width as u32).div_ceil(2) * 2];
+        let (frame, mut frame_writer) = FrameBuffer::new(Frame {
+            data: vec![
+                0u8;
+                strides[0] as usize * height as usize
+                    + strides[1] as usize * (height as u32).div_ceil(2) as usize
+            ],
+            layout: FrameLayout {
+                format: VideoPixelFormat::Nv12,
+                color: ColorInfo::default(),
+                strides,
+            },
+            pts: None,
+        });
         let upload_frame = Arc::new(AtomicBool::new(false));
+        let frames_rendered = Arc::new(AtomicU64::new(0));
+        let frames_dropped = Arc::new(AtomicU64::new(0));
         let alive = Arc::new(AtomicBool::new(true));
+        let readiness = Arc::new(Readiness::default());
+        if wait_for_state {
+            readiness.settle(true);
+        }
         let last_frame_time = Arc::new(Mutex::new(Instant::now()));
 
-        let video_props = Arc::new(Mutex::new(VideoProperties {
+        let video_props = Arc::new(VideoProps::new(VideoProperties {
             width,
             height,
             framerate,
             has_video,
         }));
 
-        // For HDR metadata detection
-        //let hdr_metadata_shared = Arc::new(Mutex::new(None::<HdrMetadata>));
+        let hdr_metadata = Arc::new(Mutex::new(None::<HdrMetadata>));
 
-        let frame_ref = Arc::clone(&frame);
         let upload_frame_ref = Arc::clone(&upload_frame);
+        let frames_rendered_ref = Arc::clone(&frames_rendered);
+        let frames_dropped_ref = Arc::clone(&frames_dropped);
         let alive_ref = Arc::clone(&alive);
+        let readiness_ref = Arc::clone(&readiness);
         let last_frame_time_ref = Arc::clone(&last_frame_time);
         let video_props_ref = Arc::clone(&video_props);
+        let hdr_metadata_ref = Arc::clone(&hdr_metadata);
 
         let pipeline_ref = pipeline.clone();
+        let log_target_ref = log_target.clone();
 
         let worker = std::thread::spawn(move || {
-            let mut caps_checked = false;
+            let mut negotiated_caps: Option<gst::Caps> = None;
+            let mut video_info: Option<gst_video::VideoInfo> = None;
+            let mut pixel_format = VideoPixelFormat::Nv12;
+            let mut color = ColorInfo::default();
+            let mut last_pts: Option<gst::ClockTime> = None;
 
             while alive_ref.load(Ordering::Acquire) {
+                // Audio-only media never delivers a sample, so reaching the target state
+                // also counts as prepared
+                if readiness_ref.get().is_none() {
+                    match pipeline_ref.state(gst::ClockTime::ZERO) {
+                        (Err(_), _, _) => readiness_ref.settle(false),
+                        (Ok(_), current, gst::State::VoidPending)
+                            if current >= gst::State::Paused =>
+                        {
+                            readiness_ref.settle(true)
+                        }
+                        _ => {}
+                    }
+                }
+
+                // Without a linked video stream the appsink is never started and pulls return
+                // at once, so poll at the frame interval instead of spinning
+                if video_sink.current_state() < gst::State::Paused {
+                    std::thread::sleep(Duration::from_millis(16));
+                    continue;
+                }
+
                 if let Err(gst::FlowError::Error) = (|| -> Result<(), gst::FlowError> {
                     let sample =
                         if pipeline_ref.state(gst::ClockTime::ZERO).1 != gst::State::Playing {
@@ -330,9 +992,12 @@ impl AppsinkVideo {
                                 .ok_or(gst::FlowError::Eos)?
                         };
 
-                    // Update video properties from the first sample with caps
-                    if !caps_checked && let Some(caps) = sample.caps() {
-                        log::debug!("Got caps from sample: {:?}", caps);
+                    // Update video properties from the first sample with caps, and again
+                    // whenever they change (adaptive streams switch resolution mid-playback)
+                    if let Some(caps) = sample.caps()
+                        && negotiated_caps.as_deref() != Some(caps)
+                    {
+                        log::debug!(target: &log_target_ref, "Got caps from sample: {:?}", caps);
 
                         if let Some(s) = caps.structure(0)
                             && let (Ok(w), Ok(h), Ok(fr)) = (
@@ -341,29 +1006,47 @@ impl AppsinkVideo {
                                 s.get::<gst::Fraction>("framerate"),
                             )
                         {
-                            let mut props =
-                                video_props_ref.lock().map_err(|_| gst::FlowError::Error)?;
-                            props.width = ((w + 4 - 1) / 4) * 4;
-                            props.height = h;
-                            props.framerate = fr.numer() as f64 / fr.denom() as f64;
-                            props.has_video = true;
+                            let framerate = fr.numer() as f64 / fr.denom() as f64;
+                            video_props_ref.update(|props| {
+                                props.width = w;
+                                props.height = h;
+                                props.framerate = framerate;
+                                props.has_video = true;
+                            });
                             log::info!(
+                                target: &log_target_ref,
                                 "Updated video properties from sample: {}x{} @ {}fps",
-                                props.width,
-                                props.height,
-                                props.framerate
+                                w,
+                                h,
+                                framerate
                             );
-
-                            // Recreate frame buffer with correct size
-                            let new_size =
-                                (props.width as usize * props.height as usize * 3).div_ceil(2);
-                            let mut frame_guard =
-                                frame_ref.lock().map_err(|_| gst::FlowError::Error)?;
-                            frame_guard.resize(new_size, 0);
-                            drop(frame_guard);
-                            drop(props);
                         }
-                        caps_checked = true;
+                        video_info = gst_video::VideoInfo::from_caps(caps).ok();
+                        pixel_format = caps
+                            .structure(0)
+                            .and_then(|s| s.get::<&str>("format").ok())
+                            .and_then(VideoPixelFormat::from_gst_name)
+                            .unwrap_or(VideoPixelFormat::Nv12);
+                        if let Some(info) = &video_info {
+                            color = ColorInfo::from_colorimetry(&info.colorimetry());
+                        }
+                        let hdr = video_info
+                            .as_ref()
+                            .and_then(|info| HdrMetadata::from_caps(caps, &info.colorimetry()));
+                        if hdr.is_some() {
+                            log::info!(target: &log_target_ref, "HDR metadata: {:?}", hdr);
+                        }
+                        if let Ok(mut hdr_metadata) = hdr_metadata_ref.lock() {
+                            *hdr_metadata = hdr;
+                        }
+                        log::info!(
+                            target: &log_target_ref,
+                            "Negotiated pixel format: {:?}, colorimetry: {:?}",
+                            pixel_format,
+                            color
+                        );
+                        negotiated_caps = Some(caps.to_owned());
+                        readiness_ref.settle(true);
                     }
 
                     *last_frame_time_ref
@@ -373,56 +1056,139 @@ impl AppsinkVideo {
                     let buffer = sample.buffer().ok_or(gst::FlowError::Error)?;
                     let map = buffer.map_readable().map_err(|_| gst::FlowError::Error)?;
 
-                    let mut frame = frame_ref.lock().map_err(|_| gst::FlowError::Error)?;
-                    let frame_len = frame.len();
-                    if map.len() >= frame_len {
-                        frame.copy_from_slice(&map.as_slice()[..frame_len]);
+                    // Prefer the per-buffer video meta: decoders may pad rows beyond what
+                    // the caps imply (e.g. 1366 wide frames with a 1368 byte stride)
+                    let layout = buffer
+                        .meta::<gst_video::VideoMeta>()
+                        .map(|meta| {
+                            (
+                                meta.height(),
+                                [meta.offset()[0], meta.offset()[1]],
+                                [meta.stride()[0] as u32, meta.stride()[1] as u32],
+                            )
+                        })
+                        .or_else(|| {
+                            video_info.as_ref().map(|info| {
+                                (
+                                    info.height(),
+                                    [info.offset()[0], info.offset()[1]],
+                                    [info.stride()[0] as u32, info.stride()[1] as u32],
+                                )
+                            })
+                        });
+
+                    if let Some((height, offsets, strides)) = layout {
+                        let frame = frame_writer.back_mut();
+                        if copy_nv12_planes(
+                            map.as_slice(),
+                            offsets,
+                            strides,
+                            height,
+                            &mut frame.data,
+                        ) {
+                            frame.layout = FrameLayout {
+                                format: pixel_format,
+                                color,
+                                strides,
+                            };
+                            // Stream time is what position() reports
+                            let stream_time = buffer.pts().and_then(|pts| {
+                                sample
+                                    .segment()
+                                    .and_then(|segment| segment.downcast_ref::<gst::ClockTime>())
+                                    .and_then(|segment| segment.to_stream_time(pts))
+                                    .or(Some(pts))
+                            });
+                            frame.pts = stream_time.map(|pts| Duration::from_nanos(pts.nseconds()));
+                            frame_writer.publish();
+                        }
                     }
 
                     upload_frame_ref.swap(true, Ordering::SeqCst);
 
+                    // appsink drops silently (drop=true), so infer drops from gaps between
+                    // consecutive timestamps. Discontinuities (seeks, flushes) reset the baseline.
+                    frames_rendered_ref.fetch_add(1, Ordering::Relaxed);
+                    let pts = buffer.pts();
+                    if let (Some(prev), Some(pts)) = (last_pts, pts)
+                        && !buffer.flags().contains(gst::BufferFlags::DISCONT)
+                    {
+                        let framerate = video_props_ref.framerate();
+                        let rate = sample.segment().map(|s| s.rate().abs()).unwrap_or(1.0);
+                        let gap = pts.saturating_sub(prev);
+                        if framerate > 0.0 && gap < gst::ClockTime::SECOND {
+                            let frames = (gap.seconds_f64() * framerate / rate).round() as u64;
+                            if frames > 1 {
+                                frames_dropped_ref.fetch_add(frames - 1, Ordering::Relaxed);
+                            }
+                        }
+                    }
+                    last_pts = pts;
+
                     Ok(())
                 })() {
-                    log::error!("error pulling frame");
+                    log::error!(target: &log_target_ref, "error pulling frame");
                 }
             }
         });
 
         Ok(AppsinkVideo(RwLock::new(Internal {
             id,
+            log_target,
+            uri,
 
             bus: pipeline.bus().unwrap(),
             source: pipeline,
             alive,
             worker: Some(worker),
+            readiness,
+            stopped: false,
 
             video_props,
             duration,
             speed: 1.0,
+            trick_mode: TrickMode::default(),
             sync_av,
+            is_live,
+            seekable,
 
             frame,
             upload_frame,
+            frames_rendered,
+            frames_dropped,
             last_frame_time,
             looping: false,
+            segment_looping: false,
             is_eos: false,
             restart_stream: false,
             sync_av_avg: 0,
             sync_av_counter: 0,
 
             seek_position: None,
+            coalesced_seek_started: None,
+            queued_seek: None,
             last_valid_position: Duration::ZERO,
 
             pending_play_after_seek: false,
             pending_start_position: None,
             user_paused: false,
 
+            is_buffering: false,
+            buffering_percent: 100,
+
+            scrub_mute: false,
+            scrub_mute_window: Duration::from_millis(150),
+            scrub_mute_restore: None,
+
             current_bitrate: 0,
             avg_in_rate: 0,
+            stats_counter: 0,
+            pinned_connection_speed: None,
 
             last_error_time: None,
             error_count: 0,
             is_reconnecting: false,
+            has_error: false,
 
             available_subtitles: Vec::new(),
             current_subtitle_track: None,
@@ -431,12 +1197,42 @@ impl AppsinkVideo {
             available_audio_tracks: Vec::new(),
             current_audio_track: 0,
 
+            available_video_tracks: Vec::new(),
+            current_video_track: -1,
+
             stream_collection: None,
+            stream_ids: StreamIds::default(),
             selected_stream_ids: Vec::new(),
-            //hdr_metadata: hdr_metadata_shared
-            //    .lock()
-            //    .ok()
-            //    .and_then(|guard| guard.clone()),
+
+            next_uri,
+            track_switch_pending,
+            subtitle_text,
+            subtitle_text_changed,
+            subtitle_style,
+            adaptive_manifest,
+            bus_taps: None,
+
+            deinterlace_mode: DeinterlaceMode::default(),
+            audio_sink_device: None,
+            rtsp_config: RtspConfig::default(),
+            decoder_preference: DecoderPreference::default(),
+            clock: None,
+            base_time: None,
+            pixel_aspect_override: None,
+            tone_mapping: ToneMappingConfig::default(),
+            scaling_filter: ScalingFilter::default(),
+
+            audio_normalization: false,
+            normalization_gain: None,
+            normalization_limiter: None,
+            replaygain_db: None,
+            loudness_lufs: None,
+
+            audio_analysis_bands: None,
+            audio_levels: None,
+
+            tags: gst::TagList::new(),
+            hdr_metadata,
         })))
     }
 
@@ -455,100 +1251,895 @@ impl AppsinkVideo {
     /// Set HTTP headers for HTTP-based sources via GStreamer "http-headers" context.
     /// Applies the context to the underlying pipeline so that HTTP elements (e.g. souphttpsrc,
     /// adaptivedemux segment fetchers) can use them for requests.
+    ///
+    /// The pipeline's `source-setup` handler also copies the headers onto the source's
+    /// `extra-headers`, `cookies` and `user-agent` properties. A source that already exists
+    /// keeps its headers; they take effect for the next one, e.g. after [`Self::reload`].
     pub fn set_http_headers(&mut self, headers: &[(impl AsRef<str>, impl AsRef<str>)]) {
         let pipeline = self.get_mut().source.clone();
         subwave_core::http::set_http_headers_on_pipeline(&pipeline, headers);
     }
-}
-
-impl Video for AppsinkVideo {
-    type Video = AppsinkVideo;
 
-    /// Create a new video player from a given video which loads from `uri`.
-    /// Note that live sources will report the duration to be zero.
-    fn new(uri: &url::Url) -> Result<Self, Error> {
-        gst::init()?;
-        let (pipeline, video_sink) = Self::build_pipeline_with_headers_vec(uri, None)?;
-        Self::from_gst_pipeline(pipeline, video_sink)
+    /// Pin playbin3's connection speed, which HLS and DASH demuxers use to choose the variant
+    /// they start with and switch between, e.g. to force a quality tier.
+    ///
+    /// While pinned, the speed is no longer updated from the measured download rate. Passing
+    /// 0 unpins it and lets the measurement take over again.
+    pub fn set_connection_speed(&mut self, bits_per_sec: u64) {
+        let mut inner = self.get_mut();
+        inner.pinned_connection_speed = (bits_per_sec > 0).then_some(bits_per_sec);
+        inner.apply_connection_speed(bits_per_sec);
     }
 
-    /// Get the size/resolution of the video as `(width, height)`.
-    fn size(&self) -> (i32, i32) {
-        let inner = self.read();
-        let props = inner.video_props.lock().expect("lock video props");
-        (props.width, props.height)
+    /// The connection speed pinned with [`Self::set_connection_speed`], in bits per second.
+    pub fn connection_speed(&self) -> Option<u64> {
+        self.read().pinned_connection_speed
     }
 
-    /// Set HTTP headers for HTTP-based sources via GStreamer "http-headers" context.
-    /// Applies the context to the underlying pipeline so that HTTP elements (e.g. souphttpsrc,
-    /// adaptivedemux segment fetchers) can use them for requests.
-    /// Get the framerate of the video as frames per second.
-    fn framerate(&self) -> f64 {
-        let inner = self.read();
-        let props = inner.video_props.lock().expect("lock video props");
-        props.framerate
+    /// Variants of an HLS or DASH stream, in manifest order, for a quality menu. Empty for
+    /// other media and until the demuxer has read the manifest.
+    pub fn adaptive_variants(&self) -> Vec<AdaptiveVariant> {
+        self.read()
+            .adaptive_manifest
+            .lock()
+            .map(|manifest| parse_manifest(&String::from_utf8_lossy(&manifest)))
+            .unwrap_or_default()
     }
 
-    /// Set the volume multiplier of the audio.
-    /// `0.0` = 0% volume, `1.0` = 100% volume.
+    /// Restrict an HLS or DASH stream to the variant at `index` in
+    /// [`adaptive_variants`](Self::adaptive_variants), or with `None` let the demuxer adapt
+    /// to the connection again.
     ///
-    /// This uses a linear scale, for example `0.5` is perceived as half as loud.
-    fn set_volume(&mut self, volume: f64) {
-        self.get_mut().source.set_property("volume", volume);
-        self.set_muted(self.muted()); // for some reason gstreamer unmutes when changing volume?
+    /// The demuxer is pinned to the variant's bitrate, and so is the connection speed (see
+    /// [`set_connection_speed`](Self::set_connection_speed)); `None` unpins both. The switch
+    /// happens at the next segment boundary.
+    pub fn select_adaptive_variant(&mut self, index: Option<usize>) -> Result<(), Error> {
+        let bitrate = match index {
+            Some(index) => {
+                self.adaptive_variants()
+                    .get(index)
+                    .ok_or(Error::InvalidState)?
+                    .bitrate
+            }
+            None => 0,
+        };
+        let demuxer = find_adaptive_demuxer(self.read().source.upcast_ref())
+            .ok_or_else(|| Error::Unsupported("not an HLS or DASH stream".into()))?;
+        log::info!(
+            target: &self.read().log_target,
+            "Adaptive variant {:?} ({} bps) on {}",
+            index,
+            bitrate,
+            demuxer.name()
+        );
+        limit_demuxer_bitrate(&demuxer, bitrate);
+        self.set_connection_speed(bitrate);
+        Ok(())
     }
 
-    /// Get the volume multiplier of the audio.
-    fn volume(&self) -> f64 {
-        self.read().source.property("volume")
+    /// Process pending bus messages and return the resulting events, for using the video
+    /// without a [`VideoPlayer`](crate::video_player::VideoPlayer), e.g. headless frame
+    /// extraction. The widget does this on every redraw, so do not call it alongside one.
+    ///
+    /// End of stream, looping, buffering, reconnects and queued track switches are only
+    /// handled here, so call it regularly (e.g. at the frame rate) while playing. Each decoded
+    /// frame is reported as one [`PlayerEvent::NewFrame`]. While paused or after the end of
+    /// the stream only seeks are completed, e.g. to step through a paused video.
+    pub fn pump(&self) -> Vec<PlayerEvent> {
+        self.write().pump(true)
     }
 
-    /// Set if the audio is muted or not, without changing the volume.
-    fn set_muted(&mut self, muted: bool) {
-        self.get_mut().source.set_property("mute", muted);
-    }
+    /// Receive a copy of every message posted on the pipeline bus, not just the ones the
+    /// [`VideoPlayer`](crate::video_player::VideoPlayer) handles.
+    ///
+    /// Messages are cloned from a bus sync handler and still queued on the bus, so the
+    /// widget's own processing is unaffected. Dropping the receiver detaches the tap; it is
+    /// pruned when the next message arrives.
+    pub fn subscribe_bus(&self) -> mpsc::Receiver<gst::Message> {
+        let (tx, rx) = mpsc::channel();
+        let mut inner = self.write();
+        if let Some(taps) = &inner.bus_taps {
+            taps.lock().expect("lock bus taps").push(tx);
+            return rx;
+        }
 
-    /// Get if the audio is muted or not.
-    fn muted(&self) -> bool {
-        self.read().source.property("mute")
+        let taps = Arc::new(Mutex::new(vec![tx]));
+        Self::install_bus_taps(&inner.bus, Arc::clone(&taps));
+        inner.bus_taps = Some(taps);
+        rx
     }
 
-    /// Get if the stream ended or not.
-    fn eos(&self) -> bool {
-        self.read().is_eos
+    /// Forward a clone of every message on `bus` to the `taps` still listening.
+    fn install_bus_taps(bus: &gst::Bus, taps: Arc<Mutex<Vec<mpsc::Sender<gst::Message>>>>) {
+        bus.set_sync_handler(move |_bus, msg| {
+            if let Ok(mut taps) = taps.lock() {
+                taps.retain(|tx| tx.send(msg.clone()).is_ok());
+            }
+            gst::BusSyncReply::Pass
+        });
     }
 
-    /// Get if the media will loop or not.
-    fn looping(&self) -> bool {
-        self.read().looping
+    /// Set how interlaced video is handled. Defaults to [`DeinterlaceMode::Auto`].
+    ///
+    /// The `deinterlace` element is always part of the video sink bin, so switching modes
+    /// does not require relinking and is safe while playing or paused.
+    pub fn set_deinterlace(&mut self, mode: DeinterlaceMode) -> Result<(), Error> {
+        let mut inner = self.get_mut();
+        let deinterlacer = inner
+            .source
+            .property::<Option<gst::Element>>("video-sink")
+            .and_then(|sink| sink.downcast::<gst::Bin>().ok())
+            .and_then(|bin| bin.by_name("deinterlacer"))
+            .ok_or_else(|| {
+                log::warn!(
+                    target: &inner.log_target,
+                    "No deinterlace element in video sink; cannot set {:?}",
+                    mode
+                );
+                Error::InvalidState
+            })?;
+        deinterlacer.set_property_from_str("mode", mode.as_gst_nick());
+        inner.deinterlace_mode = mode;
+        Ok(())
     }
 
-    /// Set if the media will loop or not.
-    fn set_looping(&mut self, looping: bool) {
-        self.get_mut().looping = looping;
+    /// Change how text subtitles look. The style is kept when the subtitle track changes
+    /// and across [`reload`](Self::reload).
+    ///
+    /// The font goes to playbin3's `subtitle-font-desc`; colors and the shaded background
+    /// are set on the text overlays inside playsink.
+    pub fn set_subtitle_style(&mut self, style: SubtitleStyle) {
+        let inner = self.get_mut();
+        if let Some(pspec) = inner.source.find_property("subtitle-font-desc") {
+            match &style.font_desc {
+                Some(font_desc) => inner.source.set_property("subtitle-font-desc", font_desc),
+                // Undo an earlier font rather than keeping it
+                None => inner
+                    .source
+                    .set_property_from_value("subtitle-font-desc", pspec.default_value()),
+            }
+        }
+        for element in inner
+            .source
+            .iterate_recurse()
+            .into_iter()
+            .filter_map(Result::ok)
+            .filter(SubtitleStyle::applies_to)
+        {
+            style.apply_to_overlay(&element);
+        }
+        if let Ok(mut current) = inner.subtitle_style.lock() {
+            *current = style;
+        }
     }
 
-    /// Set if the media is paused or not.
-    fn set_paused(&mut self, paused: bool) {
-        self.get_mut().set_paused(paused)
+    /// The style set with [`set_subtitle_style`](Self::set_subtitle_style).
+    pub fn subtitle_style(&self) -> SubtitleStyle {
+        self.read()
+            .subtitle_style
+            .lock()
+            .map(|style| style.clone())
+            .unwrap_or_default()
     }
 
-    /// Get if the media is paused or not.
-    fn paused(&self) -> bool {
-        self.read().paused()
+    /// Route audio to a specific output device, identified by [`AudioDevice::id`]
+    /// (see [`list_audio_devices`]).
+    ///
+    /// playbin only accepts a new `audio-sink` in READY or below, so a running pipeline is
+    /// dropped to READY, prerolled again and returned to its previous position and state.
+    ///
+    /// [`AudioDevice::id`]: subwave_core::audio::AudioDevice::id
+    /// [`list_audio_devices`]: subwave_core::audio::list_audio_devices
+    pub fn set_audio_sink_device(&mut self, device: &str) -> Result<(), Error> {
+        let sink = make_audio_sink(device)?;
+        let mut inner = self.get_mut();
+        inner.reconfigure_in_ready(|pipeline| pipeline.set_property("audio-sink", &sink))?;
+        inner.audio_sink_device = Some(device.to_owned());
+        drop(inner);
+        log::info!(target: &self.read().log_target, "Audio output switched to {}", device);
+        Ok(())
     }
 
-    /// Jumps to a specific position in the media.
-    /// Passing `true` to the `accurate` parameter will result in more accurate seeking,
-    /// however, it is also slower. For most seeks (e.g., scrubbing) this is not needed.
-    fn seek(&mut self, position: impl Into<Position>, accurate: bool) -> Result<(), Error> {
-        self.get_mut().seek(position, accurate)
+    /// The device set with [`set_audio_sink_device`](Self::set_audio_sink_device), or `None`
+    /// while playbin picks the output itself.
+    pub fn audio_sink_device(&self) -> Option<String> {
+        self.read().audio_sink_device.clone()
     }
 
-    /// Set the playback speed of the media.
-    /// The default speed is `1.0`.
-    fn set_speed(&mut self, speed: f64) -> Result<(), Error> {
-        self.get_mut().set_speed(speed)
+    /// Even out loudness between clips.
+    ///
+    /// ReplayGain tags are used when the media carries them; otherwise the gain follows a
+    /// running EBU R128 estimate (needs `ebur128level` from gst-plugins-rs). `rglimiter`
+    /// keeps boosted audio from clipping.
+    ///
+    /// The first call inserts the normalization chain into playbin's `audio-filter`, which
+    /// like [`set_audio_sink_device`](Self::set_audio_sink_device) briefly drops a running
+    /// pipeline to READY. Later calls only change the applied gain.
+    pub fn set_audio_normalization(&mut self, enabled: bool) -> Result<(), Error> {
+        let mut inner = self.get_mut();
+        if enabled && inner.normalization_gain.is_none() {
+            let bands = inner.audio_analysis_bands;
+            Self::rebuild_audio_filter(&mut *inner, None, true, bands)?;
+        }
+        inner.audio_normalization = enabled;
+        inner.apply_normalization_gain();
+        Ok(())
+    }
+
+    /// Get if audio normalization is enabled.
+    pub fn audio_normalization(&self) -> bool {
+        self.read().audio_normalization
+    }
+
+    /// Gain in dB currently applied by audio normalization (`0.0` when disabled or before
+    /// any loudness information is known).
+    pub fn normalization_gain_db(&self) -> f64 {
+        self.read().normalization_gain_db()
+    }
+
+    /// Start posting audio levels and a `bands`-wide spectrum, read back with
+    /// [`poll_audio_levels`](Self::poll_audio_levels).
+    ///
+    /// The first call inserts `level` and `spectrum` into playbin's `audio-filter`, which
+    /// briefly drops a running pipeline to READY; later calls only change the band count.
+    pub fn enable_audio_analysis(&mut self, bands: u32) -> Result<(), Error> {
+        let mut inner = self.get_mut();
+        match inner.audio_filter_element("audio-spectrum") {
+            Some(spectrum) => spectrum.set_property("bands", bands),
+            None => {
+                let normalization = inner.normalization_gain.is_some();
+                Self::rebuild_audio_filter(&mut *inner, None, normalization, Some(bands))?;
+            }
+        }
+        inner.audio_analysis_bands = Some(bands);
+        inner.audio_levels = None;
+        Ok(())
+    }
+
+    /// Latest audio levels, or `None` until
+    /// [`enable_audio_analysis`](Self::enable_audio_analysis) was called and the first
+    /// measurement arrived.
+    ///
+    /// Levels are updated by the widget's bus handling every 50ms while audio plays.
+    pub fn poll_audio_levels(&self) -> Option<AudioLevels> {
+        self.read().audio_levels.clone()
+    }
+
+    /// Keep the pitch of the audio when playing faster or slower, with `scaletempo` as
+    /// playbin's `audio-filter`. Enabled by default when the element is installed; disable
+    /// it to let pitch follow the speed.
+    ///
+    /// Like [`set_audio_normalization`](Self::set_audio_normalization) this rebuilds the
+    /// `audio-filter`, which briefly drops a running pipeline to READY.
+    pub fn set_pitch_correction(&mut self, enabled: bool) -> Result<(), Error> {
+        let mut inner = self.get_mut();
+        if inner.pitch_correction_enabled() == enabled {
+            return Ok(());
+        }
+        let normalization = inner.normalization_gain.is_some();
+        let bands = inner.audio_analysis_bands;
+        Self::rebuild_audio_filter(&mut *inner, Some(enabled), normalization, bands)
+    }
+
+    /// Get if `scaletempo` is correcting the pitch at speeds other than `1.0`.
+    pub fn pitch_correction_enabled(&self) -> bool {
+        self.read().pitch_correction_enabled()
+    }
+
+    /// Replace playbin's `audio-filter` and pick up the elements that are adjusted at runtime.
+    /// `pitch_correction` of `None` keeps the current setting.
+    fn rebuild_audio_filter(
+        inner: &mut Internal,
+        pitch_correction: Option<bool>,
+        normalization: bool,
+        analysis_bands: Option<u32>,
+    ) -> Result<(), Error> {
+        let pitch_correction = pitch_correction.unwrap_or_else(|| inner.pitch_correction_enabled());
+        let filter = Self::build_audio_filter(
+            pitch_correction,
+            normalization,
+            analysis_bands,
+            &inner.log_target,
+        )?;
+        inner.reconfigure_in_ready(|pipeline| pipeline.set_property("audio-filter", &filter))?;
+        inner.normalization_gain = inner.audio_filter_element("normalization-gain");
+        inner.normalization_limiter = inner.audio_filter_element("normalization-limiter");
+        inner.audio_analysis_bands = analysis_bands;
+        Ok(())
+    }
+
+    /// Save the frame currently held for display as a PNG at the video's native resolution.
+    ///
+    /// Returns [`Error::InvalidState`] until the first frame has been decoded.
+    pub fn screenshot_png(&self, path: &std::path::Path) -> Result<(), Error> {
+        let (width, height, rgba) = self.frame_rgba()?;
+        image::save_buffer_with_format(
+            path,
+            &rgba,
+            width,
+            height,
+            image::ExtendedColorType::Rgba8,
+            image::ImageFormat::Png,
+        )
+        .map_err(|err| Error::Io(std::io::Error::other(err)))
+    }
+
+    /// Convert the frame currently held for display to tightly packed RGBA8, returned with
+    /// its width and height, using the frame's own matrix and range.
+    pub(crate) fn frame_rgba(&self) -> Result<(u32, u32, Vec<u8>), Error> {
+        let inner = self.read();
+        let (width, height) = inner.video_props.size();
+        if width <= 0 || height <= 0 {
+            return Err(Error::InvalidState);
+        }
+        let (width, height) = (width as u32, height as u32);
+
+        let rgba = inner.frame.read(|frame| {
+            let (layout, frame) = (frame.layout, &frame.data);
+            let rows = [height, height.div_ceil(2)];
+            let frame_len = (layout.strides[0] * rows[0] + layout.strides[1] * rows[1]) as usize;
+            // Empty before the first frame, or briefly mismatched while the resolution changes
+            if frame.is_empty() || frame.len() < frame_len {
+                return Err(Error::InvalidState);
+            }
+            Ok(frame_to_rgba(
+                frame,
+                width as usize,
+                height as usize,
+                layout,
+            ))
+        })?;
+        Ok((width, height, rgba))
+    }
+
+    /// Set the playback speed without the flush of [`set_speed`](Video::set_speed), so audio
+    /// and video carry on uninterrupted. Cheap enough to call on every step of a speed slider.
+    ///
+    /// Uses an instant rate change (GStreamer 1.18+), which keeps the playback direction;
+    /// switching between forward and reverse, or pipelines that do not support it, fall back
+    /// to the flushing seek.
+    pub fn set_speed_instant(&mut self, speed: f64) -> Result<(), Error> {
+        self.get_mut().set_speed_instant(speed)
+    }
+
+    /// Seek with trick-mode flags on subsequent speed changes away from 1.0, e.g. keyframes
+    /// only and no audio so 8x or 16x scanning does not stall decoding every frame. Takes
+    /// effect on the next [`set_speed`](Video::set_speed); the default uses no trick mode.
+    pub fn set_trick_mode(&mut self, mode: TrickMode) {
+        self.get_mut().trick_mode = mode;
+    }
+
+    /// The trick mode set with [`set_trick_mode`](Self::set_trick_mode).
+    pub fn trick_mode(&self) -> TrickMode {
+        self.read().trick_mode
+    }
+
+    /// Run playback on `clock`, e.g. an external audio engine's, instead of the pipeline's own.
+    ///
+    /// The pipeline is already playing once constructed, so it pauses briefly to adopt the
+    /// clock. To start on it, call `use_clock` on the pipeline in
+    /// [`new_with_setup`](Self::new_with_setup) instead. Several videos on one clock with the
+    /// same [base time](Self::set_base_time) play in lockstep.
+    pub fn set_clock(&mut self, clock: &gst::Clock) -> Result<(), Error> {
+        let mut inner = self.get_mut();
+        adopt_clock(&inner.source, clock)?;
+        inner.clock = Some(clock.clone());
+        Ok(())
+    }
+
+    /// The clock playback runs on, for other pipelines or an audio engine to follow.
+    pub fn use_pipeline_clock(&self) -> gst::Clock {
+        pipeline_clock(&self.read().source)
+    }
+
+    /// Fix the base time instead of letting the pipeline choose one whenever it starts
+    /// playing. Give videos sharing a clock the same base time to keep them in sync.
+    ///
+    /// Pausing no longer holds running time still: it stays the clock time minus
+    /// `base_time`, so after [`set_paused(false)`](Video::set_paused) the video skips ahead by
+    /// the time it spent paused. To resume where it stopped, call this again with the base
+    /// time plus the paused duration first, for every video sharing it.
+    pub fn set_base_time(&mut self, base_time: gst::ClockTime) {
+        let mut inner = self.get_mut();
+        lock_base_time(&inner.source, base_time);
+        inner.base_time = Some(base_time);
+    }
+
+    /// Seek for a dragged slider: while a seek is still in flight, further calls only record
+    /// the target, and the most recent one is issued once the pipeline reports the first seek
+    /// done. This avoids flooding playbin3 with flushing seeks that each restart decoding.
+    ///
+    /// [`seek_target`](Self::seek_target) reports the latest target meanwhile. Use
+    /// [`seek`](Video::seek) for single precise seeks, which are issued at once.
+    pub fn seek_coalesced(
+        &mut self,
+        position: impl Into<Position>,
+        accurate: bool,
+    ) -> Result<(), Error> {
+        self.get_mut().seek_coalesced(position.into(), accurate)
+    }
+
+    /// Id of this video, unique across both backends. Log messages about it use the target
+    /// `subwave[id]`.
+    pub fn id(&self) -> u64 {
+        self.read().id
+    }
+
+    /// Plain text of the subtitle cue currently shown, or `None` between cues. Markup is
+    /// stripped; image-based subtitles have no text.
+    pub fn current_subtitle_text(&self) -> Option<String> {
+        self.read()
+            .subtitle_text
+            .lock()
+            .expect("lock subtitle text")
+            .clone()
+    }
+
+    /// Destination of a seek that has not completed yet, e.g. to keep a scrubber at the
+    /// requested spot while [`position`](Video::position) still reports the old one.
+    ///
+    /// Cleared once the pipeline has settled at the new position.
+    pub fn seek_target(&self) -> Option<Duration> {
+        self.read().seek_position
+    }
+
+    /// Presentation timestamp (in stream time, comparable to `position()`) of the frame
+    /// currently held for display, or `None` before the first frame.
+    ///
+    /// `position()` follows the audio clock, so the difference between the two shows how far
+    /// the picture lags behind the sound.
+    pub fn current_frame_pts(&self) -> Option<Duration> {
+        self.read().frame.read(|frame| frame.pts)
+    }
+
+    /// Frame-exact position for editors: the timestamp of the frame currently held for
+    /// display and its index, counted from the start at the stream's framerate.
+    ///
+    /// `position()` comes from a position query, which some demuxers only answer to the
+    /// nearest keyframe. This is `None` before the first frame or while the framerate is
+    /// unknown (including variable framerate streams). Only the appsink backend sees frame
+    /// timestamps; waylandsink presents frames itself.
+    pub fn position_precise(&self) -> Option<(Duration, u64)> {
+        let inner = self.read();
+        let pts = inner.frame.read(|frame| frame.pts)?;
+        let frame = time_to_frame(pts, inner.video_props.framerate())?;
+        Some((pts, frame))
+    }
+
+    /// Title, artist, album and cover art from the tags seen so far.
+    ///
+    /// Tags arrive on the bus as the media is demuxed, so this fills in shortly after the
+    /// pipeline prerolls and is cleared when the track changes.
+    pub fn metadata(&self) -> MediaMetadata {
+        MediaMetadata::from_tags(&self.read().tags)
+    }
+
+    /// Factory name of the video decoder playbin3 picked (e.g. `vah264dec` or `avdec_h264`).
+    ///
+    /// Returns `None` until the pipeline has prerolled and the decoder has been plugged.
+    pub fn active_decoder_name(&self) -> Option<String> {
+        find_factory_by_klass(self.read().source.upcast_ref(), &["Decoder", "Video"])
+    }
+
+    /// Factory name of the audio sink doing the output (e.g. `pulsesink`), looking through
+    /// auto-plugging bins such as `autoaudiosink`. Video always goes to the appsink.
+    pub fn active_sink_name(&self) -> Option<String> {
+        find_factory_by_klass(self.read().source.upcast_ref(), &["Sink", "Audio"])
+    }
+
+    /// Mute audio during inaccurate (scrubbing) seeks, restoring the previous mute state once
+    /// the seek completes and the hold window (see [`set_scrub_mute_window`]) has passed.
+    ///
+    /// [`set_scrub_mute_window`]: Self::set_scrub_mute_window
+    pub fn set_scrub_mute(&mut self, enabled: bool) {
+        let mut inner = self.get_mut();
+        inner.scrub_mute = enabled;
+        if !enabled && let Some(pending) = inner.scrub_mute_restore.take() {
+            inner.source.set_property("mute", pending.prior_muted);
+        }
+    }
+
+    /// Get if audio is muted during inaccurate seeks.
+    pub fn scrub_mute(&self) -> bool {
+        self.read().scrub_mute
+    }
+
+    /// Set how long audio stays muted after the last scrubbing seek. Defaults to 150ms, which
+    /// bridges the gaps between seeks while dragging a slider.
+    pub fn set_scrub_mute_window(&mut self, window: Duration) {
+        self.get_mut().scrub_mute_window = window;
+    }
+
+    /// Set how PQ and HLG content is tone-mapped to SDR. Takes effect on the next redraw.
+    ///
+    /// This is the appsink counterpart of `vapostproc`'s `hdr-tone-mapping` on the Wayland
+    /// backend; SDR content is not affected.
+    pub fn set_tone_mapping(&mut self, config: ToneMappingConfig) {
+        self.get_mut().tone_mapping = config;
+    }
+
+    /// Get the current tone mapping configuration.
+    pub fn tone_mapping(&self) -> ToneMappingConfig {
+        self.read().tone_mapping
+    }
+
+    /// HDR metadata of the current video stream, or `None` while it is SDR (or not yet
+    /// negotiated). Updated whenever the caps change.
+    pub fn hdr_metadata(&self) -> Option<HdrMetadata> {
+        self.read()
+            .hdr_metadata
+            .lock()
+            .ok()
+            .and_then(|metadata| *metadata)
+    }
+
+    /// Set how frames are filtered when scaled to the widget, e.g.
+    /// [`ScalingFilter::Nearest`] for crisp pixel art. Takes effect on the next redraw;
+    /// defaults to [`ScalingFilter::Linear`].
+    pub fn set_scaling_filter(&mut self, filter: ScalingFilter) {
+        self.get_mut().scaling_filter = filter;
+    }
+
+    /// Get the current scaling filter.
+    pub fn scaling_filter(&self) -> ScalingFilter {
+        self.read().scaling_filter
+    }
+
+    /// Override the pixel aspect ratio of the decoded frames, for DVDs and streams that carry
+    /// the wrong one. `None` goes back to the stream's own.
+    ///
+    /// Frames are scaled to square pixels before the appsink, so this changes the width the
+    /// video reports and is laid out with. The new ratio applies from the next frame.
+    pub fn set_pixel_aspect_ratio(&mut self, par: Option<(i32, i32)>) -> Result<(), Error> {
+        if par.is_some_and(|(n, d)| n <= 0 || d <= 0) {
+            return Err(Error::InvalidState);
+        }
+        let mut inner = self.get_mut();
+        let par_override = inner
+            .source
+            .property::<Option<gst::Element>>("video-sink")
+            .and_then(|sink| sink.downcast::<gst::Bin>().ok())
+            .and_then(|bin| bin.by_name("par-override"))
+            .ok_or_else(|| {
+                log::warn!(
+                    target: &inner.log_target,
+                    "No capssetter in video sink; cannot set pixel aspect ratio {:?}",
+                    par
+                );
+                Error::InvalidState
+            })?;
+
+        let caps = match par {
+            Some((n, d)) => gst::Caps::builder("video/x-raw")
+                .field("pixel-aspect-ratio", gst::Fraction::new(n, d))
+                .build(),
+            None => gst::Caps::new_empty_simple("video/x-raw"),
+        };
+        // capssetter renegotiates with the new caps on its next buffer
+        par_override.set_property("caps", &caps);
+        inner.pixel_aspect_override = par;
+
+        // Update the reported size right away; the worker replaces it with the negotiated one
+        if inner.coded_video_info().is_some() {
+            let (width, height) = inner.display_size();
+            inner.video_props.update(|props| {
+                props.width = width;
+                props.height = height;
+            });
+        }
+        Ok(())
+    }
+
+    /// Get the pixel aspect ratio set with [`Self::set_pixel_aspect_ratio`], if any.
+    pub fn pixel_aspect_ratio(&self) -> Option<(i32, i32)> {
+        self.read().pixel_aspect_override
+    }
+
+    /// Get the aspect ratio the video is displayed at (width / height), taking any pixel
+    /// aspect override into account. `None` until the size is known.
+    pub fn display_aspect_ratio(&self) -> Option<f64> {
+        let (width, height) = self.read().video_props.size();
+        (width > 0 && height > 0).then(|| width as f64 / height as f64)
+    }
+
+    /// Change playbin's `flags`, see [`Self::new_with_play_flags`].
+    ///
+    /// Stream selection only picks up new flags when prerolling, so a running pipeline is
+    /// dropped to READY, prerolled again and returned to its previous position and state.
+    pub fn set_play_flags(&mut self, flags: GstPlayFlags) -> Result<(), Error> {
+        let flags = flags | GstPlayFlags::SOFT_VOLUME;
+        let mut inner = self.get_mut();
+        inner.reconfigure_in_ready(|pipeline| pipeline.set_property("flags", flags))?;
+        // Set again by the worker once frames arrive
+        if !flags.contains(GstPlayFlags::VIDEO) {
+            inner.video_props.update(|props| props.has_video = false);
+        }
+        Ok(())
+    }
+
+    /// Get playbin's current `flags`.
+    pub fn play_flags(&self) -> GstPlayFlags {
+        self.read().source.property("flags")
+    }
+
+    /// Get the current deinterlacing mode.
+    pub fn deinterlace(&self) -> DeinterlaceMode {
+        self.read().deinterlace_mode
+    }
+
+    /// Get the time ranges that are currently buffered, as `(start, stop)` pairs.
+    ///
+    /// Returns an empty list when the pipeline cannot answer a time-based buffering query
+    /// (e.g. local files or sources without a download buffer).
+    pub fn buffered_ranges(&self) -> Vec<(Duration, Duration)> {
+        let inner = self.read();
+        let mut query = gst::query::Buffering::new(gst::Format::Time);
+        if !inner.source.query(&mut query) {
+            return Vec::new();
+        }
+        query
+            .ranges()
+            .into_iter()
+            .filter_map(|range| match range {
+                (
+                    gst::GenericFormattedValue::Time(Some(start)),
+                    gst::GenericFormattedValue::Time(Some(stop)),
+                ) => Some((
+                    Duration::from_nanos(start.nseconds()),
+                    Duration::from_nanos(stop.nseconds()),
+                )),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// Get the last reported buffering fill level in percent (`100` when fully buffered).
+    pub fn buffering_percent(&self) -> i32 {
+        self.read().buffering_percent
+    }
+
+    /// Number of consecutive network errors being retried. Reset once frames arrive again.
+    pub fn error_count(&self) -> u32 {
+        self.read().error_count
+    }
+
+    /// Whether a reconnect after a network error is scheduled for the next update.
+    pub fn is_reconnecting(&self) -> bool {
+        self.read().is_reconnecting
+    }
+
+    /// Queue the URI to play once the current one finishes, without a gap.
+    ///
+    /// The URI is handed to playbin from its `about-to-finish` signal; the
+    /// [`VideoPlayer`](crate::video_player::VideoPlayer) reports the switch through
+    /// `on_track_changed`. Speed, volume and mute carry over to the next track.
+    pub fn set_next_uri(&mut self, uri: &url::Url) {
+        let inner = self.get_mut();
+        *inner.next_uri.lock().expect("lock next uri") = Some(uri.clone());
+    }
+
+    /// Tear down the pipeline and build a new one for the current URI, e.g. to recover from a
+    /// fatal error without replacing the video the widget is bound to.
+    ///
+    /// Every setting made through the API carries over, from HTTP headers, buffering, RTSP
+    /// transport, decoder preference and the audio output and filters to deinterlacing,
+    /// subtitle style, clock and base time, the queued [next URI](Self::set_next_uri) and bus
+    /// subscribers, and speed, looping, position and paused state are restored. Only a
+    /// [`new_with_setup`](Self::new_with_setup) closure is not run again. Like [`Video::new`]
+    /// this blocks until the new pipeline has prerolled.
+    ///
+    /// Fails with [`Error::NoUri`] for videos without a URI, see [`Self::resume`].
+    pub fn reload(&mut self) -> Result<(), Error> {
+        let (uri, position, speed, looping, paused) = {
+            let inner = self.read();
+            let uri = inner.uri.clone().ok_or(Error::NoUri)?;
+            // A failed pipeline may no longer answer position queries
+            let position = inner
+                .source
+                .query_position::<gst::ClockTime>()
+                .map(|pos| Duration::from_nanos(pos.nseconds()))
+                .unwrap_or(inner.last_valid_position);
+            (uri, position, inner.speed, inner.looping, inner.user_paused)
+        };
+        let config = UserConfig::capture(self);
+
+        log::info!(target: &self.read().log_target, "Reloading {uri} at {position:?}");
+        let id = next_video_id();
+        let log_target = video_log_target(id);
+        let (pipeline, video_sink) = Self::build_pipeline_with_headers_vec(
+            &uri,
+            None,
+            &BufferConfig::default(),
+            &config.rtsp,
+            &log_target,
+        )?;
+        config.apply_to_playbin(&pipeline, &video_sink, &log_target)?;
+
+        let mut fresh =
+            Self::from_gst_pipeline_unprepared(id, pipeline, video_sink, gst::State::Paused, true)?;
+
+        // Swap the new pipeline in; dropping `fresh` then shuts down the old one
+        std::mem::swap(&mut *self.get_mut(), &mut *fresh.get_mut());
+        fresh.read().bus.unset_sync_handler();
+        drop(fresh);
+        config.restore(self)?;
+
+        let mut inner = self.get_mut();
+        if !position.is_zero() && inner.seekable {
+            inner.seek(position, true)?;
+            let _ = inner.source.state(gst::ClockTime::from_seconds(5));
+        }
+        if speed != 1.0 {
+            inner.set_speed(speed)?;
+        }
+        inner.set_looping(looping);
+        inner.set_paused(paused);
+        Ok(())
+    }
+
+    /// Shut the pipeline down to free its decoders and buffers, e.g. while the video is
+    /// offscreen, keeping duration, tracks and the other metadata readable.
+    ///
+    /// The pipeline goes to NULL and the frame worker is joined. Until [`Self::resume`],
+    /// seeking and speed changes fail with [`Error::InvalidState`], and pausing only records
+    /// the state to restore. The last frame stays on screen.
+    pub fn stop(&mut self) {
+        let mut inner = self.get_mut();
+        if inner.stopped {
+            return;
+        }
+        if let Some(position) = inner.source.query_position::<gst::ClockTime>() {
+            inner.last_valid_position = Duration::from_nanos(position.nseconds());
+        }
+        if let Err(err) = inner.source.set_state(gst::State::Null) {
+            log::warn!(target: &inner.log_target, "Failed to stop pipeline: {err}");
+        }
+        inner.alive.store(false, Ordering::SeqCst);
+        if let Some(worker) = inner.worker.take() {
+            worker.join().expect("failed to stop video thread");
+        }
+        inner.stopped = true;
+    }
+
+    /// Rebuild the pipeline and frame worker after [`Self::stop`] and seek back to the last
+    /// position, see [`Self::reload`]. Does nothing if the video is not stopped.
+    ///
+    /// Videos without a URI, such as [`from_bytes`](Self::from_bytes) and
+    /// [`from_device`](Self::from_device), cannot be rebuilt: they fail with
+    /// [`Error::NoUri`] and stay stopped.
+    pub fn resume(&mut self) -> Result<(), Error> {
+        if !self.read().stopped {
+            return Ok(());
+        }
+        self.reload()
+    }
+
+    /// Whether [`Self::stop`] was called without a [`Self::resume`] since.
+    pub fn is_stopped(&self) -> bool {
+        self.read().stopped
+    }
+
+    /// Get the URI playbin is currently playing, which changes after a gapless switch.
+    pub fn current_uri(&self) -> Option<url::Url> {
+        self.read()
+            .source
+            .property::<Option<String>>("current-uri")
+            .and_then(|s| url::Url::parse(&s).ok())
+    }
+}
+
+impl Video for AppsinkVideo {
+    type Video = AppsinkVideo;
+
+    /// Create a new video player from a given video which loads from `uri`.
+    /// Note that live sources will report the duration to be zero.
+    fn new(uri: &url::Url) -> Result<Self, Error> {
+        Self::new_with_setup(uri, |_| {})
+    }
+
+    /// Get the size/resolution of the video as `(width, height)`.
+    fn size(&self) -> (i32, i32) {
+        self.read().video_props.size()
+    }
+
+    fn natural_size(&self) -> (i32, i32) {
+        self.read().natural_size()
+    }
+
+    /// Frames reach the appsink scaled to square pixels, so this matches [`size`](Self::size)
+    /// once they arrive.
+    fn display_size(&self) -> (i32, i32) {
+        self.read().display_size()
+    }
+
+    /// Set HTTP headers for HTTP-based sources via GStreamer "http-headers" context.
+    /// Applies the context to the underlying pipeline so that HTTP elements (e.g. souphttpsrc,
+    /// adaptivedemux segment fetchers) can use them for requests.
+    /// Get the framerate of the video as frames per second.
+    fn framerate(&self) -> f64 {
+        self.read().video_props.framerate()
+    }
+
+    /// Set the volume multiplier of the audio.
+    /// `0.0` = 0% volume, `1.0` = 100% volume.
+    ///
+    /// This uses a linear scale, for example `0.5` is perceived as half as loud.
+    fn set_volume(&mut self, volume: f64) {
+        // Mute is left alone: with SOFT_VOLUME both live on playsink's own volume element
+        self.get_mut().source.set_property("volume", volume);
+    }
+
+    /// Get the volume multiplier of the audio.
+    fn volume(&self) -> f64 {
+        self.read().source.property("volume")
+    }
+
+    /// Set if the audio is muted or not, without changing the volume.
+    fn set_muted(&mut self, muted: bool) {
+        let mut inner = self.get_mut();
+        // While a scrub holds audio muted, apply the new state once the seek settles
+        match inner.scrub_mute_restore.as_mut() {
+            Some(pending) => pending.prior_muted = muted,
+            None => inner.source.set_property("mute", muted),
+        }
+    }
+
+    /// Get if the audio is muted or not.
+    fn muted(&self) -> bool {
+        let inner = self.read();
+        match inner.scrub_mute_restore {
+            Some(pending) => pending.prior_muted,
+            None => inner.source.property("mute"),
+        }
+    }
+
+    /// Get if the stream ended or not.
+    fn eos(&self) -> bool {
+        self.read().is_eos
+    }
+
+    /// Get if the media will loop or not.
+    fn looping(&self) -> bool {
+        self.read().looping
+    }
+
+    /// Set if the media will loop or not.
+    ///
+    /// Loops through a SEGMENT seek when the source supports it, which avoids the flush and
+    /// brief stall of restarting on EOS.
+    fn set_looping(&mut self, looping: bool) {
+        self.get_mut().set_looping(looping);
+    }
+
+    /// Set if the media is paused or not.
+    fn set_paused(&mut self, paused: bool) {
+        self.get_mut().set_paused(paused)
+    }
+
+    /// Get if the media is paused or not.
+    fn paused(&self) -> bool {
+        self.read().paused()
+    }
+
+    /// Get the overall playback status.
+    fn status(&self) -> PlaybackStatus {
+        self.read().status()
+    }
+
+    /// Jumps to a specific position in the media.
+    /// Passing `true` to the `accurate` parameter will result in more accurate seeking,
+    /// however, it is also slower. For most seeks (e.g., scrubbing) this is not needed.
+    fn seek(&mut self, position: impl Into<Position>, accurate: bool) -> Result<(), Error> {
+        let mut inner = self.get_mut();
+        // A direct seek supersedes any scrubbing target still waiting
+        inner.queued_seek = None;
+        inner.seek(position, accurate)
+    }
+
+    /// Set the playback speed of the media.
+    /// The default speed is `1.0`.
+    fn set_speed(&mut self, speed: f64) -> Result<(), Error> {
+        self.get_mut().set_speed(speed)
     }
 
     /// Get the current playback speed.
@@ -591,13 +2182,11 @@ impl Video for AppsinkVideo {
     }
 
     /// Set the subtitle URL to display.
+    ///
+    /// A running pipeline switches in place through playbin3's `instant-uri` and returns to
+    /// the same position and paused state; only if that fails is it reloaded through READY.
     fn set_subtitle_url(&mut self, url: &url::Url) -> Result<(), Error> {
-        let paused = self.paused();
-        let mut inner = self.get_mut();
-        inner.source.set_state(gst::State::Ready)?;
-        inner.source.set_property("suburi", url.as_str());
-        inner.set_paused(paused);
-        Ok(())
+        self.get_mut().set_subtitle_url(url)
     }
 
     /// Get the current subtitle URL.
@@ -608,6 +2197,21 @@ impl Video for AppsinkVideo {
             .and_then(|s| url::Url::parse(&s).ok())
     }
 
+    /// Decode preview frames at the given positions from a separate pipeline on the same URI.
+    fn generate_thumbnails(
+        &mut self,
+        positions: &[Position],
+        downscale: NonZeroU8,
+    ) -> Result<Vec<Thumbnail>, Error> {
+        let uri = self
+            .read()
+            .source
+            .property::<Option<String>>("current-uri")
+            .and_then(|s| url::Url::parse(&s).ok())
+            .ok_or(Error::Uri)?;
+        subwave_core::video::thumbnail::generate_thumbnails(&uri, positions, downscale)
+    }
+
     /// Get the underlying GStreamer pipeline.
     fn pipeline(&self) -> gst::Pipeline {
         self.read().source.clone()
@@ -653,11 +2257,50 @@ impl Video for AppsinkVideo {
         self.read().current_audio_track
     }
 
+    /// Check if the media can be seeked, from a Seeking query refreshed on AsyncDone and
+    /// pipeline state changes.
+    fn seekable(&self) -> bool {
+        self.read().seekable
+    }
+
+    /// Get the number of frames pulled from the appsink, and the number inferred to have been
+    /// dropped from gaps in their timestamps.
+    fn frame_stats(&self) -> FrameStats {
+        let inner = self.read();
+        FrameStats {
+            rendered: inner.frames_rendered.load(Ordering::Relaxed),
+            dropped: inner.frames_dropped.load(Ordering::Relaxed),
+        }
+    }
+
+    /// Check if the source is live (e.g. a camera or live broadcast).
+    fn is_live(&self) -> bool {
+        self.read().is_live
+    }
+
     /// Check if the video has video tracks (not just audio)
     fn has_video(&self) -> bool {
-        let inner = self.read();
-        let props = inner.video_props.lock().expect("lock video props");
-        props.has_video
+        self.read().video_props.has_video()
+    }
+
+    fn video_tracks(&self) -> Vec<VideoTrack> {
+        self.read().available_video_tracks.clone()
+    }
+
+    fn current_video_track(&self) -> i32 {
+        self.read().current_video_track
+    }
+
+    fn select_video_track(&mut self, track_index: i32) -> Result<(), Error> {
+        self.get_mut().select_video_track(track_index)
+    }
+
+    fn has_audio(&self) -> bool {
+        !self.read().available_audio_tracks.is_empty()
+    }
+
+    fn has_subtitles(&self) -> bool {
+        !self.read().available_subtitles.is_empty()
     }
 }
 
@@ -674,9 +2317,276 @@ impl AppsinkVideo {
             .iter()
             .map(|(k, v)| (k.as_ref().to_string(), v.as_ref().to_string()))
             .collect();
-        let (pipeline, video_sink) =
-            Self::build_pipeline_with_headers_vec(uri, Some(owned.as_slice()))?;
-        Self::from_gst_pipeline(pipeline, video_sink)
+        let id = next_video_id();
+        let (pipeline, video_sink) = Self::build_pipeline_with_headers_vec(
+            uri,
+            Some(owned.as_slice()),
+            &BufferConfig::default(),
+            &RtspConfig::default(),
+            &video_log_target(id),
+        )?;
+        Self::from_gst_pipeline_unprepared(id, pipeline, video_sink, gst::State::Playing, true)
+    }
+
+    /// Start loading a video without blocking, resolving once the first frame's caps arrive
+    /// (or, for audio-only media, once the pipeline has prerolled).
+    ///
+    /// Unlike [`Video::new`], which waits up to 5 seconds for the pipeline to change state,
+    /// this only builds the pipeline and sets it to PLAYING before returning the future.
+    /// Errors while prerolling resolve it with the error posted on the bus.
+    pub fn prepare(uri: &url::Url) -> impl Future<Output = Result<Self, Error>> + use<> {
+        let video = gst::init().map_err(Error::from).and_then(|_| {
+            let id = next_video_id();
+            let (pipeline, video_sink) = Self::build_pipeline_with_headers_vec(
+                uri,
+                None,
+                &BufferConfig::default(),
+                &RtspConfig::default(),
+                &video_log_target(id),
+            )?;
+            Self::from_gst_pipeline_unprepared(id, pipeline, video_sink, gst::State::Playing, false)
+        });
+        Prepare(Some(video))
+    }
+
+    /// True once the pipeline has prerolled, immediately so for videos built by the
+    /// blocking constructors.
+    pub fn prepared(&self) -> bool {
+        self.read().readiness.get() == Some(true)
+    }
+
+    /// Create a new video, running `setup` on the playbin3 pipeline before it leaves NULL.
+    ///
+    /// This is the escape hatch for anything not exposed here: set properties on playbin3 or
+    /// connect to signals such as `source-setup` and `element-setup` to configure elements
+    /// as they are created. Settings subwave applies itself (`video-sink`, `audio-filter`,
+    /// buffering) may be overridden, at the cost of the features that rely on them.
+    pub fn new_with_setup(
+        uri: &url::Url,
+        setup: impl FnOnce(&gst::Pipeline),
+    ) -> Result<Self, Error> {
+        gst::init()?;
+        let id = next_video_id();
+        let (pipeline, video_sink) = Self::build_pipeline_with_headers_vec(
+            uri,
+            None,
+            &BufferConfig::default(),
+            &RtspConfig::default(),
+            &video_log_target(id),
+        )?;
+        setup(&pipeline);
+        Self::from_gst_pipeline_unprepared(id, pipeline, video_sink, gst::State::Playing, true)
+    }
+
+    /// Play media held in memory, e.g. fetched through the application's own HTTP stack,
+    /// without writing it to disk first.
+    ///
+    /// `mime` (such as `video/mp4`) becomes the caps of the source; pass `""` to leave the
+    /// container to typefinding. The bytes are served to playbin3 through the `appsrc` it
+    /// creates for the `appsrc://` URI, so seeking works as it does for files.
+    /// [`reload`](Self::reload) and [`resume`](Self::resume) are not available for such
+    /// videos and fail with [`Error::NoUri`].
+    pub fn from_bytes(data: Arc<[u8]>, mime: &str) -> Result<Self, Error> {
+        gst::init()?;
+        let uri = url::Url::parse("appsrc://").map_err(|_| Error::Uri)?;
+        let id = next_video_id();
+        let log_target = video_log_target(id);
+        let (pipeline, video_sink) = Self::build_pipeline_with_headers_vec(
+            &uri,
+            None,
+            &BufferConfig::default(),
+            &RtspConfig::default(),
+            &log_target,
+        )?;
+        let caps = (!mime.is_empty()).then(|| gst::Caps::new_empty_simple(mime));
+        pipeline.connect("source-setup", false, move |values| {
+            let source = values[1].get::<gst::Element>().ok()?;
+            match source.downcast::<gst_app::AppSrc>() {
+                Ok(appsrc) => serve_bytes(&appsrc, Arc::clone(&data), caps.as_ref(), &log_target),
+                Err(source) => log::warn!(
+                    target: &log_target,
+                    "Expected an appsrc, got {}",
+                    source.name()
+                ),
+            }
+            None
+        });
+
+        let video = Self::from_gst_pipeline_unprepared(
+            id,
+            pipeline,
+            video_sink,
+            gst::State::Playing,
+            true,
+        )?;
+        video.write().uri = None;
+        Ok(video)
+    }
+
+    /// Show a live preview of a capture device, e.g. a webcam from
+    /// [`list_video_capture_devices`]. The device's source element feeds
+    /// `decodebin3 ! <video sink bin>`.
+    ///
+    /// The video is live: it reports a zero duration, [`is_live`](Video::is_live) is true and
+    /// it cannot seek, so players should hide their seek bar. There is no audio, and methods
+    /// that rely on playbin3 (volume, tracks, subtitles) are unavailable.
+    ///
+    /// [`list_video_capture_devices`]: subwave_core::video::elements::list_video_capture_devices
+    pub fn from_device(device: &gst::Device) -> Result<Self, Error> {
+        gst::init()?;
+        let source = device.create_element(None)?;
+        let decodebin = gst::ElementFactory::make("decodebin3").build()?;
+        let id = next_video_id();
+        let log_target = video_log_target(id);
+        let video_sink_bin = Self::build_video_sink(&log_target)?;
+
+        let pipeline = gst::Pipeline::builder().name("capture").build();
+        pipeline.add_many([&source, &decodebin, &video_sink_bin])?;
+        source.link(&decodebin)?;
+
+        let sink_pad = video_sink_bin.static_pad("sink").ok_or(Error::Cast)?;
+        decodebin.connect_pad_added(move |_decodebin, pad| {
+            let is_video = pad
+                .stream()
+                .is_some_and(|stream| stream.stream_type().contains(gst::StreamType::VIDEO));
+            if is_video
+                && !sink_pad.is_linked()
+                && let Err(e) = pad.link(&sink_pad)
+            {
+                log::error!(
+                    target: &log_target,
+                    "Failed to link capture device to the video sink: {:?}",
+                    e
+                );
+            }
+        });
+
+        let video_sink = video_sink_bin
+            .downcast::<gst::Bin>()
+            .map_err(|_| Error::Cast)?
+            .by_name("subwave_appsink")
+            .ok_or(Error::Cast)?
+            .downcast::<gst_app::AppSink>()
+            .map_err(|_| Error::Cast)?;
+        // A preview should show the newest frame, not catch up on a backlog
+        SinkConfig {
+            max_buffers: 1,
+            ..SinkConfig::default()
+        }
+        .apply_to_appsink(&video_sink);
+
+        let video = Self::from_gst_pipeline_unprepared(
+            id,
+            pipeline,
+            video_sink,
+            gst::State::Playing,
+            true,
+        )?;
+        {
+            // Capture sources that start straight in PLAYING never report NoPreroll
+            let mut inner = video.write();
+            inner.is_live = true;
+            inner.seekable = false;
+            inner.uri = None;
+        }
+        Ok(video)
+    }
+
+    /// Create a new video with custom appsink queueing, see [`SinkConfig`].
+    pub fn new_with_sink_config(uri: &url::Url, sink: SinkConfig) -> Result<Self, Error> {
+        gst::init()?;
+        let id = next_video_id();
+        let (pipeline, video_sink) = Self::build_pipeline_with_headers_vec(
+            uri,
+            None,
+            &BufferConfig::default(),
+            &RtspConfig::default(),
+            &video_log_target(id),
+        )?;
+        sink.apply_to_appsink(&video_sink);
+        Self::from_gst_pipeline_unprepared(id, pipeline, video_sink, gst::State::Playing, true)
+    }
+
+    /// Create a new video with custom playbin `flags`, e.g. to play audio only or to show a
+    /// visualisation for audio files:
+    ///
+    /// ```ignore
+    /// let flags = GstPlayFlags::default().video(false).vis(false);
+    /// ```
+    ///
+    /// `SOFT_VOLUME` is always added, so volume and mute stay independent of each other
+    /// and of the audio sink.
+    pub fn new_with_play_flags(uri: &url::Url, flags: GstPlayFlags) -> Result<Self, Error> {
+        let flags = flags | GstPlayFlags::SOFT_VOLUME;
+        gst::init()?;
+        let id = next_video_id();
+        let (pipeline, video_sink) = Self::build_pipeline_with_headers_vec(
+            uri,
+            None,
+            &BufferConfig::default(),
+            &RtspConfig::default(),
+            &video_log_target(id),
+        )?;
+        pipeline.set_property("flags", flags);
+        Self::from_gst_pipeline_unprepared(id, pipeline, video_sink, gst::State::Playing, true)
+    }
+
+    /// Create a new video with custom network buffering.
+    ///
+    /// Unset fields of `buffering` keep the defaults (5s `buffer-duration`, ~3MB `buffer-size`).
+    /// A short duration such as 500ms makes live streams start noticeably faster.
+    pub fn new_with_buffering(uri: &url::Url, buffering: BufferConfig) -> Result<Self, Error> {
+        gst::init()?;
+        let id = next_video_id();
+        let (pipeline, video_sink) = Self::build_pipeline_with_headers_vec(
+            uri,
+            None,
+            &buffering,
+            &RtspConfig::default(),
+            &video_log_target(id),
+        )?;
+        Self::from_gst_pipeline_unprepared(id, pipeline, video_sink, gst::State::Playing, true)
+    }
+
+    /// Create a new video from an `rtsp://` URI with custom `rtspsrc` latency and transports.
+    ///
+    /// A low latency (e.g. 200ms) suits local cameras; [`RtspProtocols::Tcp`] gets through
+    /// firewalls that drop RTP over UDP.
+    ///
+    /// [`RtspProtocols::Tcp`]: subwave_core::rtsp::RtspProtocols::Tcp
+    pub fn new_with_rtsp(uri: &url::Url, rtsp: RtspConfig) -> Result<Self, Error> {
+        gst::init()?;
+        let id = next_video_id();
+        let (pipeline, video_sink) = Self::build_pipeline_with_headers_vec(
+            uri,
+            None,
+            &BufferConfig::default(),
+            &rtsp,
+            &video_log_target(id),
+        )?;
+        let video = Self::from_gst_pipeline_unprepared(
+            id,
+            pipeline,
+            video_sink,
+            gst::State::Playing,
+            true,
+        )?;
+        video.write().rtsp_config = rtsp;
+        Ok(video)
+    }
+
+    /// Create a new video whose playbin3 picks video decoders per `decoder`.
+    ///
+    /// Only this video's pipeline is affected, see [`DecoderPreference`].
+    pub fn new_with_decoder_preference(
+        uri: &url::Url,
+        decoder: DecoderPreference,
+    ) -> Result<Self, Error> {
+        let video = Self::new_with_setup(uri, |pipeline| {
+            decoder.apply_to_playbin(pipeline.upcast_ref())
+        })?;
+        video.write().decoder_preference = decoder;
+        Ok(video)
     }
 
     /// Create a new video that starts playback at a specific position.
@@ -699,12 +2609,18 @@ impl AppsinkVideo {
                 .map(|(k, v)| (k.as_ref().to_string(), v.as_ref().to_string()))
                 .collect()
         });
-        let (pipeline, video_sink) =
-            Self::build_pipeline_with_headers_vec(uri, owned_headers.as_deref())?;
+        let id = next_video_id();
+        let (pipeline, video_sink) = Self::build_pipeline_with_headers_vec(
+            uri,
+            owned_headers.as_deref(),
+            &BufferConfig::default(),
+            &RtspConfig::default(),
+            &video_log_target(id),
+        )?;
 
         // Start PAUSED to avoid any playback before we seek
         let mut video =
-            Self::from_gst_pipeline_with_state(pipeline, video_sink, gst::State::Paused)?;
+            Self::from_gst_pipeline_unprepared(id, pipeline, video_sink, gst::State::Paused, true)?;
 
         // Configure autoplay gating: only start after the seek completes
         {
@@ -719,6 +2635,125 @@ impl AppsinkVideo {
     }
 }
 
+/// Future returned by [`AppsinkVideo::prepare`].
+struct Prepare(Option<Result<AppsinkVideo, Error>>);
+
+impl Future for Prepare {
+    type Output = Result<AppsinkVideo, Error>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let video = match self.0.take().expect("Prepare polled after completion") {
+            Ok(video) => video,
+            Err(e) => return Poll::Ready(Err(e)),
+        };
+
+        let ready = video.read().readiness.poll(cx);
+        match ready {
+            None => {
+                self.0 = Some(Ok(video));
+                Poll::Pending
+            }
+            Some(true) => {
+                // Fill in what the constructor could not query before preroll
+                {
+                    let mut inner = video.write();
+                    if let Some(duration) = inner.source.query_duration::<gst::ClockTime>() {
+                        inner.duration = Duration::from_nanos(duration.nseconds());
+                    }
+                    if let Some(seekable) = query_seekable(&inner.source) {
+                        inner.seekable = seekable;
+                    }
+                    if !inner.is_live && inner.duration.is_zero() {
+                        inner.is_live = !inner.seekable;
+                    }
+                }
+                Poll::Ready(Ok(video))
+            }
+            Some(false) => {
+                let inner = video.read();
+                let error = inner
+                    .bus
+                    .pop_filtered(&[gst::MessageType::Error])
+                    .and_then(|msg| match msg.view() {
+                        gst::MessageView::Error(err) => {
+                            let network_source =
+                                subwave_core::is_network_source(err.src(), inner.uri.as_ref());
+                            Some(subwave_core::from_gst_error(&err.error(), network_source))
+                        }
+                        _ => None,
+                    })
+                    .unwrap_or_else(|| Error::Pipeline("failed to preroll".into()));
+                // Dropping the video shuts the pipeline down
+                Poll::Ready(Err(error))
+            }
+        }
+    }
+}
+
+/// Bytes pushed per `need-data` when appsrc does not ask for a specific amount.
+const APPSRC_CHUNK_SIZE: usize = 256 * 1024;
+
+/// A window into shared bytes, so buffers reference the data instead of copying it.
+struct ByteRange(Arc<[u8]>, std::ops::Range<usize>);
+
+impl AsRef<[u8]> for ByteRange {
+    fn as_ref(&self) -> &[u8] {
+        &self.0[self.1.clone()]
+    }
+}
+
+/// Configure `appsrc` as a random-access source over `data`: every `need-data` is answered
+/// with the next chunk, `seek-data` moves the read offset, and EOS follows the last byte.
+fn serve_bytes(
+    appsrc: &gst_app::AppSrc,
+    data: Arc<[u8]>,
+    caps: Option<&gst::Caps>,
+    log_target: &str,
+) {
+    appsrc.set_caps(caps);
+    appsrc.set_stream_type(gst_app::AppStreamType::RandomAccess);
+    appsrc.set_size(data.len() as i64);
+
+    let offset = Arc::new(AtomicU64::new(0));
+    let seek_offset = Arc::clone(&offset);
+    let len = data.len() as u64;
+    let log_target = log_target.to_owned();
+    appsrc.set_callbacks(
+        gst_app::AppSrcCallbacks::builder()
+            .need_data(move |appsrc, length| {
+                let start = offset.load(Ordering::SeqCst).min(len) as usize;
+                if start == data.len() {
+                    let _ = appsrc.end_of_stream();
+                    return;
+                }
+                // u32::MAX means any amount
+                let length = match length {
+                    0 | u32::MAX => APPSRC_CHUNK_SIZE,
+                    length => length as usize,
+                };
+                let end = (start + length).min(data.len());
+                let mut buffer = gst::Buffer::from_slice(ByteRange(Arc::clone(&data), start..end));
+                if let Some(buffer) = buffer.get_mut() {
+                    buffer.set_offset(start as u64);
+                }
+                offset.store(end as u64, Ordering::SeqCst);
+                if let Err(e) = appsrc.push_buffer(buffer) {
+                    log::debug!(target: &log_target, "appsrc refused buffer at {start}: {e:?}");
+                }
+            })
+            // Only one chunk is pushed per need-data, so there is no feeding loop to pause
+            .enough_data(|_| {})
+            .seek_data(move |_, position| {
+                if position > len {
+                    return false;
+                }
+                seek_offset.store(position, Ordering::SeqCst);
+                true
+            })
+            .build(),
+    );
+}
+
 impl Drop for AppsinkVideo {
     fn drop(&mut self) {
         let inner = self.0.get_mut().expect("failed to lock");
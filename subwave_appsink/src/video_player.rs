@@ -1,5 +1,7 @@
-use crate::{render_pipeline::VideoPrimitive, video::AppsinkVideo};
-use gstreamer::prelude::ElementExtManual;
+use crate::{
+    internal::Internal, pixel_format::VideoPixelFormat, render_pipeline::VideoPrimitive,
+    video::AppsinkVideo,
+};
 use gstreamer::{self as gst, glib};
 use iced::{
     Element,
@@ -7,32 +9,108 @@ use iced::{
     wgpu::TextureFormat,
 };
 use iced_wgpu::primitive::Renderer as PrimitiveRenderer;
-use log::error;
 use std::sync::Arc;
-use std::{marker::PhantomData, sync::atomic::Ordering, time::Instant};
+use std::{
+    marker::PhantomData,
+    sync::atomic::Ordering,
+    time::{Duration, Instant},
+};
 use subwave_core::video::video_trait::Video;
 
-type ErrorCallback<'a, Message> = Box<dyn Fn(&glib::Error) -> Message + 'a>;
+type EventCallback<'a, Message> = Box<dyn Fn(&PlayerEvent) -> Option<Message> + 'a>;
+type FrameCallback<'a, Message> = Box<dyn Fn(&FrameRef<'_>) -> Message + 'a>;
+
+/// A decoded frame lent to [`VideoPlayer::on_frame`] handlers while the frame buffer is
+/// locked.
+///
+/// Pixels are NV12, or for 10-bit sources P010 with 16-bit little-endian samples; rows of
+/// both planes keep the decoder's stride, which can exceed the visible width.
+#[derive(Debug)]
+pub struct FrameRef<'a> {
+    width: u32,
+    height: u32,
+    format: VideoPixelFormat,
+    strides: [u32; 2],
+    data: &'a [u8],
+}
+
+impl FrameRef<'_> {
+    pub fn width(&self) -> u32 {
+        self.width
+    }
+
+    pub fn height(&self) -> u32 {
+        self.height
+    }
+
+    pub fn format(&self) -> VideoPixelFormat {
+        self.format
+    }
+
+    /// Row strides in bytes of the Y and interleaved UV planes.
+    pub fn strides(&self) -> [u32; 2] {
+        self.strides
+    }
+
+    /// Both planes, Y followed by UV.
+    pub fn data(&self) -> &[u8] {
+        self.data
+    }
+
+    /// The luma plane, `height` rows of `strides()[0]` bytes.
+    pub fn y_plane(&self) -> &[u8] {
+        let len = (self.strides[0] * self.height) as usize;
+        &self.data[..len.min(self.data.len())]
+    }
+
+    /// The interleaved chroma plane, `height / 2` (rounded up) rows of `strides()[1]` bytes.
+    pub fn uv_plane(&self) -> &[u8] {
+        let y_len = self.y_plane().len();
+        let len = (self.strides[1] * self.height.div_ceil(2)) as usize;
+        &self.data[y_len..(y_len + len).min(self.data.len())]
+    }
+}
+
+/// Playback events reported to [`VideoPlayer::on_event`] handlers and returned by
+/// [`AppsinkVideo::pump`].
+#[derive(Debug, Clone)]
+pub enum PlayerEvent {
+    /// A new frame was decoded and will be drawn.
+    NewFrame,
+    /// The video reached the end of stream.
+    Eos,
+    /// Playback hit an error that could not be recovered by reconnecting.
+    Error(glib::Error),
+    /// Buffering fill level in percent; `100` means playback can continue.
+    Buffering(i32),
+    /// The media duration became known or changed.
+    DurationChanged(Duration),
+    /// A new stream collection arrived, so the audio/subtitle track lists changed.
+    StreamsChanged,
+    /// The pipeline moved between states.
+    StateChanged {
+        old: gst::State,
+        current: gst::State,
+    },
+    /// Playback switched to the URI queued with [`AppsinkVideo::set_next_uri`].
+    TrackChanged,
+    /// The subtitle cue changed, with its plain text (empty between cues), see
+    /// [`AppsinkVideo::current_subtitle_text`].
+    SubtitleText(String),
+}
 
 /// Video player widget which displays the current frame of a [`Video`](crate::Video).
-pub struct VideoPlayer<'a, Message, Theme = iced::Theme, Renderer = iced::Renderer>
-where
-    Renderer: PrimitiveRenderer,
-{
-    video: &'a AppsinkVideo,
-    content_fit: iced::ContentFit,
+pub struct VideoPlayer<'a, Message, Theme = iced::Theme, Renderer = iced::Renderer> {
+    pub(crate) video: &'a AppsinkVideo,
+    pub(crate) content_fit: iced::ContentFit,
     width: iced::Length,
     height: iced::Length,
-    on_end_of_stream: Option<Message>,
-    on_new_frame: Option<Message>,
-    on_error: Option<ErrorCallback<'a, Message>>,
+    on_event: Vec<EventCallback<'a, Message>>,
+    on_frame: Option<FrameCallback<'a, Message>>,
     _phantom: PhantomData<(Theme, Renderer)>,
 }
 
-impl<'a, Message, Theme, Renderer> VideoPlayer<'a, Message, Theme, Renderer>
-where
-    Renderer: PrimitiveRenderer,
-{
+impl<'a, Message, Theme, Renderer> VideoPlayer<'a, Message, Theme, Renderer> {
     /// Creates a new video player widget for a given video.
     pub fn new(video: &'a AppsinkVideo) -> Self {
         VideoPlayer {
@@ -40,9 +118,8 @@ where
             content_fit: iced::ContentFit::default(),
             width: iced::Length::Shrink,
             height: iced::Length::Shrink,
-            on_end_of_stream: None,
-            on_new_frame: None,
-            on_error: None,
+            on_event: Vec::new(),
+            on_frame: None,
             _phantom: Default::default(),
         }
     }
@@ -71,30 +148,191 @@ where
         }
     }
 
+    /// Message to send for every [`PlayerEvent`].
+    ///
+    /// Can be combined with the per-event builders below; each registered handler is called.
+    pub fn on_event<F>(self, on_event: F) -> Self
+    where
+        F: 'a + Fn(&PlayerEvent) -> Message,
+    {
+        self.on_event_filtered(move |event| Some(on_event(event)))
+    }
+
+    fn on_event_filtered<F>(mut self, handler: F) -> Self
+    where
+        F: 'a + Fn(&PlayerEvent) -> Option<Message>,
+    {
+        self.on_event.push(Box::new(handler));
+        self
+    }
+
     /// Message to send when the video reaches the end of stream (i.e., the video ends).
-    pub fn on_end_of_stream(self, on_end_of_stream: Message) -> Self {
-        VideoPlayer {
-            on_end_of_stream: Some(on_end_of_stream),
-            ..self
-        }
+    pub fn on_end_of_stream(self, on_end_of_stream: Message) -> Self
+    where
+        Message: Clone + 'a,
+    {
+        self.on_event_filtered(move |event| {
+            matches!(event, PlayerEvent::Eos).then(|| on_end_of_stream.clone())
+        })
     }
 
     /// Message to send when the video receives a new frame.
-    pub fn on_new_frame(self, on_new_frame: Message) -> Self {
+    pub fn on_new_frame(self, on_new_frame: Message) -> Self
+    where
+        Message: Clone + 'a,
+    {
+        self.on_event_filtered(move |event| {
+            matches!(event, PlayerEvent::NewFrame).then(|| on_new_frame.clone())
+        })
+    }
+
+    /// Message built from every new frame, e.g. for motion detection without decoding the
+    /// media a second time.
+    ///
+    /// Called with the frame that is about to be drawn, while the frame buffer is locked, so
+    /// keep it short and copy out whatever the message needs.
+    pub fn on_frame<F>(self, on_frame: F) -> Self
+    where
+        F: 'a + Fn(&FrameRef<'_>) -> Message,
+    {
         VideoPlayer {
-            on_new_frame: Some(on_new_frame),
+            on_frame: Some(Box::new(on_frame)),
             ..self
         }
     }
 
+    /// Message to send when the media duration becomes known or changes, which for network
+    /// streams often happens after playback has started.
+    pub fn on_duration_changed<F>(self, on_duration_changed: F) -> Self
+    where
+        F: 'a + Fn(Duration) -> Message,
+    {
+        self.on_event_filtered(move |event| match event {
+            PlayerEvent::DurationChanged(duration) => Some(on_duration_changed(*duration)),
+            _ => None,
+        })
+    }
+
+    /// Message to send when playback switches to the URI queued with
+    /// [`AppsinkVideo::set_next_uri`].
+    pub fn on_track_changed(self, on_track_changed: Message) -> Self
+    where
+        Message: Clone + 'a,
+    {
+        self.on_event_filtered(move |event| {
+            matches!(event, PlayerEvent::TrackChanged).then(|| on_track_changed.clone())
+        })
+    }
+
+    /// Message to send when a stream collection arrives, i.e. once the audio, video and
+    /// subtitle track lists are populated after opening the media and again whenever they
+    /// change (e.g. an external subtitle file was added).
+    pub fn on_streams_changed(self, on_streams_changed: Message) -> Self
+    where
+        Message: Clone + 'a,
+    {
+        self.on_event_filtered(move |event| {
+            matches!(event, PlayerEvent::StreamsChanged).then(|| on_streams_changed.clone())
+        })
+    }
+
+    /// Message to send with the plain text of each subtitle cue as it arrives, e.g. to read
+    /// subtitles aloud. Called with an empty string when a cue is cleared.
+    pub fn on_subtitle_text<F>(self, on_subtitle_text: F) -> Self
+    where
+        F: 'a + Fn(&str) -> Message,
+    {
+        self.on_event_filtered(move |event| match event {
+            PlayerEvent::SubtitleText(text) => Some(on_subtitle_text(text)),
+            _ => None,
+        })
+    }
+
     /// Message to send when the video playback encounters an error.
     pub fn on_error<F>(self, on_error: F) -> Self
     where
         F: 'a + Fn(&glib::Error) -> Message,
     {
-        VideoPlayer {
-            on_error: Some(Box::new(on_error)),
-            ..self
+        self.on_event_filtered(move |event| match event {
+            PlayerEvent::Error(err) => Some(on_error(err)),
+            _ => None,
+        })
+    }
+
+    /// Message to send when the buffering fill level changes, with the level in percent.
+    pub fn on_buffering<F>(self, on_buffering: F) -> Self
+    where
+        F: 'a + Fn(i32) -> Message,
+    {
+        self.on_event_filtered(move |event| match event {
+            PlayerEvent::Buffering(percent) => Some(on_buffering(*percent)),
+            _ => None,
+        })
+    }
+
+    /// Layout shared with the `ImageVideoPlayer` fallback, sized from the video's display
+    /// size so anamorphic content keeps its aspect.
+    pub(crate) fn layout_node(&self, limits: &layout::Limits) -> layout::Node {
+        let (video_width, video_height) = self.video.display_size();
+
+        // based on `Image::layout`
+        let image_size = iced::Size::new(video_width as f32, video_height as f32);
+        let raw_size = limits.resolve(self.width, self.height, image_size);
+        let full_size = self.content_fit.fit(image_size, raw_size);
+        let final_size = iced::Size {
+            width: match self.width {
+                iced::Length::Shrink => f32::min(raw_size.width, full_size.width),
+                _ => raw_size.width,
+            },
+            height: match self.height {
+                iced::Length::Shrink => f32::min(raw_size.height, full_size.height),
+                _ => raw_size.height,
+            },
+        };
+
+        layout::Node::new(final_size)
+    }
+
+    /// Process the bus and publish events on each redraw, for both player widgets.
+    pub(crate) fn handle_event(
+        &self,
+        event: &iced::Event,
+        shell: &mut advanced::Shell<'_, Message>,
+    ) {
+        if let iced::Event::Window(iced::window::Event::RedrawRequested(_)) = &event {
+            let inner = &mut *self.video.write();
+            let events = inner.pump(false);
+            for event in &events {
+                self.emit(shell, event);
+            }
+
+            if let Some(on_frame) = &self.on_frame
+                && events
+                    .iter()
+                    .any(|event| matches!(event, PlayerEvent::NewFrame))
+            {
+                let (width, height) = inner.video_props.size();
+                let (width, height) = (width.max(0) as u32, height.max(0) as u32);
+                inner.frame.read(|frame| {
+                    shell.publish(on_frame(&FrameRef {
+                        width,
+                        height,
+                        format: frame.layout.format,
+                        strides: frame.layout.strides,
+                        data: &frame.data,
+                    }))
+                });
+            }
+
+            shell.request_redraw();
+        }
+    }
+
+    fn emit(&self, shell: &mut advanced::Shell<'_, Message>, event: &PlayerEvent) {
+        for handler in &self.on_event {
+            if let Some(message) = handler(event) {
+                shell.publish(message);
+            }
         }
     }
 }
@@ -118,24 +356,7 @@ where
         _renderer: &Renderer,
         limits: &layout::Limits,
     ) -> layout::Node {
-        let (video_width, video_height) = self.video.size();
-
-        // based on `Image::layout`
-        let image_size = iced::Size::new(video_width as f32, video_height as f32);
-        let raw_size = limits.resolve(self.width, self.height, image_size);
-        let full_size = self.content_fit.fit(image_size, raw_size);
-        let final_size = iced::Size {
-            width: match self.width {
-                iced::Length::Shrink => f32::min(raw_size.width, full_size.width),
-                _ => raw_size.width,
-            },
-            height: match self.height {
-                iced::Length::Shrink => f32::min(raw_size.height, full_size.height),
-                _ => raw_size.height,
-            },
-        };
-
-        layout::Node::new(final_size)
+        self.layout_node(limits)
     }
 
     fn draw(
@@ -151,9 +372,13 @@ where
         let mut inner = self.video.write();
 
         // bounds based on `Image::draw`
-        let props = inner.video_props.lock().expect("lock video props");
-        let image_size = iced::Size::new(props.width as f32, props.height as f32);
-        drop(props);
+        let (width, height) = inner.video_props.size();
+        let image_size = iced::Size::new(width as f32, height as f32);
+        let has_video = inner.video_props.has_video();
+        // Audio-only playback has nothing to draw
+        if !has_video || image_size.width <= 0.0 || image_size.height <= 0.0 {
+            return;
+        }
         let bounds = layout.bounds();
         let adjusted_fit = self.content_fit.fit(image_size, bounds.size());
         let scale = iced::Vector::new(
@@ -175,21 +400,11 @@ where
 
         let drawing_bounds = iced::Rectangle::new(position, final_size);
 
-        let upload_frame = inner.upload_frame.swap(false, Ordering::SeqCst);
-
-        if upload_frame {
-            let last_frame_time = inner
-                .last_frame_time
-                .lock()
-                .map(|time| *time)
-                .unwrap_or_else(|_| Instant::now());
-            inner.set_av_offset(Instant::now() - last_frame_time);
-        }
+        let upload_frame = take_frame_upload(&mut inner);
 
         let render = |renderer: &mut Renderer| {
-            let props = inner.video_props.lock().expect("lock video props");
-            let dims = (props.width as _, props.height as _);
-            drop(props);
+            let (width, height) = inner.video_props.size();
+            let dims = (width as _, height as _);
 
             renderer.draw_primitive(
                 drawing_bounds,
@@ -202,7 +417,9 @@ where
                     // Use the same format as the surface; iced will pass it to our prepare()
                     // This argument is ignored by our pipeline creation and replaced with actual surface format
                     TextureFormat::Bgra8UnormSrgb,
-                ),
+                )
+                .with_tone_mapping(inner.tone_mapping)
+                .with_scaling_filter(inner.scaling_filter),
             );
         };
 
@@ -224,161 +441,23 @@ where
         shell: &mut advanced::Shell<'_, Message>,
         _viewport: &iced::Rectangle,
     ) {
-        let mut inner = self.video.write();
+        self.handle_event(event, shell);
+    }
+}
 
-        if let iced::Event::Window(iced::window::Event::RedrawRequested(_)) = &event {
-            if inner.restart_stream || (!inner.is_eos && !inner.paused()) {
-                let mut restart_stream = false;
-                if inner.restart_stream {
-                    restart_stream = true;
-                    // Set flag to false to avoid potentially multiple seeks
-                    inner.restart_stream = false;
-                }
-                let mut eos_pause = false;
-
-                while let Some(msg) = inner.bus.pop_filtered(&[
-                    gst::MessageType::Error,
-                    gst::MessageType::Eos,
-                    gst::MessageType::AsyncDone,
-                    gst::MessageType::StateChanged,
-                    gst::MessageType::Buffering,
-                    gst::MessageType::StreamCollection,
-                ]) {
-                    match msg.view() {
-                        gst::MessageView::Error(err) => {
-                            error!("bus returned an error: {err}");
-                            let gst_error = err.error();
-
-                            // Check if we should retry on this error
-                            if inner.should_retry_on_error(&gst_error) {
-                                log::info!(
-                                    "Network error detected, scheduling reconnection attempt"
-                                );
-
-                                // Schedule reconnection on next frame
-                                // We can't reconnect immediately in the message handler
-                                inner.is_reconnecting = true;
-                            } else {
-                                // Non-recoverable error, notify the application
-                                if let Some(ref on_error) = self.on_error {
-                                    shell.publish(on_error(&gst_error));
-                                }
-                            }
-                        }
-                        gst::MessageView::Eos(_eos) => {
-                            if let Some(on_end_of_stream) = self.on_end_of_stream.clone() {
-                                shell.publish(on_end_of_stream);
-                            }
-                            if inner.looping {
-                                restart_stream = true;
-                            } else {
-                                eos_pause = true;
-                            }
-                        }
-                        gst::MessageView::AsyncDone(_) => {
-                            log::debug!("GStreamer AsyncDone message received - seek completed");
-                            // Clear the cached seek position
-                            inner.seek_position = None;
-
-                            // If we are gating autoplay until seek completes, start playback now
-                            if inner.pending_play_after_seek {
-                                // Optional sanity check: ensure current position is at/near target
-                                let _ = inner
-                                    .source
-                                    .query_position::<gst::ClockTime>()
-                                    .map(|pos| pos.nseconds());
-                                // Clear gating regardless; only auto-play if user did not pause
-                                inner.pending_play_after_seek = false;
-                                if !inner.user_paused {
-                                    inner.set_paused(false);
-                                } else {
-                                    log::debug!(
-                                        "Autoplay gated by user pause; staying paused after seek"
-                                    );
-                                }
-                            }
-                        }
-                        gst::MessageView::StateChanged(state_changed) => {
-                            if state_changed
-                                .src()
-                                .map(|s| s == &inner.source)
-                                .unwrap_or(false)
-                            {
-                                log::debug!(
-                                    "Pipeline state changed: {:?} -> {:?}",
-                                    state_changed.old(),
-                                    state_changed.current()
-                                );
-                            }
-                        }
-                        gst::MessageView::Buffering(_) => {}
-                        gst::MessageView::StreamCollection(stream_collection) => {
-                            log::info!("Received StreamCollection message");
-
-                            let collection = stream_collection.stream_collection();
-                            // Update the stream collection in our video state
-                            inner.update_stream_collection(collection);
-
-                            // Send stream selection event to select default streams
-                            if let Err(e) = inner.send_stream_selection() {
-                                log::error!("Failed to send stream selection: {:?}", e);
-                            }
-                        }
-                        _ => {}
-                    }
-                }
-
-                // Don't run eos_pause if restart_stream is true; fixes "pausing" after restarting a stream
-                if restart_stream {
-                    if let Err(err) = inner.restart_stream() {
-                        error!("cannot restart stream (can't seek): {err:#?}");
-                    }
-                } else if eos_pause {
-                    inner.is_eos = true;
-                    inner.set_paused(true);
-                }
-
-                // Handle reconnection attempts after network errors
-                if inner.is_reconnecting {
-                    inner.is_reconnecting = false;
-                    if let Err(e) = inner.attempt_reconnect() {
-                        log::error!("Reconnection attempt failed: {:?}", e);
-                        // Notify the application about the failure
-                        if let Some(ref on_error) = self.on_error {
-                            shell.publish(on_error(&glib::Error::new(
-                                gst::CoreError::Failed,
-                                &format!("Failed to reconnect: {:?}", e),
-                            )));
-                        }
-                    }
-                }
-
-                if inner.upload_frame.load(Ordering::SeqCst) {
-                    // Reset error state on successful frame
-                    inner.reset_error_state();
-                    if let Some(on_new_frame) = self.on_new_frame.clone() {
-                        shell.publish(on_new_frame);
-                    }
-                    // Update position cache when we get a new frame
-                    inner.update_position_cache();
-
-                    // Periodically update connection stats for network streams
-                    static mut STATS_COUNTER: u64 = 0;
-                    unsafe {
-                        STATS_COUNTER += 1;
-                        if STATS_COUNTER.is_multiple_of(60) {
-                            // Every ~60 frames (roughly 1-2 seconds)
-                            inner.update_connection_stats();
-                        }
-                    }
-                }
-
-                shell.request_redraw();
-            } else {
-                shell.request_redraw();
-            }
-        }
+/// Claim the frame the worker flagged for upload, if any, and record how long it waited to
+/// be drawn for A/V sync.
+pub(crate) fn take_frame_upload(inner: &mut Internal) -> bool {
+    let upload_frame = inner.upload_frame.swap(false, Ordering::SeqCst);
+    if upload_frame {
+        let last_frame_time = inner
+            .last_frame_time
+            .lock()
+            .map(|time| *time)
+            .unwrap_or_else(|_| Instant::now());
+        inner.set_av_offset(Instant::now() - last_frame_time);
     }
+    upload_frame
 }
 
 impl<'a, Message, Theme, Renderer> From<VideoPlayer<'a, Message, Theme, Renderer>>
@@ -0,0 +1,102 @@
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{Mutex, PoisonError};
+
+use subwave_core::video::types::VideoProperties;
+
+/// [`VideoProperties`] shared between the appsink worker and the UI thread.
+///
+/// The properties only change on caps changes, but the draw path reads them every frame, so
+/// each update also refreshes lock-free copies that readers use instead. The mutex keeps
+/// updates whole: writers hold it while they change the properties and publish the copies.
+#[derive(Debug)]
+pub(crate) struct VideoProps {
+    authoritative: Mutex<VideoProperties>,
+    // Width in the high 32 bits and height in the low, so the two never tear
+    size: AtomicU64,
+    framerate: AtomicU64,
+    has_video: AtomicBool,
+}
+
+impl VideoProps {
+    pub(crate) fn new(props: VideoProperties) -> Self {
+        let this = Self {
+            authoritative: Mutex::new(props.clone()),
+            size: AtomicU64::new(0),
+            framerate: AtomicU64::new(0),
+            has_video: AtomicBool::new(false),
+        };
+        this.publish(&props);
+        this
+    }
+
+    /// Change the properties with `f` and publish the result to readers.
+    pub(crate) fn update<R>(&self, f: impl FnOnce(&mut VideoProperties) -> R) -> R {
+        // The properties are plain values, so a panicked writer leaves nothing inconsistent
+        let mut props = self
+            .authoritative
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner);
+        let result = f(&mut props);
+        self.publish(&props);
+        result
+    }
+
+    fn publish(&self, props: &VideoProperties) {
+        self.size
+            .store(pack_size(props.width, props.height), Ordering::Release);
+        self.framerate
+            .store(props.framerate.to_bits(), Ordering::Release);
+        self.has_video.store(props.has_video, Ordering::Release);
+    }
+
+    /// Frame size as `(width, height)`.
+    pub(crate) fn size(&self) -> (i32, i32) {
+        unpack_size(self.size.load(Ordering::Acquire))
+    }
+
+    pub(crate) fn framerate(&self) -> f64 {
+        f64::from_bits(self.framerate.load(Ordering::Acquire))
+    }
+
+    pub(crate) fn has_video(&self) -> bool {
+        self.has_video.load(Ordering::Acquire)
+    }
+}
+
+fn pack_size(width: i32, height: i32) -> u64 {
+    ((width as u32 as u64) << 32) | height as u32 as u64
+}
+
+fn unpack_size(size: u64) -> (i32, i32) {
+    ((size >> 32) as u32 as i32, size as u32 as i32)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn updates_are_visible_without_locking() {
+        let props = VideoProps::new(VideoProperties {
+            width: 0,
+            height: 0,
+            framerate: 0.0,
+            has_video: false,
+        });
+        assert_eq!(props.size(), (0, 0));
+
+        props.update(|p| {
+            p.width = 1920;
+            p.height = 1080;
+            p.framerate = 24000.0 / 1001.0;
+            p.has_video = true;
+        });
+        assert_eq!(props.size(), (1920, 1080));
+        assert_eq!(props.framerate(), 24000.0 / 1001.0);
+        assert!(props.has_video());
+
+        // Both halves keep their sign through the packing
+        props.update(|p| (p.width, p.height) = (-2, 7));
+        assert_eq!(props.size(), (-2, 7));
+    }
+}
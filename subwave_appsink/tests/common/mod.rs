@@ -0,0 +1,12 @@
+//! Helpers shared by the integration tests.
+
+pub use subwave_core::test_util::TestClip;
+
+use subwave_appsink::video::AppsinkVideo;
+
+/// Encode a [`TestClip`] and open it; keep the clip alive for as long as the video plays it.
+pub fn open_clip() -> (TestClip, AppsinkVideo) {
+    let clip = TestClip::new();
+    let video = AppsinkVideo::new(clip.uri()).expect("open clip");
+    (clip, video)
+}
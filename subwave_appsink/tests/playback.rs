@@ -0,0 +1,243 @@
+mod common;
+
+use std::time::{Duration, Instant};
+
+use common::{TestClip, open_clip};
+use gstreamer::{self as gst, prelude::*};
+use subwave_appsink::video::{AppsinkVideo, SubtitleStyle};
+use subwave_appsink::video_player::PlayerEvent;
+use subwave_core::Error;
+use subwave_core::video::{
+    types::{DecoderPreference, DeinterlaceMode, Position},
+    video_trait::Video,
+};
+
+/// Pump `video` until `done` holds or 10 seconds pass, collecting the events.
+fn pump_until(
+    video: &AppsinkVideo,
+    mut done: impl FnMut(&AppsinkVideo) -> bool,
+) -> Vec<PlayerEvent> {
+    let deadline = Instant::now() + Duration::from_secs(10);
+    let mut events = Vec::new();
+    while !done(video) && Instant::now() < deadline {
+        events.extend(video.pump());
+        std::thread::sleep(Duration::from_millis(10));
+    }
+    events
+}
+
+#[test]
+#[ignore = "needs gst-plugins-base and gst-plugins-good; run with --ignored"]
+fn frame_seek_lands_within_one_frame() {
+    let (_clip, mut video) = open_clip();
+    video.set_paused(true);
+    video.seek(Position::Frame(90), true).expect("seek");
+    let _ = video.pipeline().state(gst::ClockTime::from_seconds(5));
+
+    let frame = Duration::from_secs(1) / 30;
+    let position = video.position();
+    assert!(
+        position.abs_diff(Duration::from_secs(3)) <= frame,
+        "frame 90 landed at {position:?}"
+    );
+}
+
+#[test]
+#[ignore = "needs gst-plugins-base and gst-plugins-good; run with --ignored"]
+fn coalesced_seeks_keep_only_the_latest_target() {
+    let (_clip, mut video) = open_clip();
+    video.set_paused(true);
+    for secs in 1..=3 {
+        video
+            .seek_coalesced(Duration::from_secs(secs), true)
+            .expect("seek");
+    }
+    assert_eq!(video.seek_target(), Some(Duration::from_secs(3)));
+
+    // The first seek completes, then only the latest target is issued
+    pump_until(&video, |video| video.seek_target().is_none());
+    assert_eq!(video.seek_target(), None);
+    let frame = Duration::from_secs(1) / 30;
+    let position = video.position();
+    assert!(
+        position.abs_diff(Duration::from_secs(3)) <= frame,
+        "coalesced seeks landed at {position:?}"
+    );
+}
+
+#[test]
+#[ignore = "needs gst-plugins-base and gst-plugins-good; run with --ignored"]
+fn pump_reports_eos_without_a_widget() {
+    let (_clip, mut video) = open_clip();
+    video
+        .seek(Duration::from_millis(4500), true)
+        .expect("seek near the end");
+    video.set_paused(false);
+
+    let events = pump_until(&video, |video| video.eos());
+    assert!(events.iter().any(|event| matches!(event, PlayerEvent::Eos)));
+    assert!(video.eos());
+}
+
+#[test]
+#[ignore = "needs gst-plugins-base and gst-plugins-good; run with --ignored"]
+fn pump_completes_paused_seeks_and_reports_each_frame_once() {
+    let (_clip, mut video) = open_clip();
+    video.set_paused(true);
+    video
+        .seek_coalesced(Duration::from_secs(2), true)
+        .expect("seek");
+
+    let mut new_frames = 0;
+    let deadline = Instant::now() + Duration::from_secs(10);
+    while (video.seek_target().is_some() || new_frames == 0) && Instant::now() < deadline {
+        new_frames += video
+            .pump()
+            .iter()
+            .filter(|event| matches!(event, PlayerEvent::NewFrame))
+            .count();
+        std::thread::sleep(Duration::from_millis(10));
+    }
+    assert_eq!(video.seek_target(), None);
+    assert!(video.paused());
+    assert!(new_frames >= 1);
+
+    // The prerolled frame was claimed; nothing new arrives while paused
+    std::thread::sleep(Duration::from_millis(100));
+    assert!(
+        !video
+            .pump()
+            .iter()
+            .any(|event| matches!(event, PlayerEvent::NewFrame))
+    );
+}
+
+#[test]
+#[ignore = "needs gst-plugins-base and gst-plugins-good; run with --ignored"]
+fn volume_changes_leave_mute_alone() {
+    let (_clip, mut video) = open_clip();
+    video.set_volume(0.5);
+    video.set_muted(true);
+    video.set_volume(0.8);
+    assert!(video.muted());
+    assert_eq!(video.volume(), 0.8);
+}
+
+#[test]
+#[ignore = "needs gst-plugins-base and gst-plugins-good; run with --ignored"]
+fn decoder_preference_leaves_the_registry_alone() {
+    let clip = TestClip::new();
+    let ranks = || {
+        gst::ElementFactory::factories_with_type(
+            gst::ElementFactoryType::DECODER | gst::ElementFactoryType::MEDIA_VIDEO,
+            gst::Rank::NONE,
+        )
+        .iter()
+        .map(|factory| (factory.name().to_string(), factory.rank()))
+        .collect::<Vec<_>>()
+    };
+    let before = ranks();
+
+    let video =
+        AppsinkVideo::new_with_decoder_preference(clip.uri(), DecoderPreference::ForceSoftware)
+            .expect("open clip");
+    assert!(video.duration() > Duration::ZERO);
+    assert_eq!(ranks(), before);
+}
+
+#[test]
+#[ignore = "needs gst-plugins-base and gst-plugins-good; run with --ignored"]
+fn clearing_the_subtitle_font_restores_the_default() {
+    let (_clip, mut video) = open_clip();
+    let pipeline = video.pipeline();
+    let default = pipeline
+        .find_property("subtitle-font-desc")
+        .expect("playbin3 has subtitle-font-desc")
+        .default_value()
+        .get::<Option<String>>()
+        .expect("string property");
+
+    video.set_subtitle_style(SubtitleStyle {
+        font_desc: Some("Sans Bold 36".into()),
+        ..SubtitleStyle::default()
+    });
+    assert_eq!(
+        pipeline
+            .property::<Option<String>>("subtitle-font-desc")
+            .as_deref(),
+        Some("Sans Bold 36")
+    );
+
+    video.set_subtitle_style(SubtitleStyle::default());
+    assert_eq!(
+        pipeline.property::<Option<String>>("subtitle-font-desc"),
+        default
+    );
+}
+
+#[test]
+#[ignore = "needs gst-plugins-base and gst-plugins-good; run with --ignored"]
+fn reload_keeps_user_settings() {
+    let (_clip, mut video) = open_clip();
+    // The clip has no audio, so the device never has to exist
+    video
+        .set_audio_sink_device("subwave-test-output")
+        .expect("pipewiresink or pulsesink");
+    video
+        .set_deinterlace(DeinterlaceMode::Force)
+        .expect("deinterlace");
+    video.set_paused(true);
+    video.seek(Duration::from_secs(1), true).expect("seek");
+    let clock = gst::glib::Object::new::<gst::SystemClock>().upcast::<gst::Clock>();
+    video.set_clock(&clock).expect("set clock");
+    video.set_base_time(gst::ClockTime::from_seconds(42));
+    let bus = video.subscribe_bus();
+    let old_pipeline = video.pipeline();
+
+    video.reload().expect("reload");
+    let pipeline = video.pipeline();
+    assert_ne!(pipeline, old_pipeline);
+
+    assert_eq!(
+        video.audio_sink_device().as_deref(),
+        Some("subwave-test-output")
+    );
+    assert!(
+        pipeline
+            .property::<Option<gst::Element>>("audio-sink")
+            .is_some()
+    );
+
+    assert_eq!(video.use_pipeline_clock(), clock);
+    assert_eq!(pipeline.base_time(), Some(gst::ClockTime::from_seconds(42)));
+
+    assert_eq!(video.deinterlace(), DeinterlaceMode::Force);
+    let deinterlacer = pipeline
+        .property::<Option<gst::Element>>("video-sink")
+        .and_then(|sink| sink.downcast::<gst::Bin>().ok())
+        .and_then(|bin| bin.by_name("deinterlacer"))
+        .expect("deinterlacer in the new sink bin");
+    let mode = deinterlacer.property_value("mode");
+    let nick = gst::glib::EnumValue::from_value(&mode).map(|(_, value)| value.nick());
+    assert_eq!(nick, Some(DeinterlaceMode::Force.as_gst_nick()));
+
+    // The seek back to 1s is posted on the new bus, which the subscription follows
+    assert!(
+        bus.try_iter()
+            .any(|msg| msg.src() == Some(pipeline.upcast_ref::<gst::Object>()))
+    );
+}
+
+#[test]
+#[ignore = "needs gst-plugins-base and gst-plugins-good; run with --ignored"]
+fn videos_without_a_uri_stay_stopped() {
+    let clip = TestClip::new();
+    let path = clip.uri().to_file_path().expect("clip path");
+    let data = std::fs::read(path).expect("read clip");
+    let mut video = AppsinkVideo::from_bytes(data.into(), "").expect("open clip from memory");
+
+    video.stop();
+    assert!(matches!(video.resume(), Err(Error::NoUri)));
+    assert!(video.is_stopped());
+    assert!(matches!(video.reload(), Err(Error::NoUri)));
+}
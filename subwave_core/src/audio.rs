@@ -0,0 +1,83 @@
+use gstreamer as gst;
+use gstreamer::prelude::*;
+
+use crate::Error;
+
+/// An audio output reported by the system's device providers (PipeWire or PulseAudio).
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct AudioDevice {
+    /// Identifier to pass to `set_audio_sink_device`: the PipeWire node name or the
+    /// PulseAudio sink name.
+    pub id: String,
+    /// Human-readable name, e.g. "Built-in Audio Analog Stereo".
+    pub name: String,
+    /// Sink element that drives this device (`pipewiresink` or `pulsesink`).
+    pub sink_factory: &'static str,
+}
+
+impl AudioDevice {
+    fn from_device(device: &gst::Device) -> Option<Self> {
+        let name = device.display_name().to_string();
+        let props = device.properties();
+
+        if let Some(node) = props
+            .as_ref()
+            .and_then(|p| p.get::<String>("node.name").ok())
+        {
+            return Some(Self {
+                id: node,
+                name,
+                sink_factory: "pipewiresink",
+            });
+        }
+
+        if device.has_property("internal-name") {
+            return Some(Self {
+                id: device.property::<String>("internal-name"),
+                name,
+                sink_factory: "pulsesink",
+            });
+        }
+
+        None
+    }
+}
+
+/// List the audio outputs currently available, using a `DeviceMonitor` filtered to `Audio/Sink`.
+///
+/// Returns an empty list if no device provider could be started.
+pub fn list_audio_devices() -> Vec<AudioDevice> {
+    if gst::init().is_err() {
+        return Vec::new();
+    }
+
+    let monitor = gst::DeviceMonitor::new();
+    monitor.add_filter(Some("Audio/Sink"), None);
+    if monitor.start().is_err() {
+        return Vec::new();
+    }
+    let devices = monitor.devices();
+    monitor.stop();
+
+    devices
+        .iter()
+        .filter_map(AudioDevice::from_device)
+        .collect()
+}
+
+/// Build an audio sink that plays to `device`.
+///
+/// `pipewiresink` is tried first (targeting the node by name), then `pulsesink`, which also
+/// reaches PipeWire through its PulseAudio compatibility layer.
+pub fn make_audio_sink(device: &str) -> Result<gst::Element, Error> {
+    if let Ok(sink) = gst::ElementFactory::make("pipewiresink").build()
+        && sink.has_property("target-object")
+    {
+        sink.set_property("target-object", device);
+        return Ok(sink);
+    }
+
+    let sink = gst::ElementFactory::make("pulsesink").build()?;
+    sink.set_property("device", device);
+    Ok(sink)
+}
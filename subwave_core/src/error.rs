@@ -1,7 +1,10 @@
 use gstreamer as gst;
 use gstreamer::glib;
+use gstreamer::prelude::*;
 use thiserror::Error;
 
+use crate::rtsp::is_rtsp_uri;
+
 #[derive(Debug, Error)]
 pub enum Error {
     #[error("{0}")]
@@ -20,6 +23,8 @@ pub enum Error {
     Io(#[from] std::io::Error),
     #[error("invalid URI")]
     Uri,
+    #[error("video has no URI to reopen")]
+    NoUri,
     #[error("failed to get media capabilities")]
     Caps,
     #[error("failed to query media duration or position")]
@@ -41,4 +46,155 @@ pub enum Error {
     SubsurfaceCreation(String),
     #[error("Pipeline error: {0}")]
     Pipeline(String),
+    // Classified GStreamer errors, see `from_gst_error`
+    #[error("Network error: {0}")]
+    Network(String),
+    #[error("Resource error: {0}")]
+    Resource(String),
+    #[error("Decode error: {0}")]
+    Decode(String),
+    #[error("Not found: {0}")]
+    NotFound(String),
+    #[error("Permission denied: {0}")]
+    PermissionDenied(String),
+    #[error("Unsupported media: {0}")]
+    Unsupported(String),
+}
+
+/// Source elements whose read and seek failures are dropped connections.
+const NETWORK_SOURCES: [&str; 3] = ["souphttpsrc", "curlhttpsrc", "rtspsrc"];
+
+/// Whether an error posted by `src` while playing `uri` comes from the network: `src` is, or
+/// sits inside, an HTTP or RTSP source element, or `uri` has an HTTP or RTSP scheme.
+pub fn is_network_source(src: Option<&gst::Object>, uri: Option<&url::Url>) -> bool {
+    let network_uri =
+        uri.is_some_and(|uri| matches!(uri.scheme(), "http" | "https") || is_rtsp_uri(uri));
+    network_uri
+        || std::iter::successors(src.cloned(), |object| object.parent()).any(|object| {
+            object
+                .downcast_ref::<gst::Element>()
+                .and_then(|element| element.factory())
+                .is_some_and(|factory| NETWORK_SOURCES.contains(&factory.name().as_str()))
+        })
+}
+
+/// Classify an error posted on a pipeline bus by its GStreamer domain and code, which unlike
+/// the message text does not depend on the locale.
+///
+/// Reading or seeking failures (`ResourceError::Read`, `OpenRead`, ...) are network errors
+/// when `network_source` is set (see [`is_network_source`]): that is how souphttpsrc and
+/// friends report dropped connections and timeouts. From a local file they are a
+/// [`Error::Resource`] that retrying will not fix. Errors that fit no category become
+/// [`Error::Pipeline`].
+pub fn from_gst_error(error: &glib::Error, network_source: bool) -> Error {
+    let message = error.message().to_string();
+    if let Some(code) = error.kind::<gst::ResourceError>() {
+        return match code {
+            gst::ResourceError::NotFound => Error::NotFound(message),
+            gst::ResourceError::NotAuthorized => Error::PermissionDenied(message),
+            gst::ResourceError::Busy
+            | gst::ResourceError::OpenRead
+            | gst::ResourceError::OpenReadWrite
+            | gst::ResourceError::Read
+            | gst::ResourceError::Seek
+            | gst::ResourceError::Sync
+                if network_source =>
+            {
+                Error::Network(message)
+            }
+            gst::ResourceError::Busy
+            | gst::ResourceError::OpenRead
+            | gst::ResourceError::OpenReadWrite
+            | gst::ResourceError::Read
+            | gst::ResourceError::Seek
+            | gst::ResourceError::Sync => Error::Resource(message),
+            _ => Error::Pipeline(message),
+        };
+    }
+    if let Some(code) = error.kind::<gst::StreamError>() {
+        return match code {
+            gst::StreamError::Decode | gst::StreamError::Demux => Error::Decode(message),
+            gst::StreamError::CodecNotFound
+            | gst::StreamError::TypeNotFound
+            | gst::StreamError::WrongType
+            | gst::StreamError::Format
+            | gst::StreamError::NotImplemented
+            | gst::StreamError::Decrypt
+            | gst::StreamError::DecryptNokey => Error::Unsupported(message),
+            _ => Error::Pipeline(message),
+        };
+    }
+    match error.kind::<gst::CoreError>() {
+        Some(gst::CoreError::MissingPlugin) | Some(gst::CoreError::NotImplemented) => {
+            Error::Unsupported(message)
+        }
+        _ => Error::Pipeline(message),
+    }
+}
+
+/// Whether a pipeline error is a transient network failure worth reconnecting for.
+pub fn is_network_error(error: &glib::Error, network_source: bool) -> bool {
+    matches!(from_gst_error(error, network_source), Error::Network(_))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn classifies_by_domain_and_code() {
+        let error = |e: glib::Error| from_gst_error(&e, true);
+        assert!(matches!(
+            error(glib::Error::new(
+                gst::ResourceError::Read,
+                "connexion perdue"
+            )),
+            Error::Network(_)
+        ));
+        assert!(matches!(
+            error(glib::Error::new(gst::ResourceError::NotFound, "404")),
+            Error::NotFound(_)
+        ));
+        assert!(matches!(
+            error(glib::Error::new(gst::ResourceError::NotAuthorized, "403")),
+            Error::PermissionDenied(_)
+        ));
+        assert!(matches!(
+            error(glib::Error::new(gst::StreamError::Decode, "bad frame")),
+            Error::Decode(_)
+        ));
+        assert!(matches!(
+            error(glib::Error::new(gst::CoreError::MissingPlugin, "no h265")),
+            Error::Unsupported(_)
+        ));
+        // The message text no longer decides anything
+        assert!(!is_network_error(
+            &glib::Error::new(gst::CoreError::Failed, "HTTP connection timeout"),
+            true
+        ));
+    }
+
+    #[test]
+    fn local_read_errors_are_not_retried() {
+        gst::init().unwrap();
+        let file = url::Url::parse("file:///media/movie.mkv").unwrap();
+        let filesrc = gst::ElementFactory::make("filesrc").build().unwrap();
+        let network = is_network_source(Some(filesrc.upcast_ref()), Some(&file));
+        assert!(!network);
+
+        let error = glib::Error::new(gst::ResourceError::Read, "short read");
+        assert!(matches!(
+            from_gst_error(&error, network),
+            Error::Resource(_)
+        ));
+        assert!(!is_network_error(&error, network));
+
+        for uri in [
+            "https://example.com/movie.mkv",
+            "rtsp://camera.local/stream",
+        ] {
+            let uri = url::Url::parse(uri).unwrap();
+            assert!(is_network_source(None, Some(&uri)));
+        }
+    }
 }
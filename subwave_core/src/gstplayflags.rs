@@ -31,7 +31,7 @@ pub mod gst_play_flags {
             const SOFT_COLORBALANCE = 0x00000400;
             /// Force audio/video filter(s) to be applied
             const FORCE_FILTERS     = 0x00000800;
-            /// Force only software-based decoders (no effect for playbin3)
+            /// Force only software-based decoders (no effect for playbin3; see `DecoderPreference`)
             const FORCE_SW_DECODERS = 0x00001000;
         }
     }
@@ -151,5 +151,50 @@ pub mod gst_play_flags {
         pub fn video_no_text() -> Self {
             Self::VIDEO | Self::AUDIO | Self::SOFT_VOLUME
         }
+
+        /// Render the video stream, e.g. `GstPlayFlags::default().video(false)` for audio only.
+        pub fn video(self, enabled: bool) -> Self {
+            self.with(Self::VIDEO, enabled)
+        }
+
+        /// Render the audio stream.
+        pub fn audio(self, enabled: bool) -> Self {
+            self.with(Self::AUDIO, enabled)
+        }
+
+        /// Render subtitles.
+        pub fn text(self, enabled: bool) -> Self {
+            self.with(Self::TEXT, enabled)
+        }
+
+        /// Render a visualisation when there is no video stream.
+        pub fn vis(self, enabled: bool) -> Self {
+            self.with(Self::VIS, enabled)
+        }
+
+        /// Use software volume.
+        pub fn soft_volume(self, enabled: bool) -> Self {
+            self.with(Self::SOFT_VOLUME, enabled)
+        }
+
+        /// Attempt progressive download buffering.
+        pub fn download(self, enabled: bool) -> Self {
+            self.with(Self::DOWNLOAD, enabled)
+        }
+
+        /// Buffer demuxed/parsed data.
+        pub fn buffering(self, enabled: bool) -> Self {
+            self.with(Self::BUFFERING, enabled)
+        }
+
+        /// Deinterlace video if necessary.
+        pub fn deinterlace(self, enabled: bool) -> Self {
+            self.with(Self::DEINTERLACE, enabled)
+        }
+
+        fn with(mut self, flag: Self, enabled: bool) -> Self {
+            self.set(flag, enabled);
+            self
+        }
     }
 }
@@ -32,3 +32,54 @@ pub fn set_http_headers_on_pipeline<T: AsRef<str>, U: AsRef<str>>(
         false
     }
 }
+
+/// Write headers to the properties of an HTTP source element such as souphttpsrc, for
+/// sources that are created before (or never look up) the `http-headers` context.
+/// `Cookie` and `User-Agent` go to the dedicated `cookies` and `user-agent` properties,
+/// everything else into `extra-headers`. Returns false if the element has none of these.
+pub fn set_http_headers_on_source<T: AsRef<str>, U: AsRef<str>>(
+    source: &gst::Element,
+    headers: &[(T, U)],
+) -> bool {
+    if headers.is_empty() || !source.has_property("extra-headers") {
+        return false;
+    }
+
+    let mut extra = gst::Structure::new_empty("extra-headers");
+    for (k, v) in headers.iter() {
+        let (k, v) = (k.as_ref(), v.as_ref());
+        if k.eq_ignore_ascii_case("cookie") && source.has_property("cookies") {
+            let cookies: Vec<String> = v
+                .split(';')
+                .map(str::trim)
+                .filter(|c| !c.is_empty())
+                .map(String::from)
+                .collect();
+            source.set_property("cookies", cookies);
+        } else if k.eq_ignore_ascii_case("user-agent") && source.has_property("user-agent") {
+            source.set_property("user-agent", v);
+        } else {
+            extra.set(k, v);
+        }
+    }
+    source.set_property("extra-headers", extra);
+    true
+}
+
+/// Connect to playbin's `source-setup` signal and copy the pipeline's current `http-headers`
+/// context onto each HTTP source as it is created, see [`set_http_headers_on_source`].
+/// The context is read at setup time, so headers changed later apply to the next source.
+pub fn apply_http_headers_on_source_setup(pipeline: &gst::Pipeline) {
+    pipeline.connect("source-setup", false, |values| {
+        let playbin = values[0].get::<gst::Element>().ok()?;
+        let source = values[1].get::<gst::Element>().ok()?;
+        let ctx = playbin.context("http-headers")?;
+        let headers: Vec<(String, String)> = ctx
+            .structure()
+            .iter()
+            .filter_map(|(k, v)| v.get::<String>().ok().map(|v| (k.to_string(), v)))
+            .collect();
+        set_http_headers_on_source(&source, &headers);
+        None
+    });
+}
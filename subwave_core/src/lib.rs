@@ -1,9 +1,15 @@
+pub mod audio;
 pub mod error;
 pub mod gstplayflags;
 pub mod http;
+pub mod rtsp;
+#[cfg(feature = "test-util")]
+pub mod test_util;
 pub mod video;
 
+pub use audio::*;
 pub use error::*;
 pub use gstplayflags::*;
 pub use http::*;
+pub use rtsp::*;
 pub use video::*;
@@ -0,0 +1,60 @@
+use gstreamer as gst;
+use gstreamer::prelude::*;
+
+/// Lower transports `rtspsrc` is allowed to use.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum RtspProtocols {
+    /// Let `rtspsrc` try UDP first and fall back to TCP.
+    #[default]
+    Any,
+    /// Interleaved TCP only; works through most firewalls and NAT.
+    Tcp,
+    /// Unicast UDP only; lowest overhead on trusted networks.
+    Udp,
+}
+
+impl RtspProtocols {
+    /// Value for the `protocols` property of `rtspsrc` (`GstRTSPLowerTrans` flags).
+    pub fn as_gst_flags(self) -> &'static str {
+        match self {
+            RtspProtocols::Any => "udp+udp-mcast+tcp",
+            RtspProtocols::Tcp => "tcp",
+            RtspProtocols::Udp => "udp",
+        }
+    }
+}
+
+/// Settings applied to `rtspsrc` when playbin creates it for an `rtsp://` URI.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct RtspConfig {
+    /// Jitterbuffer latency in milliseconds.
+    pub latency_ms: u32,
+    pub protocols: RtspProtocols,
+}
+
+impl Default for RtspConfig {
+    fn default() -> Self {
+        // Matches rtspsrc's own defaults
+        Self {
+            latency_ms: 2000,
+            protocols: RtspProtocols::Any,
+        }
+    }
+}
+
+/// Returns true if the URI is served over RTSP (`rtsp`, `rtsps`, `rtspt`, `rtspu`, `rtsph`).
+pub fn is_rtsp_uri(uri: &url::Url) -> bool {
+    matches!(uri.scheme(), "rtsp" | "rtsps" | "rtspt" | "rtspu" | "rtsph")
+}
+
+/// Connect to playbin's `source-setup` signal so the `rtspsrc` it creates gets `config` applied.
+pub fn set_rtsp_config_on_pipeline(pipeline: &gst::Pipeline, config: RtspConfig) {
+    pipeline.connect("source-setup", false, move |values| {
+        let source = values[1].get::<gst::Element>().ok()?;
+        if source.has_property("latency") && source.has_property("protocols") {
+            source.set_property("latency", config.latency_ms);
+            source.set_property_from_str("protocols", config.protocols.as_gst_flags());
+        }
+        None
+    });
+}
@@ -0,0 +1,53 @@
+//! Media shared by the integration tests of the subwave crates, behind the `test-util`
+//! feature.
+
+use gstreamer as gst;
+use gstreamer::prelude::*;
+
+/// A short 30 fps MJPEG clip in its own temporary directory, deleted when dropped (also when
+/// a test panics).
+pub struct TestClip {
+    uri: url::Url,
+    _dir: tempfile::TempDir,
+}
+
+impl TestClip {
+    /// Encode 150 frames (5 seconds) at 160x120. Panics if the plugins needed to do so are
+    /// missing, which is why the tests using it are `#[ignore]`d.
+    pub fn new() -> Self {
+        Self::encode().expect("videotestsrc/jpegenc/avimux not available")
+    }
+
+    fn encode() -> Option<Self> {
+        gst::init().ok()?;
+        let dir = tempfile::tempdir().ok()?;
+        let path = dir.path().join("clip.avi");
+        let pipeline = gst::parse::launch(&format!(
+            "videotestsrc num-buffers=150 ! video/x-raw,width=160,height=120,framerate=30/1 \
+             ! jpegenc ! avimux ! filesink location={}",
+            path.display()
+        ))
+        .ok()?;
+        pipeline.set_state(gst::State::Playing).ok()?;
+        let bus = pipeline.bus()?;
+        let done = bus.timed_pop_filtered(
+            gst::ClockTime::from_seconds(30),
+            &[gst::MessageType::Eos, gst::MessageType::Error],
+        );
+        let _ = pipeline.set_state(gst::State::Null);
+        if !matches!(
+            done.as_ref().map(|m| m.view()),
+            Some(gst::MessageView::Eos(_))
+        ) {
+            return None;
+        }
+        Some(Self {
+            uri: url::Url::from_file_path(&path).ok()?,
+            _dir: dir,
+        })
+    }
+
+    pub fn uri(&self) -> &url::Url {
+        &self.uri
+    }
+}
@@ -0,0 +1,83 @@
+use gstreamer as gst;
+use gstreamer::prelude::*;
+
+/// Ask `pipeline` whether it can seek in time. Returns `None` when no element answers the
+/// query, which is common before the pipeline has prerolled.
+pub fn query_seekable(pipeline: &gst::Pipeline) -> Option<bool> {
+    let mut query = gst::query::Seeking::new(gst::Format::Time);
+    pipeline.query(&mut query).then(|| query.result().0)
+}
+
+/// List the cameras and other video capture devices currently available, using a
+/// `DeviceMonitor` filtered to `Video/Source`.
+///
+/// Returns an empty list if no device provider could be started.
+pub fn list_video_capture_devices() -> Vec<gst::Device> {
+    if gst::init().is_err() {
+        return Vec::new();
+    }
+
+    let monitor = gst::DeviceMonitor::new();
+    monitor.add_filter(Some("Video/Source"), None);
+    if monitor.start().is_err() {
+        return Vec::new();
+    }
+    let devices = monitor.devices();
+    monitor.stop();
+    devices.into_iter().collect()
+}
+
+/// Factory name of the first element inside `bin` (searched recursively, bins themselves
+/// skipped) whose klass contains every entry of `klass`, e.g. `&["Decoder", "Video"]`.
+///
+/// playbin3 only plugs decoders and sinks once the streams are known, so this returns `None`
+/// until the pipeline has prerolled.
+pub fn find_factory_by_klass(bin: &gst::Bin, klass: &[&str]) -> Option<String> {
+    bin.iterate_recurse()
+        .into_iter()
+        .filter_map(Result::ok)
+        .filter(|element| !element.is::<gst::Bin>())
+        .filter_map(|element| element.factory())
+        .find(|factory| {
+            let element_klass = factory.klass().as_str();
+            klass.iter().all(|part| element_klass.contains(part))
+        })
+        .map(|factory| factory.name().to_string())
+}
+
+/// Make `pipeline` run on `clock` instead of the one it selects itself, usually the audio
+/// sink's. Pipelines sharing a clock (and a base time, see [`lock_base_time`]) stay in step.
+///
+/// The clock is handed to the elements on the PAUSED to PLAYING transition, so a playing
+/// pipeline is briefly paused to pick it up; set it before PLAYING to avoid that.
+pub fn adopt_clock(
+    pipeline: &gst::Pipeline,
+    clock: &gst::Clock,
+) -> Result<(), gst::StateChangeError> {
+    pipeline.use_clock(Some(clock));
+    if pipeline.current_state() == gst::State::Playing {
+        pipeline.set_state(gst::State::Paused)?;
+        pipeline.set_state(gst::State::Playing)?;
+    }
+    Ok(())
+}
+
+/// The clock `pipeline` runs on, or while not PLAYING the one it would select, falling
+/// back to the system clock.
+pub fn pipeline_clock(pipeline: &gst::Pipeline) -> gst::Clock {
+    pipeline
+        .clock()
+        .or_else(|| pipeline.provide_clock())
+        .unwrap_or_else(gst::SystemClock::obtain)
+}
+
+/// Pin the base time of `pipeline` so it no longer picks a new one each time it goes to
+/// PLAYING. With the same clock and base time, running time is identical across pipelines.
+///
+/// Running time then keeps advancing while the pipeline is paused, so a resume jumps ahead
+/// by however long it was paused. To continue where it stopped, move the base time forward
+/// by the paused duration (on every pipeline sharing it) before going back to PLAYING.
+pub fn lock_base_time(pipeline: &gst::Pipeline, base_time: gst::ClockTime) {
+    pipeline.set_start_time(gst::ClockTime::NONE);
+    pipeline.set_base_time(base_time);
+}
@@ -1,2 +1,5 @@
+pub mod elements;
+pub mod streams;
+pub mod thumbnail;
 pub mod types;
 pub mod video_trait;
@@ -0,0 +1,260 @@
+use gstreamer as gst;
+
+use crate::video::types::{AudioTrack, SubtitleTrack, VideoTrack};
+
+/// Stream ids of the tracks in a [`StreamInfo`], indexed like the track lists.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct StreamIds {
+    pub audio: Vec<String>,
+    pub subtitle: Vec<String>,
+    pub video: Vec<String>,
+    /// Subtitle streams in a bitmap format (PGS or DVD) rather than text.
+    pub bitmap_subtitle: Vec<String>,
+    /// Audio and video streams playbin3 selects by default. Subtitles always start disabled.
+    pub selected: Vec<String>,
+}
+
+impl StreamIds {
+    /// Index of the first of `ids` (one of the per-kind lists) that is selected by default.
+    pub fn selected_index(&self, ids: &[String]) -> Option<i32> {
+        ids.iter()
+            .position(|id| self.selected.contains(id))
+            .map(|index| index as i32)
+    }
+}
+
+/// Tracks listed in a playbin3 stream collection, as both backends expose them.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct StreamInfo {
+    pub audio: Vec<AudioTrack>,
+    pub subtitle: Vec<SubtitleTrack>,
+    pub video: Vec<VideoTrack>,
+    pub ids: StreamIds,
+    /// Subtitle streams left out of `subtitle` as `(stream id, codec)`, for backends to log.
+    pub skipped_subtitle: Vec<(String, Option<String>)>,
+}
+
+/// Read the audio, subtitle and video tracks of `collection`.
+///
+/// Streams without an id cannot be selected and are left out. So are subtitle streams whose
+/// caps are missing or neither text nor a bitmap format, as they cannot be rendered; they
+/// are listed in [`StreamInfo::skipped_subtitle`].
+pub fn parse_stream_collection(collection: &gst::StreamCollection) -> StreamInfo {
+    let string = |value: Option<gst::tags::TagValue<&str>>| value.map(|v| v.get().to_owned());
+    let mut info = StreamInfo::default();
+
+    for stream in (0..collection.len()).filter_map(|i| collection.stream(i as u32)) {
+        let Some(id) = stream.stream_id().map(|id| id.to_string()) else {
+            continue;
+        };
+        let stream_type = stream.stream_type();
+        let default_selected = stream.stream_flags().contains(gst::StreamFlags::SELECT);
+        let tags = stream.tags();
+        let caps = stream.caps();
+        let structure = caps.as_ref().and_then(|caps| caps.structure(0));
+        let caps_name = structure.map(|s| s.name().to_string());
+
+        let language = tags.as_ref().and_then(|tags| {
+            string(tags.get::<gst::tags::LanguageCode>())
+                .or_else(|| string(tags.get::<gst::tags::LanguageName>()))
+        });
+        let title = tags
+            .as_ref()
+            .and_then(|tags| string(tags.get::<gst::tags::Title>()));
+
+        if stream_type.contains(gst::StreamType::VIDEO) {
+            let index = info.video.len() as i32;
+            info.video.push(VideoTrack::from_stream(index, &stream));
+            if default_selected {
+                info.ids.selected.push(id.clone());
+            }
+            info.ids.video.push(id);
+        } else if stream_type.contains(gst::StreamType::AUDIO) {
+            let codec = tags
+                .as_ref()
+                .and_then(|tags| {
+                    string(tags.get::<gst::tags::AudioCodec>())
+                        .or_else(|| string(tags.get::<gst::tags::Codec>()))
+                })
+                .or(caps_name);
+            info.audio.push(AudioTrack {
+                index: info.audio.len() as i32,
+                language,
+                title,
+                codec,
+                channels: structure.and_then(|s| s.get::<i32>("channels").ok()),
+                sample_rate: structure.and_then(|s| s.get::<i32>("rate").ok()),
+            });
+            if default_selected {
+                info.ids.selected.push(id.clone());
+            }
+            info.ids.audio.push(id);
+        } else if stream_type.contains(gst::StreamType::TEXT) {
+            let is_bitmap = matches!(
+                caps_name.as_deref(),
+                Some("subpicture/x-pgs" | "subpicture/x-dvd")
+            );
+            let is_text = caps_name
+                .as_deref()
+                .is_some_and(|name| name.starts_with("text/"));
+            let codec = tags
+                .as_ref()
+                .and_then(|tags| {
+                    string(tags.get::<gst::tags::SubtitleCodec>())
+                        .or_else(|| string(tags.get::<gst::tags::Codec>()))
+                })
+                .or(caps_name);
+            if !is_text && !is_bitmap {
+                info.skipped_subtitle.push((id, codec));
+                continue;
+            }
+            info.subtitle.push(SubtitleTrack {
+                index: info.subtitle.len() as i32,
+                language,
+                title,
+                codec,
+            });
+            if is_bitmap {
+                info.ids.bitmap_subtitle.push(id.clone());
+            }
+            info.ids.subtitle.push(id);
+        }
+    }
+
+    info
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn stream(
+        id: &str,
+        caps: gst::Caps,
+        stream_type: gst::StreamType,
+        flags: gst::StreamFlags,
+        language: Option<&str>,
+    ) -> gst::Stream {
+        let stream = gst::Stream::new(Some(id), Some(&caps), stream_type, flags);
+        if let Some(language) = language {
+            let mut tags = gst::TagList::new();
+            tags.get_mut()
+                .unwrap()
+                .add::<gst::tags::LanguageCode>(&language, gst::TagMergeMode::Replace);
+            stream.set_tags(Some(&tags));
+        }
+        stream
+    }
+
+    fn collection() -> gst::StreamCollection {
+        gst::init().unwrap();
+        let audio_caps = |rate: i32| {
+            gst::Caps::builder("audio/x-raw")
+                .field("rate", rate)
+                .field("channels", 2i32)
+                .build()
+        };
+        gst::StreamCollection::builder(None)
+            .stream(stream(
+                "video",
+                gst::Caps::builder("video/x-raw")
+                    .field("width", 1920i32)
+                    .field("height", 1080i32)
+                    .build(),
+                gst::StreamType::VIDEO,
+                gst::StreamFlags::SELECT,
+                None,
+            ))
+            .stream(stream(
+                "audio-en",
+                audio_caps(48000),
+                gst::StreamType::AUDIO,
+                gst::StreamFlags::empty(),
+                Some("en"),
+            ))
+            .stream(stream(
+                "audio-fr",
+                audio_caps(44100),
+                gst::StreamType::AUDIO,
+                gst::StreamFlags::SELECT,
+                Some("fr"),
+            ))
+            .stream(stream(
+                "subs-srt",
+                gst::Caps::new_empty_simple("text/x-raw"),
+                gst::StreamType::TEXT,
+                gst::StreamFlags::SELECT,
+                Some("en"),
+            ))
+            .stream(stream(
+                "subs-dvb",
+                gst::Caps::new_empty_simple("subpicture/x-dvb"),
+                gst::StreamType::TEXT,
+                gst::StreamFlags::empty(),
+                None,
+            ))
+            .stream(stream(
+                "subs-pgs",
+                gst::Caps::new_empty_simple("subpicture/x-pgs"),
+                gst::StreamType::TEXT,
+                gst::StreamFlags::empty(),
+                Some("de"),
+            ))
+            .stream(gst::Stream::new(
+                Some("subs-pending"),
+                None,
+                gst::StreamType::TEXT,
+                gst::StreamFlags::empty(),
+            ))
+            .build()
+    }
+
+    #[test]
+    fn tracks_are_indexed_per_kind() {
+        let info = parse_stream_collection(&collection());
+
+        assert_eq!(info.video.len(), 1);
+        assert_eq!(info.video[0].width, Some(1920));
+
+        assert_eq!(info.audio.len(), 2);
+        assert_eq!(info.audio[1].index, 1);
+        assert_eq!(info.audio[1].language.as_deref(), Some("fr"));
+        assert_eq!(info.audio[1].sample_rate, Some(44100));
+        assert_eq!(info.audio[1].channels, Some(2));
+        assert_eq!(info.audio[0].codec.as_deref(), Some("audio/x-raw"));
+
+        assert_eq!(info.ids.video, ["video"]);
+        assert_eq!(info.ids.audio, ["audio-en", "audio-fr"]);
+    }
+
+    #[test]
+    fn unrenderable_subtitles_are_skipped() {
+        let info = parse_stream_collection(&collection());
+
+        let languages: Vec<_> = info
+            .subtitle
+            .iter()
+            .map(|track| (track.index, track.language.as_deref()))
+            .collect();
+        assert_eq!(languages, [(0, Some("en")), (1, Some("de"))]);
+        assert_eq!(info.ids.subtitle, ["subs-srt", "subs-pgs"]);
+        assert_eq!(info.ids.bitmap_subtitle, ["subs-pgs"]);
+        assert_eq!(
+            info.skipped_subtitle,
+            [
+                ("subs-dvb".to_owned(), Some("subpicture/x-dvb".to_owned())),
+                ("subs-pending".to_owned(), None),
+            ]
+        );
+    }
+
+    #[test]
+    fn default_selection_leaves_subtitles_out() {
+        let info = parse_stream_collection(&collection());
+
+        assert_eq!(info.ids.selected, ["video", "audio-fr"]);
+        assert_eq!(info.ids.selected_index(&info.ids.audio), Some(1));
+        assert_eq!(info.ids.selected_index(&info.ids.video), Some(0));
+        assert_eq!(info.ids.selected_index(&info.ids.subtitle), None);
+    }
+}
@@ -0,0 +1,140 @@
+use gstreamer as gst;
+use gstreamer::prelude::*;
+use std::num::NonZeroU8;
+
+use crate::{Error, gstplayflags::gst_play_flags::GstPlayFlags, video::types::Position};
+
+/// A decoded RGBA frame used for scrubbing previews.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Thumbnail {
+    pub width: u32,
+    pub height: u32,
+    /// Tightly packed RGBA8 pixels, `width * height * 4` bytes.
+    pub rgba: Vec<u8>,
+}
+
+impl Thumbnail {
+    /// Build a thumbnail from an RGBA sample, keeping every `downscale`-th pixel on both axes.
+    ///
+    /// The output is always `(width / downscale) * (height / downscale)` pixels (at least
+    /// one), also for odd dimensions.
+    fn from_sample(sample: &gst::Sample, downscale: NonZeroU8) -> Result<Self, Error> {
+        let caps = sample.caps().ok_or(Error::Caps)?;
+        let s = caps.structure(0).ok_or(Error::Caps)?;
+        let width = s.get::<i32>("width").map_err(|_| Error::Caps)? as usize;
+        let height = s.get::<i32>("height").map_err(|_| Error::Caps)? as usize;
+
+        let buffer = sample.buffer().ok_or(Error::Caps)?;
+        let map = buffer.map_readable().map_err(|_| Error::Caps)?;
+        let stride = width * 4;
+        if width == 0 || height == 0 || map.len() < stride * height {
+            return Err(Error::Caps);
+        }
+
+        let step = downscale.get() as usize;
+        let out_width = (width / step).max(1);
+        let out_height = (height / step).max(1);
+        let mut rgba = Vec::with_capacity(out_width * out_height * 4);
+        for y in 0..out_height {
+            let row = &map[y * step * stride..];
+            for x in 0..out_width {
+                let offset = x * step * 4;
+                rgba.extend_from_slice(&row[offset..offset + 4]);
+            }
+        }
+
+        Ok(Self {
+            width: out_width as u32,
+            height: out_height as u32,
+            rgba,
+        })
+    }
+}
+
+/// Decode frames at `positions` from `uri` using a separate, video-only pipeline.
+///
+/// The playback pipeline is left untouched, so this works regardless of which sink is
+/// displaying the video (including `waylandsink`, which offers no pixel access).
+pub fn generate_thumbnails(
+    uri: &url::Url,
+    positions: &[Position],
+    downscale: NonZeroU8,
+) -> Result<Vec<Thumbnail>, Error> {
+    gst::init()?;
+
+    let sink_bin = gst::parse::bin_from_description(
+        "videoconvertscale ! appsink name=thumbnail_sink sync=false enable-last-sample=false caps=\"video/x-raw,format=RGBA,pixel-aspect-ratio=1/1\"",
+        true,
+    )?;
+    let appsink = sink_bin
+        .by_name("thumbnail_sink")
+        .ok_or_else(|| Error::AppSink("thumbnail_sink".to_string()))?;
+
+    let pipeline = gst::ElementFactory::make("playbin3")
+        .property("uri", uri.as_str())
+        .property("video-sink", &sink_bin)
+        .property("flags", GstPlayFlags::VIDEO)
+        .build()?
+        .downcast::<gst::Pipeline>()
+        .map_err(|_| Error::Cast)?;
+
+    let result = (|| -> Result<Vec<Thumbnail>, Error> {
+        pipeline.set_state(gst::State::Paused)?;
+        pipeline.state(gst::ClockTime::from_seconds(5)).0?;
+
+        let mut thumbnails = Vec::with_capacity(positions.len());
+        for &position in positions {
+            pipeline.seek_simple(
+                gst::SeekFlags::FLUSH | gst::SeekFlags::ACCURATE,
+                gst::GenericFormattedValue::from(position),
+            )?;
+            pipeline.state(gst::ClockTime::from_seconds(5)).0?;
+
+            let sample = appsink
+                .emit_by_name::<Option<gst::Sample>>("pull-preroll", &[])
+                .ok_or(Error::Sync)?;
+            thumbnails.push(Thumbnail::from_sample(&sample, downscale)?);
+        }
+        Ok(thumbnails)
+    })();
+
+    let _ = pipeline.set_state(gst::State::Null);
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rgba_sample(width: i32, height: i32, len: usize) -> gst::Sample {
+        gst::init().unwrap();
+        let caps = gst::Caps::builder("video/x-raw")
+            .field("format", "RGBA")
+            .field("width", width)
+            .field("height", height)
+            .build();
+        let buffer = gst::Buffer::from_mut_slice(vec![128u8; len]);
+        gst::Sample::builder().caps(&caps).buffer(&buffer).build()
+    }
+
+    #[test]
+    fn thumbnails_handle_odd_dimensions() {
+        let (width, height) = (721, 481);
+        let sample = rgba_sample(width as i32, height as i32, width * height * 4);
+        for downscale in [1, 2, 3, 4] {
+            let downscale = NonZeroU8::new(downscale).unwrap();
+            let step = downscale.get() as usize;
+            let thumbnail = Thumbnail::from_sample(&sample, downscale).unwrap();
+            assert_eq!(thumbnail.width as usize, width / step);
+            assert_eq!(thumbnail.height as usize, height / step);
+            assert_eq!(thumbnail.rgba.len(), (width / step) * (height / step) * 4);
+        }
+    }
+
+    #[test]
+    fn thumbnails_reject_truncated_frames() {
+        // One row short
+        let sample = rgba_sample(721, 481, 721 * 480 * 4);
+        assert!(Thumbnail::from_sample(&sample, NonZeroU8::MIN).is_err());
+    }
+}
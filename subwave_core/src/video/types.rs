@@ -1,4 +1,6 @@
 use gstreamer as gst;
+use gstreamer::prelude::*;
+use std::sync::atomic::{AtomicU64, Ordering};
 use std::time::Duration;
 
 #[derive(Clone, Debug)]
@@ -22,6 +24,437 @@ pub struct VideoProperties {
     pub has_video: bool,
 }
 
+/// Snapshot of where playback currently stands, as reported by [`Video::status`].
+///
+/// [`Video::status`]: crate::video::video_trait::Video::status
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PlaybackStatus {
+    /// The pipeline has not prerolled yet (or is stopped), so no frame can be shown.
+    Idle,
+    /// Playback is held while the network buffer fills, with the fill level in percent.
+    Buffering(i32),
+    /// The pipeline is running.
+    Playing,
+    /// The pipeline is prerolled and paused.
+    Paused,
+    /// The stream reached its end and is not looping.
+    Ended,
+    /// The pipeline posted an error it could not recover from.
+    Error,
+}
+
+impl PlaybackStatus {
+    /// Map a pipeline's current state, ignoring EOS, buffering and errors.
+    pub fn from_gst_state(state: gst::State) -> Self {
+        match state {
+            gst::State::Playing => Self::Playing,
+            gst::State::Paused => Self::Paused,
+            _ => Self::Idle,
+        }
+    }
+}
+
+/// What a backend should do with its pipeline in response to a buffering message.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BufferingAction {
+    Pause,
+    Resume,
+}
+
+impl BufferingAction {
+    /// What a buffering message at `percent` calls for: pause when buffering starts and
+    /// resume once it completes, unless the user has paused in the meantime.
+    pub fn from_percent(was_buffering: bool, percent: i32, user_paused: bool) -> Option<Self> {
+        let buffering_now = percent < 100;
+        if user_paused || buffering_now == was_buffering {
+            None
+        } else if buffering_now {
+            Some(Self::Pause)
+        } else {
+            Some(Self::Resume)
+        }
+    }
+}
+
+/// Allocate the id of a new video, unique across both backends.
+pub fn next_video_id() -> u64 {
+    static NEXT_ID: AtomicU64 = AtomicU64::new(0);
+    NEXT_ID.fetch_add(1, Ordering::SeqCst)
+}
+
+/// Log target of the video with `id`, e.g. `subwave[42]`. Messages about one video's
+/// pipeline and threads use it, so output from several videos can be told apart and
+/// filtered (`RUST_LOG='subwave[42]=debug'` with env_logger).
+pub fn video_log_target(id: u64) -> String {
+    format!("subwave[{id}]")
+}
+
+/// Slowest playback speed accepted by [`Video::set_speed`], in either direction.
+///
+/// [`Video::set_speed`]: crate::video::video_trait::Video::set_speed
+pub const MIN_SPEED: f64 = 0.1;
+
+/// Fastest playback speed accepted by [`Video::set_speed`], in either direction.
+///
+/// [`Video::set_speed`]: crate::video::video_trait::Video::set_speed
+pub const MAX_SPEED: f64 = 16.0;
+
+/// Check a requested playback speed before it becomes a seek rate.
+///
+/// Zero, NaN and infinite speeds would wedge the pipeline and are rejected with
+/// [`Error::InvalidState`](crate::Error::InvalidState); other speeds keep their sign and are
+/// clamped to [`MIN_SPEED`]..=[`MAX_SPEED`].
+pub fn clamp_speed(speed: f64) -> Result<f64, crate::Error> {
+    if speed == 0.0 || !speed.is_finite() {
+        return Err(crate::Error::InvalidState);
+    }
+    Ok(speed.signum() * speed.abs().clamp(MIN_SPEED, MAX_SPEED))
+}
+
+/// Position `fraction` (0.0 to 1.0, clamped) of the way through `duration`, for scrub bars.
+///
+/// Fails with [`Error::InvalidState`](crate::Error::InvalidState) when the duration is unknown
+/// (zero, e.g. live streams) or the fraction is NaN.
+pub fn position_at_fraction(duration: Duration, fraction: f64) -> Result<Duration, crate::Error> {
+    if duration.is_zero() || fraction.is_nan() {
+        return Err(crate::Error::InvalidState);
+    }
+    Ok(duration.mul_f64(fraction.clamp(0.0, 1.0)))
+}
+
+/// `position` moved by `delta_ms` milliseconds (negative seeks back), for skip buttons.
+///
+/// The result is clamped to `0..=duration`, or only at zero when the duration is unknown
+/// (zero, e.g. live streams).
+pub fn position_offset(position: Duration, duration: Duration, delta_ms: i64) -> Duration {
+    let delta = Duration::from_millis(delta_ms.unsigned_abs());
+    let target = if delta_ms < 0 {
+        position.saturating_sub(delta)
+    } else {
+        position.saturating_add(delta)
+    };
+    if duration.is_zero() {
+        target
+    } else {
+        target.min(duration)
+    }
+}
+
+/// ISO 639-2 codes (bibliographic and terminological) of every language with an ISO 639-1
+/// code, with that code. Containers tag tracks with either.
+const ISO_639_2_TO_1: &[(&str, &str)] = &[
+    ("aar", "aa"),
+    ("abk", "ab"),
+    ("ave", "ae"),
+    ("afr", "af"),
+    ("aka", "ak"),
+    ("amh", "am"),
+    ("arg", "an"),
+    ("ara", "ar"),
+    ("asm", "as"),
+    ("ava", "av"),
+    ("aym", "ay"),
+    ("aze", "az"),
+    ("bak", "ba"),
+    ("bel", "be"),
+    ("bul", "bg"),
+    ("bis", "bi"),
+    ("bam", "bm"),
+    ("ben", "bn"),
+    ("bod", "bo"),
+    ("tib", "bo"),
+    ("bre", "br"),
+    ("bos", "bs"),
+    ("cat", "ca"),
+    ("che", "ce"),
+    ("cha", "ch"),
+    ("cos", "co"),
+    ("cre", "cr"),
+    ("ces", "cs"),
+    ("cze", "cs"),
+    ("chu", "cu"),
+    ("chv", "cv"),
+    ("cym", "cy"),
+    ("wel", "cy"),
+    ("dan", "da"),
+    ("deu", "de"),
+    ("ger", "de"),
+    ("div", "dv"),
+    ("dzo", "dz"),
+    ("ewe", "ee"),
+    ("ell", "el"),
+    ("gre", "el"),
+    ("eng", "en"),
+    ("epo", "eo"),
+    ("spa", "es"),
+    ("est", "et"),
+    ("eus", "eu"),
+    ("baq", "eu"),
+    ("fas", "fa"),
+    ("per", "fa"),
+    ("ful", "ff"),
+    ("fin", "fi"),
+    ("fij", "fj"),
+    ("fao", "fo"),
+    ("fra", "fr"),
+    ("fre", "fr"),
+    ("fry", "fy"),
+    ("gle", "ga"),
+    ("gla", "gd"),
+    ("glg", "gl"),
+    ("grn", "gn"),
+    ("guj", "gu"),
+    ("glv", "gv"),
+    ("hau", "ha"),
+    ("heb", "he"),
+    ("hin", "hi"),
+    ("hmo", "ho"),
+    ("hrv", "hr"),
+    ("hat", "ht"),
+    ("hun", "hu"),
+    ("hye", "hy"),
+    ("arm", "hy"),
+    ("her", "hz"),
+    ("ina", "ia"),
+    ("ind", "id"),
+    ("ile", "ie"),
+    ("ibo", "ig"),
+    ("iii", "ii"),
+    ("ipk", "ik"),
+    ("ido", "io"),
+    ("isl", "is"),
+    ("ice", "is"),
+    ("ita", "it"),
+    ("iku", "iu"),
+    ("jpn", "ja"),
+    ("jav", "jv"),
+    ("kat", "ka"),
+    ("geo", "ka"),
+    ("kon", "kg"),
+    ("kik", "ki"),
+    ("kua", "kj"),
+    ("kaz", "kk"),
+    ("kal", "kl"),
+    ("khm", "km"),
+    ("kan", "kn"),
+    ("kor", "ko"),
+    ("kau", "kr"),
+    ("kas", "ks"),
+    ("kur", "ku"),
+    ("kom", "kv"),
+    ("cor", "kw"),
+    ("kir", "ky"),
+    ("lat", "la"),
+    ("ltz", "lb"),
+    ("lug", "lg"),
+    ("lim", "li"),
+    ("lin", "ln"),
+    ("lao", "lo"),
+    ("lit", "lt"),
+    ("lub", "lu"),
+    ("lav", "lv"),
+    ("mlg", "mg"),
+    ("mah", "mh"),
+    ("mri", "mi"),
+    ("mao", "mi"),
+    ("mkd", "mk"),
+    ("mac", "mk"),
+    ("mal", "ml"),
+    ("mon", "mn"),
+    ("mar", "mr"),
+    ("msa", "ms"),
+    ("may", "ms"),
+    ("mlt", "mt"),
+    ("mya", "my"),
+    ("bur", "my"),
+    ("nau", "na"),
+    ("nob", "nb"),
+    ("nde", "nd"),
+    ("nep", "ne"),
+    ("ndo", "ng"),
+    ("nld", "nl"),
+    ("dut", "nl"),
+    ("nno", "nn"),
+    ("nor", "no"),
+    ("nbl", "nr"),
+    ("nav", "nv"),
+    ("nya", "ny"),
+    ("oci", "oc"),
+    ("oji", "oj"),
+    ("orm", "om"),
+    ("ori", "or"),
+    ("oss", "os"),
+    ("pan", "pa"),
+    ("pli", "pi"),
+    ("pol", "pl"),
+    ("pus", "ps"),
+    ("por", "pt"),
+    ("que", "qu"),
+    ("roh", "rm"),
+    ("run", "rn"),
+    ("ron", "ro"),
+    ("rum", "ro"),
+    ("rus", "ru"),
+    ("kin", "rw"),
+    ("san", "sa"),
+    ("srd", "sc"),
+    ("snd", "sd"),
+    ("sme", "se"),
+    ("sag", "sg"),
+    ("sin", "si"),
+    ("slk", "sk"),
+    ("slo", "sk"),
+    ("slv", "sl"),
+    ("smo", "sm"),
+    ("sna", "sn"),
+    ("som", "so"),
+    ("sqi", "sq"),
+    ("alb", "sq"),
+    ("srp", "sr"),
+    ("ssw", "ss"),
+    ("sot", "st"),
+    ("sun", "su"),
+    ("swe", "sv"),
+    ("swa", "sw"),
+    ("tam", "ta"),
+    ("tel", "te"),
+    ("tgk", "tg"),
+    ("tha", "th"),
+    ("tir", "ti"),
+    ("tuk", "tk"),
+    ("tgl", "tl"),
+    ("tsn", "tn"),
+    ("ton", "to"),
+    ("tur", "tr"),
+    ("tso", "ts"),
+    ("tat", "tt"),
+    ("twi", "tw"),
+    ("tah", "ty"),
+    ("uig", "ug"),
+    ("ukr", "uk"),
+    ("urd", "ur"),
+    ("uzb", "uz"),
+    ("ven", "ve"),
+    ("vie", "vi"),
+    ("vol", "vo"),
+    ("wln", "wa"),
+    ("wol", "wo"),
+    ("xho", "xh"),
+    ("yid", "yi"),
+    ("yor", "yo"),
+    ("zha", "za"),
+    ("zho", "zh"),
+    ("chi", "zh"),
+    ("zul", "zu"),
+];
+
+/// Whether a track tagged `language` satisfies the `wanted` BCP-47 or ISO 639 code.
+///
+/// Matching ignores case and maps three-letter codes onto their two-letter equivalents, so
+/// `"en"` selects `"eng"`. Codes without one (such as `"fil"`) only match themselves. A bare
+/// language selects any region (`"pt"` matches `"pt-BR"`), while a region only rules out
+/// tracks tagged with a different one.
+pub fn language_matches(language: &str, wanted: &str) -> bool {
+    fn split(tag: &str) -> (String, Option<String>) {
+        let mut subtags = tag.trim().split(['-', '_']);
+        let primary = subtags.next().unwrap_or_default().to_ascii_lowercase();
+        let primary = ISO_639_2_TO_1
+            .iter()
+            .find(|(long, _)| *long == primary)
+            .map_or(primary, |(_, short)| short.to_string());
+        let region = subtags.next().map(str::to_ascii_lowercase);
+        (primary, region)
+    }
+
+    let (language, region) = split(language);
+    let (wanted, wanted_region) = split(wanted);
+    !wanted.is_empty()
+        && language == wanted
+        && match (region, wanted_region) {
+            (Some(region), Some(wanted_region)) => region == wanted_region,
+            _ => true,
+        }
+}
+
+/// Size at which `(width, height)` pixels with pixel aspect ratio `par` are displayed: the
+/// width is scaled and the height kept. Invalid ratios leave the size unchanged.
+pub fn apply_pixel_aspect(size: (i32, i32), par: (i32, i32)) -> (i32, i32) {
+    let (width, height) = size;
+    let (n, d) = par;
+    if n <= 0 || d <= 0 {
+        return size;
+    }
+    ((width as i64 * n as i64 / d as i64) as i32, height)
+}
+
+/// Frame delivery counters since the video was created, see [`Video::frame_stats`].
+///
+/// [`Video::frame_stats`]: crate::video::video_trait::Video::frame_stats
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct FrameStats {
+    /// Frames that reached the sink and were presented.
+    pub rendered: u64,
+    /// Frames dropped for arriving late or because the sink's queue was full.
+    pub dropped: u64,
+}
+
+/// Audio measurements posted while audio analysis is enabled.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct AudioLevels {
+    /// RMS level per channel, in dBFS.
+    pub rms: Vec<f64>,
+    /// Peak level per channel, in dBFS.
+    pub peak: Vec<f64>,
+    /// Magnitude per frequency band from 0 Hz to Nyquist, in dB (down to -60).
+    pub bands: Vec<f32>,
+}
+
+/// Descriptive tags of the media, for "now playing" displays.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct MediaMetadata {
+    pub title: Option<String>,
+    pub artist: Option<String>,
+    pub album: Option<String>,
+    pub album_artist: Option<String>,
+    pub genre: Option<String>,
+    pub track_number: Option<u32>,
+    /// Encoded cover art (usually JPEG or PNG) as embedded in the file.
+    pub album_art: Option<Vec<u8>>,
+    /// Media type of `album_art`, e.g. `image/jpeg`.
+    pub album_art_mime: Option<String>,
+}
+
+impl MediaMetadata {
+    /// Read the known tags from `tags`, falling back to the preview image for cover art.
+    pub fn from_tags(tags: &gst::TagListRef) -> Self {
+        let string = |value: Option<gst::tags::TagValue<&str>>| value.map(|v| v.get().to_owned());
+        let image = tags
+            .get::<gst::tags::Image>()
+            .or_else(|| tags.get::<gst::tags::PreviewImage>())
+            .map(|value| value.get());
+        let album_art = image.as_ref().and_then(|sample| {
+            let map = sample.buffer()?.map_readable().ok()?;
+            Some(map.as_slice().to_vec())
+        });
+        let album_art_mime = image
+            .as_ref()
+            .and_then(|sample| sample.caps())
+            .and_then(|caps| caps.structure(0))
+            .map(|s| s.name().to_string());
+
+        Self {
+            title: string(tags.get::<gst::tags::Title>()),
+            artist: string(tags.get::<gst::tags::Artist>()),
+            album: string(tags.get::<gst::tags::Album>()),
+            album_artist: string(tags.get::<gst::tags::AlbumArtist>()),
+            genre: string(tags.get::<gst::tags::Genre>()),
+            track_number: tags.get::<gst::tags::TrackNumber>().map(|v| v.get()),
+            album_art,
+            album_art_mime,
+        }
+    }
+}
+
 /// Position in the media.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub enum Position {
@@ -84,6 +517,231 @@ pub struct AudioTrack {
     pub sample_rate: Option<i32>,
 }
 
+/// Information about a video track, e.g. one camera angle of a multi-angle file
+#[derive(Debug, Clone, PartialEq)]
+pub struct VideoTrack {
+    /// The track index (0-based)
+    pub index: i32,
+    /// Codec used for the video (e.g., "H.264", "HEVC")
+    pub codec: Option<String>,
+    /// Coded width in pixels
+    pub width: Option<i32>,
+    /// Coded height in pixels
+    pub height: Option<i32>,
+    /// Nominal bitrate in bits per second
+    pub bitrate: Option<u32>,
+    /// Frames per second, `None` for variable framerate
+    pub framerate: Option<f64>,
+}
+
+impl VideoTrack {
+    /// Read a track's metadata from a video stream of a playbin3 stream collection.
+    pub fn from_stream(index: i32, stream: &gst::Stream) -> Self {
+        let tags = stream.tags();
+        let caps = stream.caps();
+        let structure = caps.as_ref().and_then(|caps| caps.structure(0));
+
+        let codec = tags
+            .as_ref()
+            .and_then(|tags| tags.get::<gst::tags::VideoCodec>())
+            .map(|codec| codec.get().to_string())
+            .or_else(|| structure.map(|s| s.name().to_string()));
+        let bitrate = tags.as_ref().and_then(|tags| {
+            tags.get::<gst::tags::Bitrate>()
+                .or_else(|| tags.get::<gst::tags::NominalBitrate>())
+                .map(|bitrate| bitrate.get())
+        });
+        let framerate = structure
+            .and_then(|s| s.get::<gst::Fraction>("framerate").ok())
+            .filter(|fr| fr.numer() > 0 && fr.denom() > 0)
+            .map(|fr| fr.numer() as f64 / fr.denom() as f64);
+
+        Self {
+            index,
+            codec,
+            width: structure.and_then(|s| s.get::<i32>("width").ok()),
+            height: structure.and_then(|s| s.get::<i32>("height").ok()),
+            bitrate,
+            framerate,
+        }
+    }
+}
+
+/// Network buffering overrides for playbin3.
+///
+/// Fields left as `None` keep the backend's built-in defaults.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct BufferConfig {
+    /// Amount of media to buffer ahead (`buffer-duration`).
+    pub duration: Option<Duration>,
+    /// Maximum bytes held in the network buffer (`buffer-size`).
+    pub size_bytes: Option<i32>,
+    /// Maximum size of the download ring buffer (`ring-buffer-max-size`).
+    pub ring_buffer_max: Option<u64>,
+}
+
+impl BufferConfig {
+    /// Set the configured properties on a playbin/playbin3 element, leaving unset ones untouched.
+    pub fn apply_to_playbin(&self, playbin: &gst::Element) {
+        if let Some(duration) = self.duration {
+            let nanos = i64::try_from(duration.as_nanos()).unwrap_or(i64::MAX);
+            playbin.set_property("buffer-duration", nanos);
+        }
+        if let Some(size) = self.size_bytes {
+            playbin.set_property("buffer-size", size);
+        }
+        if let Some(max) = self.ring_buffer_max {
+            playbin.set_property("ring-buffer-max-size", max);
+        }
+    }
+}
+
+/// Deinterlacing behaviour for backends that run their own video filter chain.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum DeinterlaceMode {
+    /// Deinterlace only frames flagged as interlaced.
+    #[default]
+    Auto,
+    /// Always deinterlace, even if the stream claims to be progressive.
+    Force,
+    /// Never deinterlace; frames are passed through untouched.
+    Disabled,
+}
+
+impl DeinterlaceMode {
+    /// Nick of the matching `GstDeinterlaceModes` value on the `deinterlace` element.
+    pub fn as_gst_nick(self) -> &'static str {
+        match self {
+            DeinterlaceMode::Auto => "auto",
+            DeinterlaceMode::Force => "interlaced",
+            DeinterlaceMode::Disabled => "disabled",
+        }
+    }
+}
+
+/// Trick-mode flags added to rate-change seeks, so fast playback decodes less.
+///
+/// The flags only apply at speeds other than 1.0; returning to normal speed seeks without
+/// them and every frame is decoded again.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct TrickMode {
+    /// Decode and show keyframes only.
+    pub key_units_only: bool,
+    /// Skip audio decoding altogether.
+    pub no_audio: bool,
+}
+
+impl TrickMode {
+    /// Whether any trick-mode flag is set.
+    pub fn is_enabled(self) -> bool {
+        self.key_units_only || self.no_audio
+    }
+
+    /// Seek flags for a rate-change seek to `rate`.
+    pub fn seek_flags(self, rate: f64) -> gst::SeekFlags {
+        let mut flags = gst::SeekFlags::empty();
+        if rate == 1.0 || !self.is_enabled() {
+            return flags;
+        }
+        flags |= gst::SeekFlags::TRICKMODE;
+        if self.key_units_only {
+            flags |= gst::SeekFlags::TRICKMODE_KEY_UNITS;
+        }
+        if self.no_audio {
+            flags |= gst::SeekFlags::TRICKMODE_NO_AUDIO;
+        }
+        flags
+    }
+}
+
+/// Which kind of video decoder playbin3 should pick.
+///
+/// playbin3 ignores [`GstPlayFlags::FORCE_SW_DECODERS`], so the preference is applied to a
+/// single pipeline by [`apply_to_playbin`](Self::apply_to_playbin): video decoders that do
+/// not fit it refuse the stream's caps, and decodebin3 moves on to its next candidate.
+/// Hardware decoders are the factories whose klass contains `Hardware`. The plugin registry
+/// is left alone, so other pipelines in the process are not affected.
+///
+/// [`GstPlayFlags::FORCE_SW_DECODERS`]: crate::gstplayflags::gst_play_flags::GstPlayFlags::FORCE_SW_DECODERS
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum DecoderPreference {
+    /// Let decodebin3 pick by rank.
+    #[default]
+    Auto,
+    /// Skip software decoders whenever a hardware decoder handles the codec. Codecs the
+    /// hardware cannot handle still fall back to software.
+    ForceHardware,
+    /// Never use hardware decoders, e.g. to work around a broken VA-API driver.
+    ForceSoftware,
+}
+
+impl DecoderPreference {
+    /// Filter the video decoders `playbin` (a playbin3) creates from now on. Call it before
+    /// the pipeline leaves NULL; decoders that already exist are kept.
+    pub fn apply_to_playbin(self, playbin: &gst::Element) {
+        if self == DecoderPreference::Auto {
+            return;
+        }
+        playbin.connect("element-setup", false, move |values| {
+            if let Ok(element) = values[1].get::<gst::Element>() {
+                self.filter_decoder(&element);
+            }
+            None
+        });
+    }
+
+    /// Make `element`, if it is a video decoder this preference rules out, answer decodebin3's
+    /// accept-caps query with `false`.
+    fn filter_decoder(self, element: &gst::Element) {
+        let Some(factory) = element.factory() else {
+            return;
+        };
+        let video_decoder = factory
+            .has_type(gst::ElementFactoryType::DECODER | gst::ElementFactoryType::MEDIA_VIDEO);
+        let Some(pad) = element.static_pad("sink").filter(|_| video_decoder) else {
+            return;
+        };
+        let hardware = is_hardware_decoder(&factory);
+        pad.add_probe(gst::PadProbeType::QUERY_BOTH, move |_pad, info| {
+            let Some(gst::PadProbeData::Query(query)) = &mut info.data else {
+                return gst::PadProbeReturn::Ok;
+            };
+            let gst::QueryViewMut::AcceptCaps(accept_caps) = query.view_mut() else {
+                return gst::PadProbeReturn::Ok;
+            };
+            let refused = match self {
+                DecoderPreference::Auto => false,
+                DecoderPreference::ForceSoftware => hardware,
+                DecoderPreference::ForceHardware => {
+                    !hardware && hardware_decoder_exists(accept_caps.caps())
+                }
+            };
+            if refused {
+                accept_caps.set_result(false);
+                gst::PadProbeReturn::Handled
+            } else {
+                gst::PadProbeReturn::Ok
+            }
+        });
+    }
+}
+
+fn is_hardware_decoder(factory: &gst::ElementFactory) -> bool {
+    factory.klass().contains("Hardware")
+}
+
+/// Whether a hardware video decoder in the registry takes `caps`.
+fn hardware_decoder_exists(caps: &gst::CapsRef) -> bool {
+    let decoders = gst::ElementFactory::factories_with_type(
+        gst::ElementFactoryType::DECODER | gst::ElementFactoryType::MEDIA_VIDEO,
+        gst::Rank::MARGINAL,
+    );
+    decoders
+        .iter()
+        .filter(|factory| is_hardware_decoder(factory))
+        .any(|factory| factory.can_sink_any_caps(caps))
+}
+
 impl From<Position> for gst::GenericFormattedValue {
     fn from(pos: Position) -> Self {
         match pos {
@@ -119,6 +777,16 @@ impl std::fmt::Display for AudioTrack {
     }
 }
 
+impl std::fmt::Display for VideoTrack {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Track {}", self.index + 1)?;
+        if let (Some(width), Some(height)) = (self.width, self.height) {
+            write!(f, " ({}x{})", width, height)?;
+        }
+        Ok(())
+    }
+}
+
 impl std::fmt::Display for SubtitleTrack {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         if let Some(lang) = &self.language {
@@ -131,3 +799,148 @@ impl std::fmt::Display for SubtitleTrack {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        BufferingAction, MAX_SPEED, MIN_SPEED, TrickMode, apply_pixel_aspect, clamp_speed,
+        language_matches, position_at_fraction, position_offset,
+    };
+    use gstreamer as gst;
+    use std::time::Duration;
+
+    #[test]
+    fn pause_during_buffering_is_not_overridden_by_resume() {
+        // Playing when the buffer runs low
+        assert_eq!(
+            BufferingAction::from_percent(false, 20, false),
+            Some(BufferingAction::Pause)
+        );
+        assert_eq!(BufferingAction::from_percent(true, 60, false), None);
+
+        // The user pauses mid-buffering, then the buffer fills up
+        assert_eq!(BufferingAction::from_percent(true, 100, true), None);
+    }
+
+    #[test]
+    fn buffering_resumes_playback_once_complete() {
+        assert_eq!(
+            BufferingAction::from_percent(true, 100, false),
+            Some(BufferingAction::Resume)
+        );
+        assert_eq!(BufferingAction::from_percent(false, 100, false), None);
+    }
+
+    #[test]
+    fn anamorphic_pixels_widen_the_display_size() {
+        // DVD NTSC widescreen: 720x480 at 32:27
+        assert_eq!(apply_pixel_aspect((720, 480), (32, 27)), (853, 480));
+        assert_eq!(apply_pixel_aspect((1920, 1080), (1, 1)), (1920, 1080));
+        assert_eq!(apply_pixel_aspect((1920, 1080), (0, 1)), (1920, 1080));
+    }
+
+    #[test]
+    fn clamp_speed_rejects_unusable_rates() {
+        for speed in [0.0, -0.0, f64::NAN, f64::INFINITY, f64::NEG_INFINITY] {
+            assert!(matches!(
+                clamp_speed(speed),
+                Err(crate::Error::InvalidState)
+            ));
+        }
+    }
+
+    #[test]
+    fn clamp_speed_keeps_direction_within_range() {
+        assert_eq!(clamp_speed(1.5).unwrap(), 1.5);
+        assert_eq!(clamp_speed(0.01).unwrap(), MIN_SPEED);
+        assert_eq!(clamp_speed(100.0).unwrap(), MAX_SPEED);
+        assert_eq!(clamp_speed(-2.0).unwrap(), -2.0);
+        assert_eq!(clamp_speed(-100.0).unwrap(), -MAX_SPEED);
+    }
+
+    #[test]
+    fn fraction_maps_onto_known_durations_only() {
+        let duration = Duration::from_secs(120);
+        assert_eq!(
+            position_at_fraction(duration, 0.25).unwrap(),
+            Duration::from_secs(30)
+        );
+        assert_eq!(position_at_fraction(duration, 1.5).unwrap(), duration);
+        assert_eq!(
+            position_at_fraction(duration, -0.1).unwrap(),
+            Duration::ZERO
+        );
+        assert!(position_at_fraction(duration, f64::NAN).is_err());
+        assert!(position_at_fraction(Duration::ZERO, 0.5).is_err());
+    }
+
+    #[test]
+    fn relative_seeks_stay_within_the_media() {
+        let duration = Duration::from_secs(120);
+        let at = |secs| Duration::from_secs(secs);
+        assert_eq!(position_offset(at(60), duration, 10_000), at(70));
+        assert_eq!(position_offset(at(60), duration, -10_000), at(50));
+        assert_eq!(position_offset(at(5), duration, -10_000), Duration::ZERO);
+        assert_eq!(position_offset(at(115), duration, 10_000), duration);
+        assert_eq!(position_offset(at(60), duration, i64::MIN), Duration::ZERO);
+
+        // Unknown duration: only the start bounds the seek
+        assert_eq!(position_offset(at(60), Duration::ZERO, 10_000), at(70));
+        assert_eq!(
+            position_offset(at(5), Duration::ZERO, -10_000),
+            Duration::ZERO
+        );
+    }
+
+    #[test]
+    fn languages_match_across_code_styles() {
+        assert!(language_matches("en", "en"));
+        assert!(language_matches("eng", "en"));
+        assert!(language_matches("en", "ENG"));
+        assert!(language_matches("ger", "deu"));
+        assert!(language_matches("pt-BR", "pt"));
+        assert!(language_matches("pt_br", "pt-BR"));
+        assert!(language_matches("pt", "pt-BR"));
+
+        assert!(!language_matches("pt-PT", "pt-BR"));
+        assert!(!language_matches("jav", "ja"));
+        assert!(!language_matches("en", "fr"));
+        assert!(!language_matches("", ""));
+
+        assert!(language_matches("hrv", "hr"));
+        assert!(language_matches("ca", "cat"));
+        assert!(language_matches("cat-ES", "ca"));
+        assert!(language_matches("fil", "FIL"));
+        assert!(!language_matches("cat-ES", "ca-AD"));
+        assert!(!language_matches("tgl", "ca"));
+        assert!(!language_matches("", "ca"));
+        assert!(!language_matches("cat", ""));
+
+        // Languages whose codes share a prefix stay apart
+        assert!(!language_matches("slk", "sl"));
+        assert!(!language_matches("fao", "fa"));
+        assert!(!language_matches("tat", "ta"));
+        assert!(!language_matches("sme", "sm"));
+        assert!(language_matches("slo", "sk"));
+        assert!(language_matches("per", "fa"));
+    }
+
+    #[test]
+    fn trick_mode_flags_only_apply_off_normal_speed() {
+        let mode = TrickMode {
+            key_units_only: true,
+            no_audio: true,
+        };
+        assert_eq!(
+            mode.seek_flags(8.0),
+            gst::SeekFlags::TRICKMODE
+                | gst::SeekFlags::TRICKMODE_KEY_UNITS
+                | gst::SeekFlags::TRICKMODE_NO_AUDIO
+        );
+        assert_eq!(mode.seek_flags(1.0), gst::SeekFlags::empty());
+        assert_eq!(
+            TrickMode::default().seek_flags(8.0),
+            gst::SeekFlags::empty()
+        );
+    }
+}
@@ -1,11 +1,22 @@
 use gstreamer as gst;
+use std::num::NonZeroU8;
+use std::path::Path;
 use std::time::Duration;
 
 use crate::{
     Error,
-    video::types::{AudioTrack, Position, SubtitleTrack},
+    video::{
+        thumbnail::Thumbnail,
+        types::{
+            AudioTrack, FrameStats, PlaybackStatus, Position, SubtitleTrack, VideoTrack,
+            language_matches, position_at_fraction, position_offset,
+        },
+    },
 };
 
+/// File extensions accepted by [`Video::set_subtitle_file`].
+pub const SUBTITLE_FILE_EXTENSIONS: &[&str] = &["srt", "ass", "ssa", "vtt", "sub", "smi", "sami"];
+
 pub trait Video {
     type Video: Video;
     /// Create a new video instance from a given video which loads from `uri`.
@@ -15,6 +26,14 @@ pub trait Video {
     /// Get the size/resolution of the video as `(width, height)`.
     fn size(&self) -> (i32, i32);
 
+    /// Get the coded size of the video as `(width, height)`, in the decoder's pixels, which
+    /// need not be square.
+    fn natural_size(&self) -> (i32, i32);
+
+    /// Get the size the video is displayed at as `(width, height)`, once the pixel aspect
+    /// ratio (and any override of it) is applied. Lay the video out with this size.
+    fn display_size(&self) -> (i32, i32);
+
     /// Get the framerate of the video as frames per second.
     fn framerate(&self) -> f64;
 
@@ -51,11 +70,24 @@ pub trait Video {
     /// Set if the media is paused or not.
     fn set_paused(&mut self, paused: bool);
 
+    /// Get the overall playback status.
+    ///
+    /// Unlike combining [`paused`](Self::paused) and [`eos`](Self::eos), this reports
+    /// [`PlaybackStatus::Idle`] while the pipeline is still prerolling.
+    fn status(&self) -> PlaybackStatus;
+
     /// Get the current playback speed.
     fn speed(&self) -> f64;
 
     /// Set the playback speed of the media.
     /// The default speed is `1.0`.
+    ///
+    /// Speeds are clamped to [`MIN_SPEED`]..=[`MAX_SPEED`] in either direction, and zero,
+    /// NaN or infinite speeds return [`Error::InvalidState`]. Negative speeds play in
+    /// reverse, which only works when the demuxer supports it.
+    ///
+    /// [`MIN_SPEED`]: crate::video::types::MIN_SPEED
+    /// [`MAX_SPEED`]: crate::video::types::MAX_SPEED
     fn set_speed(&mut self, speed: f64) -> Result<(), Error>;
 
     /// Get the current playback position in time.
@@ -69,18 +101,68 @@ pub trait Video {
     /// Get the media duration.
     fn duration(&self) -> Duration;
 
+    /// Seek to `fraction` (0.0 to 1.0, clamped) of the duration, as scrub bars report it.
+    ///
+    /// Returns [`Error::InvalidState`] when the duration is unknown, e.g. for live streams.
+    fn seek_percent(&mut self, fraction: f64, accurate: bool) -> Result<(), Error> {
+        let position = position_at_fraction(self.duration(), fraction)?;
+        self.seek(position, accurate)
+    }
+
+    /// Seek `delta_ms` milliseconds from the current position (negative seeks back), as skip
+    /// buttons do.
+    ///
+    /// The target is clamped to the start and, when it is known, the end of the media.
+    fn seek_relative(&mut self, delta_ms: i64, accurate: bool) -> Result<(), Error> {
+        let position = position_offset(self.position(), self.duration(), delta_ms);
+        self.seek(position, accurate)
+    }
+
     /// Get the current subtitle URL.
     fn subtitle_url(&self) -> Option<url::Url>;
 
     /// Set the subtitle URL to display.
     fn set_subtitle_url(&mut self, url: &url::Url) -> Result<(), Error>;
 
+    /// Load an external subtitle file from the local filesystem.
+    ///
+    /// The path is converted to a `file://` URL and passed to [`set_subtitle_url`](Self::set_subtitle_url).
+    /// Returns [`Error::InvalidState`] if the file type is not one GStreamer's subparse understands.
+    fn set_subtitle_file(&mut self, path: &Path) -> Result<(), Error> {
+        let supported = path
+            .extension()
+            .and_then(|ext| ext.to_str())
+            .is_some_and(|ext| {
+                SUBTITLE_FILE_EXTENSIONS
+                    .iter()
+                    .any(|known| ext.eq_ignore_ascii_case(known))
+            });
+        if !supported {
+            return Err(Error::InvalidState);
+        }
+        let absolute = std::path::absolute(path)?;
+        let url = url::Url::from_file_path(&absolute).map_err(|_| Error::InvalidState)?;
+        self.set_subtitle_url(&url)
+    }
+
     /// Check if subtitles are enabled
     fn subtitles_enabled(&self) -> bool;
 
     /// Enable or disable subtitle display
     fn set_subtitles_enabled(&mut self, enabled: bool);
 
+    /// Check if the source is live (e.g. a camera or live broadcast).
+    ///
+    /// Live sources have no meaningful duration and cannot be scrubbed.
+    fn is_live(&self) -> bool;
+
+    /// Check if the media can be seeked, e.g. to disable a scrub bar. Refreshed whenever the
+    /// pipeline settles after a state change or seek.
+    fn seekable(&self) -> bool;
+
+    /// Get the number of rendered and dropped video frames since the video was created.
+    fn frame_stats(&self) -> FrameStats;
+
     /// Get the list of available subtitle tracks
     fn subtitle_tracks(&mut self) -> Vec<SubtitleTrack>;
 
@@ -90,6 +172,24 @@ pub trait Video {
     /// Select a specific subtitle track by index, or None to disable subtitles
     fn select_subtitle_track(&mut self, track_index: Option<i32>) -> Result<(), Error>;
 
+    /// Select the first subtitle track in `language`, a BCP-47 or ISO 639 code matched as
+    /// described in [`language_matches`].
+    ///
+    /// Returns [`Error::InvalidState`] when no track matches.
+    fn select_subtitle_track_by_language(&mut self, language: &str) -> Result<(), Error> {
+        let track = self
+            .subtitle_tracks()
+            .into_iter()
+            .find(|track| {
+                track
+                    .language
+                    .as_deref()
+                    .is_some_and(|tag| language_matches(tag, language))
+            })
+            .ok_or(Error::InvalidState)?;
+        self.select_subtitle_track(Some(track.index))
+    }
+
     /// Get the list of available audio tracks
     fn audio_tracks(&mut self) -> Vec<AudioTrack>;
 
@@ -99,9 +199,51 @@ pub trait Video {
     /// Select a specific audio track by index
     fn select_audio_track(&mut self, track_index: i32) -> Result<(), Error>;
 
+    /// Select the first audio track in `language`, see
+    /// [`select_subtitle_track_by_language`](Self::select_subtitle_track_by_language).
+    fn select_audio_track_by_language(&mut self, language: &str) -> Result<(), Error> {
+        let track = self
+            .audio_tracks()
+            .into_iter()
+            .find(|track| {
+                track
+                    .language
+                    .as_deref()
+                    .is_some_and(|tag| language_matches(tag, language))
+            })
+            .ok_or(Error::InvalidState)?;
+        self.select_audio_track(track.index)
+    }
+
+    /// Get the list of available video tracks, e.g. the angles of a multi-angle file
+    fn video_tracks(&self) -> Vec<VideoTrack>;
+
+    /// Get the currently selected video track index, -1 if none
+    fn current_video_track(&self) -> i32;
+
+    /// Select a specific video track by index, keeping the audio and subtitle selection
+    fn select_video_track(&mut self, track_index: i32) -> Result<(), Error>;
+
     /// Check if the video has video tracks (not just audio)
     fn has_video(&self) -> bool;
 
+    /// Check if the media has audio tracks, without building the track list like
+    /// [`audio_tracks`](Self::audio_tracks). False until the stream collection is known.
+    fn has_audio(&self) -> bool;
+
+    /// Check if the media has subtitle tracks, without building the track list like
+    /// [`subtitle_tracks`](Self::subtitle_tracks). False until the stream collection is known.
+    fn has_subtitles(&self) -> bool;
+
+    /// Decode preview frames at the given positions, shrunk by `downscale` on each axis.
+    ///
+    /// Frames come from a separate pipeline against the same URI, so playback is not disturbed.
+    fn generate_thumbnails(
+        &mut self,
+        positions: &[Position],
+        downscale: NonZeroU8,
+    ) -> Result<Vec<Thumbnail>, Error>;
+
     /// Get the underlying GStreamer pipeline.
     fn pipeline(&self) -> gst::Pipeline;
 }
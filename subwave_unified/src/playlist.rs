@@ -0,0 +1,71 @@
+//! Ordered list of media URIs for gapless playback.
+//!
+//! A [`Playlist`] only tracks which entry is current; pair it with
+//! [`SubwaveVideo::set_next_uri`](crate::video::SubwaveVideo::set_next_uri) to queue the
+//! following entry and [`SubwaveVideo::poll_track_change`](crate::video::SubwaveVideo::poll_track_change)
+//! to advance once the backend has switched.
+
+/// An ordered list of media URIs with a cursor on the current entry.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Playlist {
+    uris: Vec<url::Url>,
+    index: usize,
+}
+
+impl Playlist {
+    /// Create a playlist positioned at the first entry.
+    pub fn new(uris: Vec<url::Url>) -> Self {
+        Self { uris, index: 0 }
+    }
+
+    /// Append an entry to the end of the playlist.
+    pub fn push(&mut self, uri: url::Url) {
+        self.uris.push(uri);
+    }
+
+    /// All entries in play order.
+    pub fn uris(&self) -> &[url::Url] {
+        &self.uris
+    }
+
+    /// Index of the current entry.
+    pub fn index(&self) -> usize {
+        self.index
+    }
+
+    pub fn len(&self) -> usize {
+        self.uris.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.uris.is_empty()
+    }
+
+    /// The entry that is currently playing.
+    pub fn current(&self) -> Option<&url::Url> {
+        self.uris.get(self.index)
+    }
+
+    /// The entry that should be queued for gapless playback, if any.
+    pub fn peek_next(&self) -> Option<&url::Url> {
+        self.uris.get(self.index + 1)
+    }
+
+    /// Move to the next entry, returning it. Stays on the last entry when there is none.
+    pub fn advance(&mut self) -> Option<&url::Url> {
+        if self.index + 1 >= self.uris.len() {
+            return None;
+        }
+        self.index += 1;
+        self.current()
+    }
+
+    /// Jump to a specific entry, returning it if the index is in range.
+    pub fn select(&mut self, index: usize) -> Option<&url::Url> {
+        if index >= self.uris.len() {
+            return None;
+        }
+        self.index = index;
+        self.current()
+    }
+}
@@ -1,9 +1,17 @@
+use gstreamer as gst;
 use gstreamer::Pipeline;
+use gstreamer::prelude::*;
+use iced::advanced::image;
 use iced::{Element, Length};
 use log::warn;
-use std::time::Duration;
+use std::num::NonZeroU8;
+use std::time::{Duration, Instant};
 use subwave_appsink::video::AppsinkVideo;
-use subwave_core::video::types::{AudioTrack, SubtitleTrack};
+use subwave_core::gstplayflags::gst_play_flags::GstPlayFlags;
+use subwave_core::video::types::{
+    AudioTrack, FrameStats, PlaybackStatus, Position, SubtitleTrack, TrickMode, VideoTrack,
+    position_at_fraction, position_offset,
+};
 use subwave_core::video::video_trait::Video as VideoTrait;
 
 #[cfg(all(feature = "wayland", target_os = "linux"))]
@@ -29,12 +37,18 @@ pub enum BackendPreference {
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub struct SubwaveConfig {
     pub preference: BackendPreference,
+    /// Play audio only, for podcast and music players: no video is decoded, `has_video()`
+    /// is false and the widget draws nothing. There is no subsurface to gain, so this always
+    /// uses a headless appsink playbin3 with the VIDEO and TEXT flags cleared, whatever the
+    /// preference.
+    pub audio_only: bool,
 }
 
 impl Default for SubwaveConfig {
     fn default() -> Self {
         Self {
             preference: BackendPreference::Auto,
+            audio_only: false,
         }
     }
 }
@@ -80,8 +94,9 @@ impl OpenOptions {
     }
 }
 
-/// Snapshot of playback state used for backend switching
+/// Snapshot of playback state, used for backend switching and for persisting a resume point
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct PlaybackState {
     pub paused: bool,
     pub position: Duration,
@@ -94,6 +109,9 @@ pub struct PlaybackState {
     pub subtitle_url: Option<url::Url>,
 }
 
+/// How long a backend switch waits for the old pipeline to settle on a position.
+const SWITCH_SETTLE_TIMEOUT: Duration = Duration::from_millis(500);
+
 /// Environment-based backend selection
 #[inline]
 pub fn is_wayland() -> bool {
@@ -123,6 +141,9 @@ pub enum SubwaveVideo {
 impl SubwaveVideo {
     #[inline]
     fn select_backend(cfg: SubwaveConfig) -> BackendPreference {
+        if cfg.audio_only {
+            return BackendPreference::ForceAppsink;
+        }
         match cfg.preference {
             BackendPreference::Auto => {
                 if is_wayland() {
@@ -156,6 +177,23 @@ impl SubwaveVideo {
         }
     }
 
+    fn new_audio_only<T: AsRef<str>, U: AsRef<str>>(
+        uri: &url::Url,
+        headers: Option<&[(T, U)]>,
+        start_seconds: Option<f64>,
+    ) -> Result<AppsinkVideo, subwave_core::Error> {
+        let mut video = AppsinkVideo::new_with_setup(uri, |pipeline| {
+            pipeline.set_property("flags", GstPlayFlags::default().video(false).text(false));
+            if let Some(h) = headers {
+                subwave_core::http::set_http_headers_on_pipeline(pipeline, h);
+            }
+        })?;
+        if let Some(s) = start_seconds {
+            video.seek(Duration::from_secs_f64(s), true)?;
+        }
+        Ok(video)
+    }
+
     /// Create a new unified video instance from a URL, selecting backend by config.
     /// Optionally apply HTTP headers to the underlying pipeline.
     pub fn new_with_config<T: AsRef<str>, U: AsRef<str>>(
@@ -166,7 +204,9 @@ impl SubwaveVideo {
         let backend = Self::select_backend(cfg);
         match backend {
             BackendPreference::ForceAppsink => {
-                let v = if let Some(h) = headers {
+                let v = if cfg.audio_only {
+                    Self::new_audio_only(uri, headers, None)?
+                } else if let Some(h) = headers {
                     AppsinkVideo::new_with_headers(uri, h)?
                 } else {
                     AppsinkVideo::new(uri)?
@@ -202,6 +242,7 @@ impl SubwaveVideo {
                     uri: uri.clone(),
                     cfg: SubwaveConfig {
                         preference: BackendPreference::ForceAppsink,
+                        ..cfg
                     },
                     inner: Box::new(v),
                 })
@@ -218,7 +259,9 @@ impl SubwaveVideo {
         let start = options.start_seconds.filter(|s| s.is_finite() && *s > 0.0);
         match backend {
             BackendPreference::ForceAppsink => {
-                let video = if let Some(s) = start {
+                let video = if options.cfg.audio_only {
+                    Self::new_audio_only(uri, options.headers.as_deref(), start)?
+                } else if let Some(s) = start {
                     match &options.headers {
                         Some(h) => AppsinkVideo::new_with_start(uri, s, Some(h.as_slice()))?,
                         None => AppsinkVideo::new_with_start::<&str, &str>(uri, s, None)?,
@@ -284,6 +327,7 @@ impl SubwaveVideo {
                     uri: uri.clone(),
                     cfg: SubwaveConfig {
                         preference: BackendPreference::ForceAppsink,
+                        ..options.cfg
                     },
                     inner: Box::new(video),
                 })
@@ -340,6 +384,16 @@ impl SubwaveVideo {
         }
     }
 
+    pub fn status(&self) -> PlaybackStatus {
+        match self {
+            SubwaveVideo::Appsink { inner, .. } => inner.status(),
+            #[cfg(all(feature = "wayland", target_os = "linux"))]
+            SubwaveVideo::Wayland { .. } => self
+                .with_wayland(|video| video.status())
+                .unwrap_or(PlaybackStatus::Idle),
+        }
+    }
+
     pub fn play(&mut self) {
         self.set_paused(false)
     }
@@ -358,6 +412,38 @@ impl SubwaveVideo {
         }
     }
 
+    /// Change speed without a flushing seek, see [`AppsinkVideo::set_speed_instant`].
+    pub fn set_speed_instant(&mut self, speed: f64) -> Result<(), subwave_core::Error> {
+        match self {
+            SubwaveVideo::Appsink { inner, .. } => inner.set_speed_instant(speed),
+            #[cfg(all(feature = "wayland", target_os = "linux"))]
+            SubwaveVideo::Wayland { .. } => self
+                .with_wayland_mut(|video| video.set_speed_instant(speed))
+                .unwrap_or(Ok(())),
+        }
+    }
+
+    /// Trick-mode flags for later speed changes, see [`AppsinkVideo::set_trick_mode`].
+    pub fn set_trick_mode(&mut self, mode: TrickMode) {
+        match self {
+            SubwaveVideo::Appsink { inner, .. } => inner.set_trick_mode(mode),
+            #[cfg(all(feature = "wayland", target_os = "linux"))]
+            SubwaveVideo::Wayland { .. } => {
+                self.with_wayland_mut(|video| video.set_trick_mode(mode));
+            }
+        }
+    }
+
+    pub fn trick_mode(&self) -> TrickMode {
+        match self {
+            SubwaveVideo::Appsink { inner, .. } => inner.trick_mode(),
+            #[cfg(all(feature = "wayland", target_os = "linux"))]
+            SubwaveVideo::Wayland { .. } => self
+                .with_wayland(|video| video.trick_mode())
+                .unwrap_or_default(),
+        }
+    }
+
     pub fn position(&self) -> Duration {
         match self {
             SubwaveVideo::Appsink { inner, .. } => inner.position(),
@@ -388,6 +474,43 @@ impl SubwaveVideo {
         }
     }
 
+    /// Seek to `fraction` (0.0 to 1.0) of the duration, see [`VideoTrait::seek_percent`].
+    pub fn seek_percent(
+        &mut self,
+        fraction: f64,
+        accurate: bool,
+    ) -> Result<(), subwave_core::Error> {
+        let position = position_at_fraction(self.duration(), fraction)?;
+        self.seek(position, accurate)
+    }
+
+    /// Seek `delta_ms` milliseconds from the current position, see
+    /// [`VideoTrait::seek_relative`].
+    pub fn seek_relative(
+        &mut self,
+        delta_ms: i64,
+        accurate: bool,
+    ) -> Result<(), subwave_core::Error> {
+        let position = position_offset(self.position(), self.duration(), delta_ms);
+        self.seek(position, accurate)
+    }
+
+    /// Seek for a dragged slider, see [`AppsinkVideo::seek_coalesced`]. The Wayland backend
+    /// seeks directly.
+    pub fn seek_coalesced(
+        &mut self,
+        position: Duration,
+        accurate: bool,
+    ) -> Result<(), subwave_core::Error> {
+        match self {
+            SubwaveVideo::Appsink { inner, .. } => inner.seek_coalesced(position, accurate),
+            #[cfg(all(feature = "wayland", target_os = "linux"))]
+            SubwaveVideo::Wayland { .. } => self
+                .with_wayland_mut(|video| video.seek(position, accurate))
+                .unwrap_or(Err(subwave_core::Error::InvalidState)),
+        }
+    }
+
     pub fn set_volume(&mut self, volume: f64) {
         match self {
             SubwaveVideo::Appsink { inner, .. } => inner.set_volume(volume),
@@ -422,6 +545,58 @@ impl SubwaveVideo {
         }
     }
 
+    pub fn frame_stats(&self) -> FrameStats {
+        match self {
+            SubwaveVideo::Appsink { inner, .. } => inner.frame_stats(),
+            #[cfg(all(feature = "wayland", target_os = "linux"))]
+            SubwaveVideo::Wayland { .. } => self
+                .with_wayland(|video| video.frame_stats())
+                .unwrap_or_default(),
+        }
+    }
+
+    pub fn is_live(&self) -> bool {
+        match self {
+            SubwaveVideo::Appsink { inner, .. } => inner.is_live(),
+            #[cfg(all(feature = "wayland", target_os = "linux"))]
+            SubwaveVideo::Wayland { .. } => {
+                self.with_wayland(|video| video.is_live()).unwrap_or(false)
+            }
+        }
+    }
+
+    pub fn seekable(&self) -> bool {
+        match self {
+            SubwaveVideo::Appsink { inner, .. } => inner.seekable(),
+            #[cfg(all(feature = "wayland", target_os = "linux"))]
+            SubwaveVideo::Wayland { .. } => {
+                self.with_wayland(|video| video.seekable()).unwrap_or(false)
+            }
+        }
+    }
+
+    /// Number of consecutive network errors the backend is retrying.
+    pub fn error_count(&self) -> u32 {
+        match self {
+            SubwaveVideo::Appsink { inner, .. } => inner.error_count(),
+            #[cfg(all(feature = "wayland", target_os = "linux"))]
+            SubwaveVideo::Wayland { .. } => {
+                self.with_wayland(|video| video.error_count()).unwrap_or(0)
+            }
+        }
+    }
+
+    /// Whether the backend is reconnecting after a network error.
+    pub fn is_reconnecting(&self) -> bool {
+        match self {
+            SubwaveVideo::Appsink { inner, .. } => inner.is_reconnecting(),
+            #[cfg(all(feature = "wayland", target_os = "linux"))]
+            SubwaveVideo::Wayland { .. } => self
+                .with_wayland(|video| video.is_reconnecting())
+                .unwrap_or(false),
+        }
+    }
+
     pub fn has_video(&self) -> bool {
         match self {
             SubwaveVideo::Appsink { inner, .. } => inner.has_video(),
@@ -432,6 +607,26 @@ impl SubwaveVideo {
         }
     }
 
+    pub fn has_audio(&self) -> bool {
+        match self {
+            SubwaveVideo::Appsink { inner, .. } => inner.has_audio(),
+            #[cfg(all(feature = "wayland", target_os = "linux"))]
+            SubwaveVideo::Wayland { .. } => self
+                .with_wayland(|video| video.has_audio())
+                .unwrap_or(false),
+        }
+    }
+
+    pub fn has_subtitles(&self) -> bool {
+        match self {
+            SubwaveVideo::Appsink { inner, .. } => inner.has_subtitles(),
+            #[cfg(all(feature = "wayland", target_os = "linux"))]
+            SubwaveVideo::Wayland { .. } => self
+                .with_wayland(|video| video.has_subtitles())
+                .unwrap_or(false),
+        }
+    }
+
     // Size
     pub fn size(&self) -> (i32, i32) {
         match self {
@@ -443,6 +638,28 @@ impl SubwaveVideo {
         }
     }
 
+    /// Coded size in the decoder's pixels, see [`VideoTrait::natural_size`].
+    pub fn natural_size(&self) -> (i32, i32) {
+        match self {
+            SubwaveVideo::Appsink { inner, .. } => inner.natural_size(),
+            #[cfg(all(feature = "wayland", target_os = "linux"))]
+            SubwaveVideo::Wayland { .. } => self
+                .with_wayland(|video| video.natural_size())
+                .unwrap_or((0, 0)),
+        }
+    }
+
+    /// Size after pixel aspect correction, see [`VideoTrait::display_size`].
+    pub fn display_size(&self) -> (i32, i32) {
+        match self {
+            SubwaveVideo::Appsink { inner, .. } => inner.display_size(),
+            #[cfg(all(feature = "wayland", target_os = "linux"))]
+            SubwaveVideo::Wayland { .. } => self
+                .with_wayland(|video| video.display_size())
+                .unwrap_or((0, 0)),
+        }
+    }
+
     // Tracks and subtitles
     pub fn audio_tracks(&mut self) -> Vec<AudioTrack> {
         match self {
@@ -474,6 +691,51 @@ impl SubwaveVideo {
         }
     }
 
+    /// Select the first audio track in `language`, see
+    /// [`VideoTrait::select_audio_track_by_language`].
+    pub fn select_audio_track_by_language(
+        &mut self,
+        language: &str,
+    ) -> Result<(), subwave_core::Error> {
+        match self {
+            SubwaveVideo::Appsink { inner, .. } => inner.select_audio_track_by_language(language),
+            #[cfg(all(feature = "wayland", target_os = "linux"))]
+            SubwaveVideo::Wayland { .. } => self
+                .with_wayland_mut(|video| video.select_audio_track_by_language(language))
+                .unwrap_or(Err(subwave_core::Error::InvalidState)),
+        }
+    }
+
+    pub fn video_tracks(&self) -> Vec<VideoTrack> {
+        match self {
+            SubwaveVideo::Appsink { inner, .. } => inner.video_tracks(),
+            #[cfg(all(feature = "wayland", target_os = "linux"))]
+            SubwaveVideo::Wayland { .. } => self
+                .with_wayland(|video| video.video_tracks())
+                .unwrap_or_default(),
+        }
+    }
+
+    pub fn current_video_track(&self) -> i32 {
+        match self {
+            SubwaveVideo::Appsink { inner, .. } => inner.current_video_track(),
+            #[cfg(all(feature = "wayland", target_os = "linux"))]
+            SubwaveVideo::Wayland { .. } => self
+                .with_wayland(|video| video.current_video_track())
+                .unwrap_or(-1),
+        }
+    }
+
+    pub fn select_video_track(&mut self, index: i32) -> Result<(), subwave_core::Error> {
+        match self {
+            SubwaveVideo::Appsink { inner, .. } => inner.select_video_track(index),
+            #[cfg(all(feature = "wayland", target_os = "linux"))]
+            SubwaveVideo::Wayland { .. } => self
+                .with_wayland_mut(|video| video.select_video_track(index))
+                .unwrap_or(Err(subwave_core::Error::InvalidState)),
+        }
+    }
+
     pub fn subtitle_tracks(&mut self) -> Vec<SubtitleTrack> {
         match self {
             SubwaveVideo::Appsink { inner, .. } => inner.subtitle_tracks(),
@@ -504,6 +766,23 @@ impl SubwaveVideo {
         }
     }
 
+    /// Select the first subtitle track in `language`, see
+    /// [`VideoTrait::select_subtitle_track_by_language`].
+    pub fn select_subtitle_track_by_language(
+        &mut self,
+        language: &str,
+    ) -> Result<(), subwave_core::Error> {
+        match self {
+            SubwaveVideo::Appsink { inner, .. } => {
+                inner.select_subtitle_track_by_language(language)
+            }
+            #[cfg(all(feature = "wayland", target_os = "linux"))]
+            SubwaveVideo::Wayland { .. } => self
+                .with_wayland_mut(|video| video.select_subtitle_track_by_language(language))
+                .unwrap_or(Err(subwave_core::Error::InvalidState)),
+        }
+    }
+
     pub fn subtitles_enabled(&self) -> bool {
         match self {
             SubwaveVideo::Appsink { inner, .. } => inner.subtitles_enabled(),
@@ -528,6 +807,25 @@ impl SubwaveVideo {
         }
     }
 
+    /// Decode scrubbing previews at the given positions, shrunk by `downscale` on each axis.
+    pub fn generate_thumbnails(
+        &mut self,
+        positions: &[Position],
+        downscale: NonZeroU8,
+    ) -> Result<Vec<image::Handle>, subwave_core::Error> {
+        let thumbnails = match self {
+            SubwaveVideo::Appsink { inner, .. } => inner.generate_thumbnails(positions, downscale),
+            #[cfg(all(feature = "wayland", target_os = "linux"))]
+            SubwaveVideo::Wayland { .. } => self
+                .with_wayland_mut(|video| video.generate_thumbnails(positions, downscale))
+                .unwrap_or(Err(subwave_core::Error::InvalidState)),
+        }?;
+        Ok(thumbnails
+            .into_iter()
+            .map(|t| image::Handle::from_rgba(t.width, t.height, t.rgba))
+            .collect())
+    }
+
     /// Convenience to construct a backend-agnostic video widget.
     pub fn widget<'a, Message, Theme>(
         &'a self,
@@ -602,6 +900,16 @@ impl SubwaveVideo {
             }
         }
     }
+    /// Id of the video, which its log messages carry as the target `subwave[id]`. A new id
+    /// is allocated when switching backends.
+    pub fn id(&self) -> u64 {
+        match self {
+            SubwaveVideo::Appsink { inner, .. } => inner.id(),
+            #[cfg(all(feature = "wayland", target_os = "linux"))]
+            SubwaveVideo::Wayland { .. } => self.with_wayland(|video| video.id()).unwrap_or(0),
+        }
+    }
+
     /// Return the configured backend preference
     pub fn config(&self) -> SubwaveConfig {
         match self {
@@ -620,6 +928,40 @@ impl SubwaveVideo {
         }
     }
 
+    /// Queue the URI to play once the current one finishes (gapless playback).
+    ///
+    /// Speed, volume and mute carry over to the next track. Use
+    /// [`poll_track_change`](Self::poll_track_change) to learn when the switch happened.
+    pub fn set_next_uri(&mut self, uri: &url::Url) {
+        match self {
+            SubwaveVideo::Appsink { inner, .. } => inner.set_next_uri(uri),
+            #[cfg(all(feature = "wayland", target_os = "linux"))]
+            SubwaveVideo::Wayland { .. } => {
+                warn!(
+                    "Gapless playback is not supported on the Wayland backend; ignoring next URI"
+                );
+            }
+        }
+    }
+
+    /// Returns the new URI once if playback switched to the queued next URI since the last call.
+    ///
+    /// Also updates [`uri`](Self::uri) so it keeps reflecting what is playing.
+    pub fn poll_track_change(&mut self) -> Option<url::Url> {
+        match self {
+            SubwaveVideo::Appsink { uri, inner, .. } => {
+                let current = inner.current_uri()?;
+                if current == *uri {
+                    return None;
+                }
+                *uri = current.clone();
+                Some(current)
+            }
+            #[cfg(all(feature = "wayland", target_os = "linux"))]
+            SubwaveVideo::Wayland { .. } => None,
+        }
+    }
+
     /// Identify the current backend
     pub fn backend(&self) -> BackendPreference {
         match self {
@@ -629,9 +971,54 @@ impl SubwaveVideo {
         }
     }
 
-    fn capture_state(&self) -> PlaybackState {
+    /// Position to resume from after a backend switch.
+    ///
+    /// Right after a seek or state change the old pipeline answers position queries with
+    /// stale values (or not at all), so wait briefly for it to settle first.
+    fn settled_position(&self) -> Duration {
+        let pipeline = self.pipeline();
+        let deadline = Instant::now() + SWITCH_SETTLE_TIMEOUT;
+        let _ = pipeline.state(gst::ClockTime::from_nseconds(
+            SWITCH_SETTLE_TIMEOUT.as_nanos() as u64,
+        ));
+
+        let reported = self.position();
+        loop {
+            if let Some(pos) = pipeline.query_position::<gst::ClockTime>() {
+                let pos = Duration::from_nanos(pos.nseconds());
+                // A zero position right after a flush is not trusted unless nothing else is known
+                if pos > Duration::ZERO || reported == Duration::ZERO {
+                    return pos;
+                }
+            }
+            if Instant::now() >= deadline {
+                return reported;
+            }
+            std::thread::sleep(Duration::from_millis(10));
+        }
+    }
+
+    /// Snapshot of the current playback state. Pass it to
+    /// [`restore_playback_state`](Self::restore_playback_state) to resume later.
+    pub fn playback_state(&self) -> PlaybackState {
+        // A Wayland video that never drew still holds the state it was created with
+        #[cfg(all(feature = "wayland", target_os = "linux"))]
+        if let Some(st) = self.with_wayland(|video| video.pending_state()).flatten() {
+            return PlaybackState {
+                paused: st.paused,
+                position: st.position,
+                speed: st.speed,
+                volume: st.volume,
+                muted: st.muted,
+                audio_track: st.audio_track,
+                subtitle_track: st.subtitle_track,
+                subtitles_enabled: st.subtitles_enabled,
+                subtitle_url: st.subtitle_url,
+            };
+        }
+
         let paused = self.paused();
-        let position = self.position();
+        let position = self.settled_position();
         let speed = match self {
             SubwaveVideo::Appsink { inner, .. } => inner.speed(),
             #[cfg(all(feature = "wayland", target_os = "linux"))]
@@ -682,12 +1069,49 @@ impl SubwaveVideo {
             let _ = inner.set_subtitle_url(url);
         }
         let _ = inner.seek(st.position, true);
+        // Let the seek land so position() reports the resume point straight away
+        let _ = inner.pipeline().state(gst::ClockTime::from_nseconds(
+            SWITCH_SETTLE_TIMEOUT.as_nanos() as u64,
+        ));
         inner.set_volume(st.volume);
         inner.set_muted(st.muted);
         let _ = inner.set_speed(st.speed);
         inner.set_paused(st.paused);
     }
 
+    #[cfg(all(feature = "wayland", target_os = "linux"))]
+    fn pending_state_from(st: &PlaybackState) -> PendingState {
+        PendingState {
+            paused: st.paused,
+            position: st.position,
+            speed: st.speed,
+            volume: st.volume,
+            muted: st.muted,
+            audio_track: st.audio_track,
+            subtitle_track: st.subtitle_track,
+            subtitles_enabled: st.subtitles_enabled,
+            subtitle_url: st.subtitle_url.clone(),
+        }
+    }
+
+    /// Apply a snapshot taken with [`playback_state`](Self::playback_state). The Wayland
+    /// backend queues it and applies it once the pipeline has prerolled.
+    pub fn restore_playback_state(&mut self, st: &PlaybackState) {
+        match self {
+            SubwaveVideo::Appsink { inner, .. } => Self::apply_state_to_appsink(inner, st),
+            #[cfg(all(feature = "wayland", target_os = "linux"))]
+            SubwaveVideo::Wayland { .. } => {
+                let pending = Self::pending_state_from(st);
+                if self
+                    .with_wayland(|video| video.queue_pending_state(pending))
+                    .is_none()
+                {
+                    warn!("Wayland video is unavailable; playback state not restored");
+                }
+            }
+        }
+    }
+
     /// Change backend preference and switch if needed (preserving playback state)
     pub fn set_preference(
         &mut self,
@@ -695,15 +1119,18 @@ impl SubwaveVideo {
     ) -> Result<(), subwave_core::Error> {
         let uri = self.uri().clone();
         let current = self.backend();
-        if (preference == BackendPreference::Auto
-            && current
-                == if is_wayland() {
-                    BackendPreference::ForceWayland
-                } else {
-                    BackendPreference::ForceAppsink
-                })
+        let audio_only = self.config().audio_only;
+        if audio_only
+            || (preference == BackendPreference::Auto
+                && current
+                    == if is_wayland() {
+                        BackendPreference::ForceWayland
+                    } else {
+                        BackendPreference::ForceAppsink
+                    })
             || preference == current
         {
+            // Audio-only playback stays on appsink whatever the preference
             // No change required
             // Still update config
             match self {
@@ -714,7 +1141,7 @@ impl SubwaveVideo {
             return Ok(());
         }
         // Capture state
-        let st = self.capture_state();
+        let st = self.playback_state();
         // Build new per preference
         match preference {
             BackendPreference::ForceAppsink => {
@@ -722,7 +1149,10 @@ impl SubwaveVideo {
                 Self::apply_state_to_appsink(&mut inner, &st);
                 *self = SubwaveVideo::Appsink {
                     uri,
-                    cfg: SubwaveConfig { preference },
+                    cfg: SubwaveConfig {
+                        preference,
+                        audio_only,
+                    },
                     inner: Box::new(inner),
                 };
                 Ok(())
@@ -731,20 +1161,13 @@ impl SubwaveVideo {
             BackendPreference::ForceWayland => {
                 let v = SubsurfaceVideo::new(&uri)?;
                 // Queue state into Wayland video to apply after init
-                v.queue_pending_state(PendingState {
-                    paused: st.paused,
-                    position: st.position,
-                    speed: st.speed,
-                    volume: st.volume,
-                    muted: st.muted,
-                    audio_track: st.audio_track,
-                    subtitle_track: st.subtitle_track,
-                    subtitles_enabled: st.subtitles_enabled,
-                    subtitle_url: st.subtitle_url.clone(),
-                });
+                v.queue_pending_state(Self::pending_state_from(&st));
                 *self = SubwaveVideo::Wayland {
                     uri,
-                    cfg: SubwaveConfig { preference },
+                    cfg: SubwaveConfig {
+                        preference,
+                        audio_only,
+                    },
                     handle: Rc::new(RefCell::new(Some(Box::new(v)))),
                     pending: Arc::new(Mutex::new(None)),
                 };
@@ -784,6 +1207,44 @@ impl SubwaveVideo {
                 .unwrap_or_default(),
         }
     }
+
+    /// Run playback on `clock` instead of the pipeline's own, see
+    /// [`AppsinkVideo::set_clock`]. A backend switch builds a new pipeline, which does not
+    /// inherit the clock.
+    pub fn set_clock(&mut self, clock: &gst::Clock) -> Result<(), subwave_core::Error> {
+        match self {
+            SubwaveVideo::Appsink { inner, .. } => inner.set_clock(clock),
+            #[cfg(all(feature = "wayland", target_os = "linux"))]
+            SubwaveVideo::Wayland { .. } => self
+                .with_wayland_mut(|video| video.set_clock(clock))
+                .unwrap_or(Ok(())),
+        }
+    }
+
+    /// The clock playback runs on, for other pipelines or an audio engine to follow.
+    pub fn use_pipeline_clock(&self) -> gst::Clock {
+        match self {
+            SubwaveVideo::Appsink { inner, .. } => inner.use_pipeline_clock(),
+            #[cfg(all(feature = "wayland", target_os = "linux"))]
+            SubwaveVideo::Wayland { .. } => self
+                .with_wayland(|video| video.use_pipeline_clock())
+                .unwrap_or_else(gst::SystemClock::obtain),
+        }
+    }
+
+    /// Fix the pipeline's base time, see [`AppsinkVideo::set_base_time`].
+    pub fn set_base_time(&mut self, base_time: gst::ClockTime) -> Result<(), subwave_core::Error> {
+        match self {
+            SubwaveVideo::Appsink { inner, .. } => {
+                inner.set_base_time(base_time);
+                Ok(())
+            }
+            #[cfg(all(feature = "wayland", target_os = "linux"))]
+            SubwaveVideo::Wayland { .. } => self
+                .with_wayland_mut(|video| video.set_base_time(base_time))
+                .unwrap_or(Err(subwave_core::Error::InvalidState)),
+        }
+    }
 }
 
 impl std::fmt::Debug for SubwaveVideo {
@@ -0,0 +1,50 @@
+#![cfg(all(feature = "wayland", target_os = "linux"))]
+
+use std::time::Duration;
+
+use gstreamer::{self as gst, prelude::*};
+use subwave_core::test_util::TestClip;
+use subwave_unified::video::{BackendPreference, SubwaveConfig, SubwaveVideo};
+
+#[test]
+#[ignore = "needs gst-plugins-base and gst-plugins-good; run with --ignored"]
+fn backend_switch_resumes_at_position() {
+    let clip = TestClip::new();
+    let target = Duration::from_secs(3);
+    let config = SubwaveConfig {
+        preference: BackendPreference::ForceAppsink,
+        ..SubwaveConfig::default()
+    };
+    let mut video = SubwaveVideo::new_with_config(clip.uri(), config, None::<&[(&str, &str)]>)
+        .expect("open clip");
+    video.set_paused(true);
+    video.seek(target, true).expect("seek");
+
+    // Switch immediately, while the seek may still be in flight. Without a compositor the
+    // Wayland video never prerolls, so its queued state has to carry over on the way back.
+    video
+        .set_preference(BackendPreference::ForceWayland)
+        .expect("switch to wayland");
+    assert_eq!(video.backend(), BackendPreference::ForceWayland);
+    video
+        .set_preference(BackendPreference::ForceAppsink)
+        .expect("switch to appsink");
+    assert_eq!(video.backend(), BackendPreference::ForceAppsink);
+    assert!(video.paused());
+
+    // Ask the new pipeline itself rather than the cached seek target
+    let pipeline = video.pipeline();
+    let (result, state, _) = pipeline.state(gst::ClockTime::from_seconds(5));
+    assert!(result.is_ok());
+    assert_eq!(state, gst::State::Paused);
+    let position = pipeline
+        .query_position::<gst::ClockTime>()
+        .map(|position| Duration::from_nanos(position.nseconds()))
+        .expect("position of the prerolled pipeline");
+    // Every frame of the clip is a keyframe, so a seek that carried over lands within one
+    let frame = Duration::from_secs(1) / 30;
+    assert!(
+        position.abs_diff(target) <= frame,
+        "resumed at {position:?}, expected {target:?}"
+    );
+}
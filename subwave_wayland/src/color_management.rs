@@ -199,6 +199,7 @@ impl ColorManager {
         video_surface: &wayland_client::protocol::wl_surface::WlSurface,
         qh: &QueueHandle<super::subsurface_manager::State>,
         event_queue: &mut wayland_client::EventQueue<super::subsurface_manager::State>,
+        state: &mut super::subsurface_manager::State,
     ) -> Result<()> {
         // Skip if we already applied the same colorimetry
         if self.applied_colorimetry.as_deref() == Some(colorimetry) {
@@ -301,9 +302,8 @@ impl ColorManager {
         let desc = creator.create(qh, DescriptionRole::HdrPq);
 
         // Roundtrip to receive the ready/failed event for the description
-        let mut state = super::subsurface_manager::State::new();
         event_queue
-            .roundtrip(&mut state)
+            .roundtrip(state)
             .map_err(|e| crate::Error::Wayland(format!("Roundtrip for HDR desc: {}", e)))?;
 
         // Apply to the video surface
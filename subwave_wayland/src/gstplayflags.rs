@@ -31,7 +31,7 @@ pub mod gst_play_flags {
             const SOFT_COLORBALANCE = 0x00000400;
             /// Force audio/video filter(s) to be applied
             const FORCE_FILTERS     = 0x00000800;
-            /// Force only software-based decoders (no effect for playbin3)
+            /// Force only software-based decoders (no effect for playbin3; see `DecoderPreference`)
             const FORCE_SW_DECODERS = 0x00001000;
         }
     }
@@ -145,7 +145,9 @@ pub mod gst_play_flags {
             // TEXT so playbin3 never activates subtitleoverlay, which
             // would interfere with HDR passthrough on the video chain.
             // NATIVE_VIDEO is safe because subtitleoverlay is never
-            // involved.
+            // involved.  SOFT_VOLUME keeps volume and mute on playsink's
+            // own volume element rather than the audio sink, so the two
+            // stay independent.
             Self::VIDEO
                 | Self::AUDIO
                 | Self::NATIVE_VIDEO
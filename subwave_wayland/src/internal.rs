@@ -4,42 +4,77 @@ use std::{
     time::{Duration, Instant},
 };
 
-use gstreamer::StreamCollection;
+use gstreamer::{self as gst, StreamCollection};
 use parking_lot::Mutex as ParkMutex;
 use std::sync::mpsc;
 use subwave_core::{
+    rtsp::RtspConfig,
     types::PendingState,
-    video::types::{AudioTrack, SubtitleTrack},
+    video::types::{
+        AudioTrack, BufferConfig, DecoderPreference, SubtitleTrack, TrickMode, VideoTrack,
+    },
 };
 
 use crate::{
     pipeline::SubsurfacePipeline,
     subtitle_runtime::{ActiveSubtitleSelection, SubtitleProbeEvent, WaylandSubtitleScheduler},
     video::Cmd,
-    WaylandSubsurfaceManager,
+    WaylandIntegration, WaylandSubsurfaceManager,
 };
 
 // Internal encapsulates all state and is only accessed behind the RwLock
 pub(crate) struct Internal {
-    // Identity
+    // Identity; `log_target` is `subwave[id]`, the target of log messages about this video
+    pub(crate) id: u64,
+    pub(crate) log_target: String,
     pub(crate) uri: url::Url,
 
     // Core handles
     pub(crate) pipeline: Option<Arc<SubsurfacePipeline>>, // read-mostly; clone and drop lock before external calls
     pub(crate) subsurface: Option<Arc<WaylandSubsurfaceManager>>, // same
+    // Integration and bounds passed to init_wayland, kept to rebuild the pipeline on reload
+    pub(crate) wayland_init: Option<(WaylandIntegration, (i32, i32, i32, i32))>,
+    // Subsurface taken down by detach_surface. waylandsink still holds its video surface,
+    // so it is only dropped once the pipeline is rebuilt onto a new one.
+    pub(crate) detached_subsurface: Option<Arc<WaylandSubsurfaceManager>>,
 
     pub(crate) duration: Option<Duration>,
+    // Set when the bus reports a new duration until the widget publishes it
+    pub(crate) duration_unreported: bool,
+    // Set when a stream collection updated the track lists until the widget publishes it
+    pub(crate) streams_unreported: bool,
     pub(crate) speed: f64,
+    // Requested volume and mute, applied to every pipeline built for this video
+    pub(crate) volume: f64,
+    pub(crate) muted: bool,
+    // Flags added to rate-change seeks away from 1.0
+    pub(crate) trick_mode: TrickMode,
 
     // Playback state flags for trait support
     pub(crate) looping: bool,
     pub(crate) is_eos: bool,
+    // Set on a non-looping EOS until the widget publishes its end-of-stream message
+    pub(crate) eos_unreported: bool,
     pub(crate) restart_stream: bool,
+    pub(crate) has_error: bool,
+    // The error that stopped playback, and whether the widget still has to publish it
+    pub(crate) last_error: Option<gst::glib::Error>,
+    pub(crate) error_unreported: bool,
+    pub(crate) seekable: bool, // refreshed by the bus thread on AsyncDone/StateChanged
+    pub(crate) hidden: bool,
+    pub(crate) video_opaque: bool,
+    pub(crate) background_color: [u8; 4],
+
+    // Network error recovery, driven by the bus thread
+    pub(crate) error_count: u32,
+    pub(crate) is_reconnecting: bool,
 
     // Buffering state
     pub(crate) is_buffering: bool,
     pub(crate) buffering_percent: i32,
-    pub(crate) user_paused: bool,
+    // Explicit user pause intent, atomic so set_paused can record it without the write
+    // lock. Bus commands read it when they run, not when their message arrived.
+    pub(crate) user_paused: Arc<AtomicBool>,
 
     // Bus thread control
     pub(crate) bus_thread: Option<JoinHandle<()>>,
@@ -53,6 +88,8 @@ pub(crate) struct Internal {
 
     // Track selection state
     pub(crate) stream_collection: Option<StreamCollection>,
+    // Last framerate read from caps, reported while the caps carry none (VFR, before preroll)
+    pub(crate) last_framerate: ParkMutex<Option<f64>>,
 
     // Subtitle tracking
     pub(crate) available_subtitles: Vec<SubtitleTrack>,
@@ -67,7 +104,12 @@ pub(crate) struct Internal {
     pub(crate) available_audio_tracks: Vec<AudioTrack>,
     pub(crate) current_audio_track: i32,
 
+    // Video track tracking
+    pub(crate) available_video_tracks: Vec<VideoTrack>,
+    pub(crate) current_video_track: i32,
+
     pub(crate) audio_index_to_stream_id: Vec<String>,
+    pub(crate) video_index_to_stream_id: Vec<String>,
     pub(crate) subtitle_index_to_stream_id: Vec<String>,
 
     pub(crate) selected_stream_ids: Vec<String>,
@@ -78,10 +120,20 @@ pub(crate) struct Internal {
     // Pending HTTP headers to apply to pipeline when available
     pub(crate) pending_http_headers: Option<Vec<(String, String)>>,
 
+    // Network buffering overrides applied when the pipeline is built
+    pub(crate) buffer_config: BufferConfig,
+    // rtspsrc settings applied via source-setup for rtsp:// URIs
+    pub(crate) rtsp_config: RtspConfig,
+    // Hardware/software decoder ranking applied just before the pipeline is built, if requested
+    pub(crate) decoder_preference: Option<DecoderPreference>,
+
     // Autoplay gating: when true, wait for seek completion (AsyncDone) before starting playback
     pub(crate) pending_play_after_seek: bool,
     pub(crate) pending_start_position: Option<Duration>,
 
+    // Clock to run on instead of the pipeline's own choice, applied as it is built
+    pub(crate) clock: Option<gst::Clock>,
+
     // Throttling
     pub(crate) last_position_update: Instant,
 }
@@ -4,8 +4,9 @@ use gstreamer_video::{
     prelude::{VideoOverlayExt, VideoOverlayExtManual},
     VideoOverlay,
 };
-use std::sync::{mpsc, Arc, Mutex};
-use std::time::Duration;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{mpsc, Arc, Mutex, OnceLock};
+use std::time::{Duration, Instant};
 
 use crate::gstplayflags::gst_play_flags::GstPlayFlags;
 
@@ -16,7 +17,9 @@ use crate::{
     },
     Error, Result, WaylandIntegration, WaylandSubsurfaceManager,
 };
-use subwave_core::video::types::Position;
+use subwave_core::rtsp::RtspConfig;
+use subwave_core::video::elements::find_factory_by_klass;
+use subwave_core::video::types::{BufferConfig, Position, TrickMode};
 
 /// Build a `GstWaylandDisplayHandleContextType` context carrying `display`.
 ///
@@ -120,8 +123,21 @@ fn pgs_display_set_event(
 pub struct SubsurfacePipeline {
     speed: f64,
     pub pipeline: Arc<gst::Pipeline>,
+    // Set when a state change reports NoPreroll, i.e. the source is live
+    is_live: AtomicBool,
+    // While set, buffers are dropped before waylandsink so the detached video surface
+    // stays unmapped (see `set_video_hidden`)
+    video_hidden: Arc<AtomicBool>,
+    // When the first buffer reached waylandsink (see `first_frame_rendered`)
+    first_frame_at: Arc<OnceLock<Instant>>,
+    // `subwave[id]` of the owning video, used as the target of every pipeline log
+    log_target: String,
 }
 
+/// How long after the first buffer reaches waylandsink before its frame is taken to be on
+/// screen; rendering and the compositor's next repaint both happen after the pad probe.
+const FIRST_FRAME_SETTLE: Duration = Duration::from_millis(50);
+
 impl SubsurfacePipeline {
     pub fn send_select_streams(&self, ids: &[String]) -> bool {
         let evt = gst::event::SelectStreams::new(ids.iter().map(|s| s.as_str()));
@@ -131,6 +147,59 @@ impl SubsurfacePipeline {
         false
     }
 
+    /// Converter in front of waylandsink: `vapostproc` where VA-API is available, otherwise
+    /// plain `videoconvert`. waylandsink only takes system memory or DMABuf, so a GL
+    /// upload would need downloading again and gains nothing.
+    fn build_video_converter(compositor_has_cm: bool, log_target: &str) -> Result<gst::Element> {
+        let vapostproc = match gst::ElementFactory::make("vapostproc")
+            .name("vconvert")
+            .property("add-borders", false)
+            .property("disable-passthrough", true)
+            .build()
+        {
+            Ok(vapostproc) => vapostproc,
+            Err(err) => {
+                // Without vapostproc nothing tone-maps; HDR reaches the compositor as decoded
+                log::warn!(
+                    target: log_target,
+                    "[pipeline] vapostproc unavailable ({}), using videoconvert",
+                    err
+                );
+                return gst::ElementFactory::make("videoconvert")
+                    .name("vconvert")
+                    .build()
+                    .map_err(|err| {
+                        log::error!(target: log_target, "Failed to build videoconvert: {}", err);
+                        Error::Pipeline("Failed to build videoconvert".to_string())
+                    });
+            }
+        };
+        log::info!(target: log_target, "[pipeline] Using vapostproc for video conversion");
+
+        if vapostproc.has_property("hdr-tone-mapping") {
+            if compositor_has_cm {
+                // Compositor supports color management — let HDR pixels pass
+                // through to waylandsink untouched.  The compositor will do
+                // the tone-mapping using the image description we set on the
+                // surface via wp-color-management-v1.
+                vapostproc.set_property("hdr-tone-mapping", false);
+                log::info!(
+                    target: log_target,
+                    "[pipeline] vapostproc hdr-tone-mapping DISABLED (compositor has CM)"
+                );
+            } else {
+                // No compositor CM — vapostproc must tone-map HDR→SDR itself.
+                vapostproc.set_property("hdr-tone-mapping", true);
+                log::info!(
+                    target: log_target,
+                    "[pipeline] vapostproc hdr-tone-mapping ENABLED (no compositor CM)"
+                );
+            }
+        }
+        Ok(vapostproc)
+    }
+
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         uri: &url::Url,
         subsurface: &Arc<WaylandSubsurfaceManager>,
@@ -139,6 +208,9 @@ impl SubsurfacePipeline {
         compositor_has_cm: bool,
         active_subtitle_selection: &Arc<parking_lot::Mutex<ActiveSubtitleSelection>>,
         subtitle_tx: mpsc::Sender<SubtitleProbeEvent>,
+        buffering: &BufferConfig,
+        rtsp: &RtspConfig,
+        log_target: &str,
     ) -> Result<Self> {
         gst::init()?;
 
@@ -155,10 +227,22 @@ impl SubsurfacePipeline {
                 Error::Pipeline("Failed to downcast to pipeline from playbin3".to_string())
             })?;
 
+        buffering.apply_to_playbin(pipeline.upcast_ref());
+
+        if subwave_core::rtsp::is_rtsp_uri(uri) {
+            log::info!(
+                target: log_target,
+                "[pipeline] RTSP source detected, latency={}ms protocols={:?}",
+                rtsp.latency_ms,
+                rtsp.protocols
+            );
+            subwave_core::rtsp::set_rtsp_config_on_pipeline(&pipeline, *rtsp);
+        }
+
         pipeline.set_property("uri", uri.as_str());
 
         let play_flags = GstPlayFlags::wayland_native();
-        log::info!("[pipeline] playbin flags={play_flags}");
+        log::info!(target: log_target, "[pipeline] playbin flags={play_flags}");
         pipeline.set_property("flags", play_flags);
 
         // ── Build waylandsink ──────────────────────────────────────────
@@ -179,7 +263,7 @@ impl SubsurfacePipeline {
             .property("sync", true)
             .build()
             .map_err(|err| {
-                log::error!("Failed to build waylandsink: {}", err);
+                log::error!(target: log_target, "Failed to build waylandsink: {}", err);
                 Error::Pipeline("Failed to build waylandsink".to_string())
             })?;
 
@@ -189,44 +273,23 @@ impl SubsurfacePipeline {
             video_sink.set_property("force-aspect-ratio", false);
         }
 
-        // ── Build vapostproc ───────────────────────────────────────────
-        let vapostproc = gst::ElementFactory::make("vapostproc")
-            .name("vapostproc")
-            .property("add-borders", false)
-            .property("disable-passthrough", true)
-            .build()
-            .map_err(|err| {
-                log::error!("Failed to build vapostproc: {}", err);
-                Error::Pipeline("Failed to build vapostproc".to_string())
-            })?;
+        let video_hidden = Arc::new(AtomicBool::new(false));
+        Self::attach_hidden_video_probe(&video_sink, &video_hidden, log_target);
+        let first_frame_at = Arc::new(OnceLock::new());
+        Self::attach_first_frame_probe(&video_sink, &first_frame_at);
 
-        if vapostproc.has_property("hdr-tone-mapping") {
-            if compositor_has_cm {
-                // Compositor supports color management — let HDR pixels pass
-                // through to waylandsink untouched.  The compositor will do
-                // the tone-mapping using the image description we set on the
-                // surface via wp-color-management-v1.
-                vapostproc.set_property("hdr-tone-mapping", false);
-                log::info!("[pipeline] vapostproc hdr-tone-mapping DISABLED (compositor has CM)");
-            } else {
-                // No compositor CM — vapostproc must tone-map HDR→SDR itself.
-                vapostproc.set_property("hdr-tone-mapping", true);
-                log::info!("[pipeline] vapostproc hdr-tone-mapping ENABLED (no compositor CM)");
-            }
-        }
+        let converter = Self::build_video_converter(compositor_has_cm, log_target)?;
 
         // ── Assemble video-sink bin ────────────────────────────────────
         let vsink_bin = gst::Bin::with_name("waylandsink-bin");
 
-        vsink_bin
-            .add_many([&vapostproc, &video_sink])
-            .map_err(|e| {
-                Error::Pipeline(format!("Failed to add elements to video-sink bin: {}", e))
-            })?;
-        gst::Element::link_many([&vapostproc, &video_sink])
+        vsink_bin.add_many([&converter, &video_sink]).map_err(|e| {
+            Error::Pipeline(format!("Failed to add elements to video-sink bin: {}", e))
+        })?;
+        gst::Element::link_many([&converter, &video_sink])
             .map_err(|e| Error::Pipeline(format!("Failed to link video-sink chain: {}", e)))?;
 
-        let ghost_pad = gst::GhostPad::with_target(&vapostproc.static_pad("sink").unwrap())
+        let ghost_pad = gst::GhostPad::with_target(&converter.static_pad("sink").unwrap())
             .map_err(|e| {
                 Error::Pipeline(format!("Failed to create ghost pad for video-sink: {}", e))
             })?;
@@ -241,16 +304,19 @@ impl SubsurfacePipeline {
         pipeline.set_property("video-sink", vsink_bin);
 
         // ── Prepare subsurface geometry ────────────────────────────────
-        log::debug!("Setting initial subsurface size (will be updated by widget)");
+        log::debug!(
+            target: log_target,
+            "Setting initial subsurface size (will be updated by widget)"
+        );
         subsurface.set_position(0, 0);
         let init_w = bounds.2.max(1);
         let init_h = bounds.3.max(1);
-        log::info!("[subs] Initial size from bounds: {}x{}", init_w, init_h);
+        log::info!(target: log_target, "[subs] Initial size from bounds: {}x{}", init_w, init_h);
         subsurface.set_size(init_w, init_h);
 
         subsurface.force_damage_and_commit();
         subsurface.flush()?;
-        log::debug!("Forced damage and committed subsurface");
+        log::debug!(target: log_target, "Forced damage and committed subsurface");
 
         // Install the Wayland sink sync handler only after the subsurface has
         // valid initial geometry. This follows GStreamer's waylandsink embedding
@@ -260,6 +326,7 @@ impl SubsurfacePipeline {
         let display_addr = integration.display as usize;
         let surface_handle = subsurface.surface_handle();
         let init_bounds = (bounds.0, bounds.1, init_w, init_h);
+        let sync_log_target = log_target.to_owned();
         if let Some(bus) = pipeline.bus() {
             bus.set_sync_handler(move |_bus, msg| {
                 match msg.view() {
@@ -269,6 +336,7 @@ impl SubsurfacePipeline {
                             || context_type == "GstWlDisplayHandleContextType"
                         {
                             log::info!(
+                                target: &sync_log_target,
                                 "[sync] Providing Wayland display context (type={context_type})"
                             );
                             let context = wayland_display_context(display_addr);
@@ -286,6 +354,7 @@ impl SubsurfacePipeline {
                             .is_some_and(|s| s.name().as_str() == "prepare-window-handle");
                         if is_prepare_window {
                             log::info!(
+                                target: &sync_log_target,
                                 "[sync] Providing window handle 0x{surface_handle:x} and render rect {init_bounds:?}"
                             );
                             if let Some(src) = msg.src() {
@@ -310,18 +379,75 @@ impl SubsurfacePipeline {
             });
         }
 
-        Self::install_subtitle_probes(&pipeline, active_subtitle_selection, subtitle_tx);
+        Self::install_subtitle_probes(
+            &pipeline,
+            active_subtitle_selection,
+            subtitle_tx,
+            log_target,
+        );
 
         log::debug!(
+            target: log_target,
             "Pipeline ready (Wayland sync handler installed, scheduled subtitle probes armed)"
         );
 
         Ok(Self {
             speed: 1.0,
             pipeline: Arc::new(pipeline),
+            is_live: AtomicBool::new(false),
+            video_hidden,
+            first_frame_at,
+            log_target: log_target.to_owned(),
         })
     }
 
+    /// Drop video buffers ahead of waylandsink while the surface is hidden. Preroll
+    /// buffers (the sink is not settled in PLAYING) still pass so seeks can complete.
+    fn attach_hidden_video_probe(
+        video_sink: &gst::Element,
+        hidden: &Arc<AtomicBool>,
+        log_target: &str,
+    ) {
+        let Some(pad) = video_sink.static_pad("sink") else {
+            log::warn!(
+                target: log_target,
+                "[visibility] waylandsink has no sink pad; hiding will not pause video"
+            );
+            return;
+        };
+        let hidden = Arc::clone(hidden);
+        let sink = video_sink.downgrade();
+        pad.add_probe(gst::PadProbeType::BUFFER, move |_pad, _info| {
+            if !hidden.load(Ordering::Relaxed) {
+                return gst::PadProbeReturn::Ok;
+            }
+            let playing = sink.upgrade().is_some_and(|sink| {
+                let (_, current, pending) = sink.state(gst::ClockTime::ZERO);
+                current == gst::State::Playing && pending == gst::State::VoidPending
+            });
+            if playing {
+                gst::PadProbeReturn::Drop
+            } else {
+                gst::PadProbeReturn::Ok
+            }
+        });
+    }
+
+    /// Record when the first buffer gets past the hidden-video probe into waylandsink.
+    fn attach_first_frame_probe(
+        video_sink: &gst::Element,
+        first_frame_at: &Arc<OnceLock<Instant>>,
+    ) {
+        let Some(pad) = video_sink.static_pad("sink") else {
+            return;
+        };
+        let first_frame_at = Arc::clone(first_frame_at);
+        pad.add_probe(gst::PadProbeType::BUFFER, move |_pad, _info| {
+            first_frame_at.get_or_init(Instant::now);
+            gst::PadProbeReturn::Remove
+        });
+    }
+
     // ── Scheduled subtitle interception (PGS + text/x-raw) ────────────
     //
     // Subtitle buffers are intercepted on demuxer source pads and decoded into
@@ -371,8 +497,10 @@ impl SubsurfacePipeline {
         pipeline: &gst::Pipeline,
         active_selection: &Arc<parking_lot::Mutex<ActiveSubtitleSelection>>,
         subtitle_tx: mpsc::Sender<SubtitleProbeEvent>,
+        log_target: &str,
     ) {
         let active = Arc::clone(active_selection);
+        let log_target = log_target.to_owned();
 
         pipeline.connect_deep_element_added(move |_pipeline, _bin, element| {
             let factory_name = element
@@ -380,12 +508,15 @@ impl SubsurfacePipeline {
                 .map(|factory| factory.name().to_string())
                 .unwrap_or_default();
 
-            if !factory_name.contains("demux") {
+            if !emits_subtitle_pads(&factory_name) {
                 return;
             }
 
             let element_name = element.name().to_string();
-            log::info!("[subs] Watching demuxer {element_name} for subtitle pads");
+            log::info!(
+                target: &log_target,
+                "[subs] Watching {factory_name} {element_name} for subtitle pads"
+            );
 
             let active_existing = Arc::clone(&active);
             let tx_existing = subtitle_tx.clone();
@@ -395,11 +526,13 @@ impl SubsurfacePipeline {
                     &active_existing,
                     tx_existing.clone(),
                     &element_name,
+                    &log_target,
                 );
             }
 
             let active_dynamic = Arc::clone(&active);
             let tx_dynamic = subtitle_tx.clone();
+            let log_target_dynamic = log_target.clone();
             element.connect_pad_added(move |element, pad| {
                 if pad.direction() != gst::PadDirection::Src {
                     return;
@@ -409,6 +542,7 @@ impl SubsurfacePipeline {
                     &active_dynamic,
                     tx_dynamic.clone(),
                     element.name().as_ref(),
+                    &log_target_dynamic,
                 );
             });
         });
@@ -419,21 +553,24 @@ impl SubsurfacePipeline {
         active: &Arc<parking_lot::Mutex<ActiveSubtitleSelection>>,
         subtitle_tx: mpsc::Sender<SubtitleProbeEvent>,
         element_name: &str,
+        log_target: &str,
     ) {
         match Self::classify_pad(pad) {
             Some(SubtitlePadKind::Pgs) => {
                 log::info!(
+                    target: log_target,
                     "[subs] Attaching scheduled PGS probe on {element_name}:{}",
                     pad.name()
                 );
-                Self::attach_pgs_probe(pad, active, subtitle_tx);
+                Self::attach_pgs_probe(pad, active, subtitle_tx, log_target);
             }
             Some(SubtitlePadKind::Text) => {
                 log::info!(
+                    target: log_target,
                     "[subs] Attaching scheduled text probe on {element_name}:{}",
                     pad.name()
                 );
-                Self::attach_text_probe(pad, active, subtitle_tx);
+                Self::attach_text_probe(pad, active, subtitle_tx, log_target);
             }
             None => {}
         }
@@ -455,7 +592,7 @@ impl SubsurfacePipeline {
         let mut stream_id_guard = pad_stream_id.lock().ok()?;
         if stream_id_guard.is_none() {
             if let Some(stream_id) = Self::pad_stream_id(probe_pad) {
-                log::info!("[{log_target}] Resolved pad stream-id: {stream_id}");
+                log::info!(target: log_target, "[subs] Resolved pad stream-id: {stream_id}");
                 *stream_id_guard = Some(stream_id);
             }
         }
@@ -480,7 +617,7 @@ impl SubsurfacePipeline {
         }
 
         if let Ok(mut guard) = pad_stream_id.lock() {
-            log::debug!("[{log_target}] STREAM_START stream-id: {stream_id}");
+            log::debug!(target: log_target, "[subs] STREAM_START stream-id: {stream_id}");
             *guard = Some(stream_id.to_string());
         }
     }
@@ -491,13 +628,13 @@ impl SubsurfacePipeline {
         log_target: &str,
     ) {
         let Some(time_segment) = segment.segment().downcast_ref::<gst::ClockTime>() else {
-            log::debug!("[{log_target}] Ignoring non-time subtitle segment");
+            log::debug!(target: log_target, "[subs] Ignoring non-time subtitle segment");
             return;
         };
 
         if let Ok(mut timing) = timing.lock() {
             timing.update_segment(time_segment);
-            log::debug!("[{log_target}] Updated subtitle segment: {time_segment:?}");
+            log::debug!(target: log_target, "[subs] Updated subtitle segment: {time_segment:?}");
         }
     }
 
@@ -559,8 +696,10 @@ impl SubsurfacePipeline {
         pad: &gst::Pad,
         active: &Arc<parking_lot::Mutex<ActiveSubtitleSelection>>,
         subtitle_tx: mpsc::Sender<SubtitleProbeEvent>,
+        log_target: &str,
     ) {
         let active = Arc::clone(active);
+        let log_target = log_target.to_owned();
         let decoder = Mutex::new(crate::pgs_decoder::PgsDecoder::new());
         let pad_stream_id = Mutex::new(Self::pad_stream_id(pad));
         let timing = Mutex::new(PadSubtitleTiming::new());
@@ -574,13 +713,16 @@ impl SubsurfacePipeline {
                             probe_pad,
                             &pad_stream_id,
                             &active,
-                            "pgs-probe",
+                            &log_target,
                         ) else {
                             return gst::PadProbeReturn::Ok;
                         };
                         let Some((start, _buffer_end)) = Self::buffer_window(buffer, &timing)
                         else {
-                            log::debug!("[pgs-probe] Buffer without timestamp; skipping");
+                            log::debug!(
+                                target: &log_target,
+                                "[pgs-probe] Buffer without timestamp; skipping"
+                            );
                             return gst::PadProbeReturn::Ok;
                         };
                         let Ok(map) = buffer.map_readable() else {
@@ -600,6 +742,7 @@ impl SubsurfacePipeline {
                                 .and_then(|pts| Self::running_time_for_duration_timestamp(pts, &timing))
                                 .unwrap_or(start);
                             log::debug!(
+                                target: &log_target,
                                 "[pgs-probe] scheduling display set: buffer_start={start:?}, raw_pts={:?}, display_start={display_start:?}",
                                 display_set.pts
                             );
@@ -619,7 +762,7 @@ impl SubsurfacePipeline {
                             Self::update_pad_stream_id_from_event(
                                 &pad_stream_id,
                                 stream_start,
-                                "pgs-probe",
+                                &log_target,
                             );
                         }
                         gst::EventView::Segment(segment) => {
@@ -627,11 +770,11 @@ impl SubsurfacePipeline {
                                 probe_pad,
                                 &pad_stream_id,
                                 &active,
-                                "pgs-probe",
+                                &log_target,
                             )
                             .is_some()
                             {
-                                Self::update_timing_from_segment(&timing, segment, "pgs-probe");
+                                Self::update_timing_from_segment(&timing, segment, &log_target);
                             }
                         }
                         gst::EventView::Gap(_) => {
@@ -641,6 +784,7 @@ impl SubsurfacePipeline {
                             // absence/buffering, not the subtitle's presentation end, and can
                             // arrive immediately after a bitmap display set.
                             log::debug!(
+                                target: &log_target,
                                 "[pgs-probe] Ignoring GAP event; PGS display sets carry clear timing"
                             );
                         }
@@ -650,7 +794,7 @@ impl SubsurfacePipeline {
                                 &pad_stream_id,
                                 &active,
                                 &subtitle_tx,
-                                "pgs-probe",
+                                &log_target,
                             );
                         }
                         gst::EventView::Eos(_) => {
@@ -659,7 +803,7 @@ impl SubsurfacePipeline {
                                 &pad_stream_id,
                                 &active,
                                 &subtitle_tx,
-                                "pgs-probe",
+                                &log_target,
                             );
                         }
                         _ => {}
@@ -676,8 +820,10 @@ impl SubsurfacePipeline {
         pad: &gst::Pad,
         active: &Arc<parking_lot::Mutex<ActiveSubtitleSelection>>,
         subtitle_tx: mpsc::Sender<SubtitleProbeEvent>,
+        log_target: &str,
     ) {
         let active = Arc::clone(active);
+        let log_target = log_target.to_owned();
         let pad_stream_id = Mutex::new(Self::pad_stream_id(pad));
         let timing = Mutex::new(PadSubtitleTiming::new());
 
@@ -690,12 +836,15 @@ impl SubsurfacePipeline {
                             probe_pad,
                             &pad_stream_id,
                             &active,
-                            "text-probe",
+                            &log_target,
                         ) else {
                             return gst::PadProbeReturn::Ok;
                         };
                         let Some((start, end)) = Self::buffer_window(buffer, &timing) else {
-                            log::debug!("[text-probe] Buffer without timestamp; skipping");
+                            log::debug!(
+                                target: &log_target,
+                                "[text-probe] Buffer without timestamp; skipping"
+                            );
                             return gst::PadProbeReturn::Ok;
                         };
                         let Ok(map) = buffer.map_readable() else {
@@ -709,7 +858,10 @@ impl SubsurfacePipeline {
                             )
                         } else {
                             let preview: String = text.chars().take(80).collect();
-                            log::debug!("[text-probe] queued subtitle: {preview}...");
+                            log::debug!(
+                                target: &log_target,
+                                "[text-probe] queued subtitle: {preview}..."
+                            );
                             crate::subtitle_scheduler::DecodedSubtitleEvent::show(
                                 stream_id,
                                 generation,
@@ -725,7 +877,7 @@ impl SubsurfacePipeline {
                             Self::update_pad_stream_id_from_event(
                                 &pad_stream_id,
                                 stream_start,
-                                "text-probe",
+                                &log_target,
                             );
                         }
                         gst::EventView::Segment(segment) => {
@@ -733,11 +885,11 @@ impl SubsurfacePipeline {
                                 probe_pad,
                                 &pad_stream_id,
                                 &active,
-                                "text-probe",
+                                &log_target,
                             )
                             .is_some()
                             {
-                                Self::update_timing_from_segment(&timing, segment, "text-probe");
+                                Self::update_timing_from_segment(&timing, segment, &log_target);
                             }
                         }
                         gst::EventView::Gap(gap) => {
@@ -745,7 +897,7 @@ impl SubsurfacePipeline {
                                 probe_pad,
                                 &pad_stream_id,
                                 &active,
-                                "text-probe",
+                                &log_target,
                             ) {
                                 let at = Self::gap_running_time(gap, &timing);
                                 let event = crate::subtitle_scheduler::DecodedSubtitleEvent::clear(
@@ -760,7 +912,7 @@ impl SubsurfacePipeline {
                                 &pad_stream_id,
                                 &active,
                                 &subtitle_tx,
-                                "text-probe",
+                                &log_target,
                             );
                         }
                         gst::EventView::Eos(_) => {
@@ -769,7 +921,7 @@ impl SubsurfacePipeline {
                                 &pad_stream_id,
                                 &active,
                                 &subtitle_tx,
-                                "text-probe",
+                                &log_target,
                             );
                         }
                         _ => {}
@@ -785,29 +937,87 @@ impl SubsurfacePipeline {
     /// Start playback
     pub fn play(&self) -> Result<()> {
         let current_state = self.pipeline.current_state();
-        log::debug!("play() called, current state: {:?}", current_state);
+        log::debug!(target: &self.log_target, "play() called, current state: {:?}", current_state);
 
         // Non-blocking: request PAUSED to trigger preroll if needed, do not wait
         if current_state != gst::State::Paused && current_state != gst::State::Playing {
-            if let Err(e) = self.pipeline.set_state(gst::State::Paused) {
-                log::debug!("Failed to request PAUSED state: {:?}", e);
-                return Err(Error::Pipeline(format!("Failed to pause: {:?}", e)));
+            match self.pipeline.set_state(gst::State::Paused) {
+                Ok(change) => self.note_state_change(change),
+                Err(e) => {
+                    log::debug!(
+                        target: &self.log_target,
+                        "Failed to request PAUSED state: {:?}",
+                        e
+                    );
+                    return Err(Error::Pipeline(format!("Failed to pause: {:?}", e)));
+                }
             }
         }
 
         // Immediately request PLAYING; bus thread will observe readiness/AsyncDone
-        log::debug!("Requesting PLAYING state (non-blocking)...");
-        self.pipeline
+        log::debug!(target: &self.log_target, "Requesting PLAYING state (non-blocking)...");
+        let change = self
+            .pipeline
             .set_state(gst::State::Playing)
             .map_err(|e| Error::Pipeline(format!("Failed to play: {:?}", e)))?;
+        self.note_state_change(change);
 
         Ok(())
     }
 
+    fn note_state_change(&self, change: gst::StateChangeSuccess) {
+        if change == gst::StateChangeSuccess::NoPreroll
+            && !self.is_live.swap(true, Ordering::Relaxed)
+        {
+            log::info!(target: &self.log_target, "Detected live source");
+        }
+    }
+
+    /// Factory names of the video path: the decoder playbin3 picked (once plugged), followed
+    /// by the converter (`vapostproc` or `videoconvert`) and the `waylandsink` inside our
+    /// video sink bin.
+    pub fn video_chain(&self) -> Vec<String> {
+        let decoder = find_factory_by_klass(self.pipeline.upcast_ref(), &["Decoder", "Video"]);
+        let sink_elements = ["vconvert", "vsink"].into_iter().filter_map(|name| {
+            self.pipeline
+                .by_name(name)
+                .and_then(|element| element.factory())
+                .map(|factory| factory.name().to_string())
+        });
+        decoder.into_iter().chain(sink_elements).collect()
+    }
+
+    /// Stop (or resume) feeding frames to waylandsink without touching the pipeline state,
+    /// so audio keeps playing while the video surface is detached.
+    pub fn set_video_hidden(&self, hidden: bool) {
+        self.video_hidden.store(hidden, Ordering::Relaxed);
+        if !hidden {
+            // Redraw the last frame right away in case playback is paused
+            if let Some(video_sink) = self.pipeline.by_name("vsink") {
+                if let Some(video_overlay) = video_sink.dynamic_cast_ref::<VideoOverlay>() {
+                    video_overlay.expose();
+                }
+            }
+        }
+    }
+
+    /// Whether waylandsink has had its first frame long enough for it to be composited, so
+    /// revealing the video surface no longer shows an empty (black) buffer.
+    pub fn first_frame_rendered(&self) -> bool {
+        self.first_frame_at
+            .get()
+            .is_some_and(|at| at.elapsed() >= FIRST_FRAME_SETTLE)
+    }
+
+    /// Whether a state change has reported `NoPreroll`, which only live sources do
+    pub fn is_live(&self) -> bool {
+        self.is_live.load(Ordering::Relaxed)
+    }
+
     /// Pause playback
     pub fn pause(&self) -> Result<()> {
         let current_state = self.pipeline.current_state();
-        log::debug!("pause() called from state: {:?}", current_state);
+        log::debug!(target: &self.log_target, "pause() called from state: {:?}", current_state);
 
         // Non-blocking: request PAUSED and return
         self.pipeline
@@ -887,15 +1097,25 @@ impl SubsurfacePipeline {
         Ok(())
     }
 
+    /// Mute or unmute the pipeline, leaving the volume as it is
+    pub fn set_muted(&self, muted: bool) {
+        self.pipeline.set_property("mute", muted);
+    }
+
     /// Update the render rectangle for the video output
     pub fn set_render_rectangle(&self, x: i32, y: i32, width: i32, height: i32) {
         if let Some(video_sink) = self.pipeline.by_name("vsink") {
             if let Some(video_overlay) = video_sink.dynamic_cast_ref::<VideoOverlay>() {
                 // Safe to call - this updates where waylandsink renders within the surface
                 if let Err(e) = video_overlay.set_render_rectangle(x, y, width, height) {
-                    log::error!("Failed to update render rectangle: {}", e);
+                    log::error!(
+                        target: &self.log_target,
+                        "Failed to update render rectangle: {}",
+                        e
+                    );
                 } else {
                     log::debug!(
+                        target: &self.log_target,
                         "Updated render rectangle to x={}, y={}, w={}, h={}",
                         x,
                         y,
@@ -911,8 +1131,8 @@ impl SubsurfacePipeline {
         }
     }
 
-    /// Set the playback rate (speed)
-    pub fn set_playback_rate(&self, rate: f64) -> Result<()> {
+    /// Set the playback rate (speed), with `trick_mode`'s flags when away from 1.0
+    pub fn set_playback_rate(&self, rate: f64, trick_mode: TrickMode) -> Result<()> {
         // Get current position for the seek
         let position = self
             .pipeline
@@ -920,7 +1140,7 @@ impl SubsurfacePipeline {
             .ok_or_else(|| Error::Pipeline("Failed to query position".into()))?;
 
         // Perform seek with new rate
-        let flags = gst::SeekFlags::FLUSH;
+        let flags = gst::SeekFlags::FLUSH | trick_mode.seek_flags(rate);
 
         self.pipeline
             .seek(
@@ -936,6 +1156,41 @@ impl SubsurfacePipeline {
         Ok(())
     }
 
+    /// Set the playback rate with an instant rate change, which does not flush or move the
+    /// position. Falls back to [`Self::set_playback_rate`] when the direction changes, a
+    /// trick mode is set (an instant change cannot switch its flags) or the pipeline refuses
+    /// the seek.
+    pub fn set_playback_rate_instant(
+        &self,
+        current_rate: f64,
+        rate: f64,
+        trick_mode: TrickMode,
+    ) -> Result<()> {
+        if rate > 0.0
+            && current_rate > 0.0
+            && !trick_mode.is_enabled()
+            && self
+                .pipeline
+                .seek(
+                    rate,
+                    gst::SeekFlags::INSTANT_RATE_CHANGE,
+                    gst::SeekType::None,
+                    gst::ClockTime::NONE,
+                    gst::SeekType::None,
+                    gst::ClockTime::NONE,
+                )
+                .is_ok()
+        {
+            return Ok(());
+        }
+        log::debug!(
+            target: &self.log_target,
+            "Instant rate change to {} unavailable, using a flushing seek",
+            rate
+        );
+        self.set_playback_rate(rate, trick_mode)
+    }
+
     /// Get the current audio track index
     #[allow(dead_code)]
     pub fn current_audio_track(&self) -> i32 {
@@ -960,7 +1215,7 @@ impl SubsurfacePipeline {
 
 impl Drop for SubsurfacePipeline {
     fn drop(&mut self) {
-        log::debug!("Beginning cleanup");
+        log::debug!(target: &self.log_target, "Beginning cleanup");
 
         // Clear the sync handler first to prevent callbacks during teardown.
         if let Some(bus) = self.pipeline.bus() {
@@ -969,24 +1224,65 @@ impl Drop for SubsurfacePipeline {
 
         // First, stop the pipeline
         if let Err(e) = self.pipeline.set_state(gst::State::Null) {
-            log::error!("Error: Failed to set state to Null during cleanup: {:?}", e);
+            log::error!(
+                target: &self.log_target,
+                "Error: Failed to set state to Null during cleanup: {:?}",
+                e
+            );
         }
 
         // Wait for state change to complete
         let _ = self.pipeline.state(gst::ClockTime::from_seconds(1));
 
-        log::debug!("Cleanup completed");
+        log::debug!(target: &self.log_target, "Cleanup completed");
+    }
+}
+
+/// Elements whose source pads carry subtitle streams: demuxers for embedded tracks, and
+/// `subparse` for external files set through `suburi`, whose text would otherwise never
+/// reach the subtitle surface since waylandsink only shows video.
+fn emits_subtitle_pads(factory_name: &str) -> bool {
+    factory_name.contains("demux") || factory_name == "subparse"
+}
+
+#[cfg(test)]
+impl SubsurfacePipeline {
+    /// A bare playbin3 with the flags [`Self::new`] sets, for tests that have no compositor
+    /// to build waylandsink against.
+    pub(crate) fn without_surface() -> Self {
+        gst::init().unwrap();
+        let pipeline = gst::ElementFactory::make("playbin3")
+            .build()
+            .unwrap()
+            .downcast::<gst::Pipeline>()
+            .unwrap();
+        pipeline.set_property("flags", GstPlayFlags::wayland_native());
+        Self {
+            speed: 1.0,
+            pipeline: Arc::new(pipeline),
+            is_live: AtomicBool::new(false),
+            video_hidden: Arc::default(),
+            first_frame_at: Arc::default(),
+            log_target: subwave_core::video::types::video_log_target(0),
+        }
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use super::{pgs_display_set_event, WaylandSubtitlePayload};
+    use super::{
+        emits_subtitle_pads, pgs_display_set_event, ActiveSubtitleSelection, SubsurfacePipeline,
+        SubtitleProbeEvent, WaylandSubtitlePayload,
+    };
     use crate::{
         pgs_decoder::{PgsDisplaySet, PgsFrame},
         subtitle_scheduler::{DecodedSubtitleEvent, SubtitleAction, SubtitleScheduler},
+        text_renderer::TextRenderer,
     };
-    use std::time::Duration;
+    use gstreamer::{self as gst, prelude::*};
+    use std::sync::{mpsc, Arc};
+    use std::time::{Duration, Instant};
+    use subwave_core::test_util::TestClip;
 
     const STREAM: &str = "pgs/en";
 
@@ -994,6 +1290,101 @@ mod tests {
         Duration::from_millis(value)
     }
 
+    #[test]
+    fn external_subtitle_parsers_are_probed() {
+        assert!(emits_subtitle_pads("matroskademux"));
+        assert!(emits_subtitle_pads("subparse"));
+        assert!(!emits_subtitle_pads("vah264dec"));
+    }
+
+    #[test]
+    #[ignore = "needs gst-plugins-base and gst-plugins-good; run with --ignored"]
+    fn sidecar_srt_cues_render_to_subtitle_frames() {
+        let clip = TestClip::new();
+        let dir = tempfile::tempdir().expect("temp dir");
+        let srt = dir.path().join("clip.srt");
+        std::fs::write(
+            &srt,
+            "1\n00:00:00,000 --> 00:00:04,000\nHello from the sidecar\n\n",
+        )
+        .expect("write srt");
+
+        // The same probes `SubsurfacePipeline::new` installs, on a playbin3 without waylandsink
+        let pipeline = SubsurfacePipeline::without_surface();
+        let playbin = &pipeline.pipeline;
+        let video_sink = gst::ElementFactory::make("fakesink").build().unwrap();
+        playbin.set_property("video-sink", &video_sink);
+        playbin.set_property("uri", clip.uri().as_str());
+        let suburi = url::Url::from_file_path(&srt).unwrap();
+        playbin.set_property("suburi", suburi.as_str());
+        let active = Arc::new(parking_lot::Mutex::new(ActiveSubtitleSelection::default()));
+        let (tx, rx) = mpsc::channel();
+        SubsurfacePipeline::install_subtitle_probes(playbin, &active, tx, "subwave[test]");
+
+        playbin.set_state(gst::State::Paused).expect("preroll");
+        let bus = playbin.bus().unwrap();
+        let deadline = Instant::now() + Duration::from_secs(10);
+        let stream_id = loop {
+            assert!(
+                Instant::now() < deadline,
+                "no subtitle stream in the collection"
+            );
+            let Some(msg) = bus.timed_pop_filtered(
+                gst::ClockTime::from_mseconds(100),
+                &[gst::MessageType::StreamCollection, gst::MessageType::Error],
+            ) else {
+                continue;
+            };
+            match msg.view() {
+                gst::MessageView::StreamCollection(collection) => {
+                    let text = collection
+                        .stream_collection()
+                        .iter()
+                        .find(|stream| stream.stream_type().contains(gst::StreamType::TEXT));
+                    if let Some(id) = text.and_then(|stream| stream.stream_id()) {
+                        break id.to_string();
+                    }
+                }
+                gst::MessageView::Error(err) => panic!("pipeline error: {}", err.error()),
+                _ => {}
+            }
+        };
+
+        // Selecting the track only arms the probes, as `select_subtitle_track` does; the
+        // flushing seek replays the cue parsed during preroll
+        active.lock().set_stream(Some(stream_id.clone()));
+        playbin
+            .seek_simple(gst::SeekFlags::FLUSH, gst::ClockTime::ZERO)
+            .expect("seek");
+        playbin.set_state(gst::State::Playing).expect("play");
+
+        let cue = loop {
+            let remaining = deadline.saturating_duration_since(Instant::now());
+            match rx
+                .recv_timeout(remaining)
+                .expect("no subtitle cue from subparse")
+            {
+                SubtitleProbeEvent::Decoded(DecodedSubtitleEvent::Show(cue)) => break cue,
+                _ => continue,
+            }
+        };
+        assert_eq!(cue.stream_id, stream_id);
+        assert_eq!(cue.start, Duration::ZERO);
+        let WaylandSubtitlePayload::Text(text) = cue.payload else {
+            panic!("expected a text cue, got {:?}", cue.payload);
+        };
+        assert!(text.contains("Hello from the sidecar"));
+
+        // What `SubsurfaceVideo::tick` hands to `attach_subtitle_frame`
+        let (width, height) = (320, 240);
+        let argb = TextRenderer::new()
+            .expect("a system font")
+            .render(&text, width, height, 1.0)
+            .expect("rendered cue");
+        assert_eq!(argb.len(), width * height * 4);
+        assert!(argb.chunks_exact(4).any(|pixel| pixel[3] != 0));
+    }
+
     #[test]
     fn non_empty_pgs_display_sets_have_no_scheduled_end() {
         let frames = vec![PgsFrame {
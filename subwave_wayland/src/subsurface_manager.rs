@@ -6,6 +6,7 @@ use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
 use tempfile::tempfile;
 use wayland_backend::client::{Backend, ObjectId};
+use wayland_client::protocol::wl_output::{self, WlOutput};
 use wayland_client::protocol::wl_region::WlRegion;
 use wayland_client::protocol::wl_surface::Event;
 use wayland_client::{
@@ -14,7 +15,7 @@ use wayland_client::{
         wl_shm::WlShm, wl_shm_pool::WlShmPool, wl_subcompositor::WlSubcompositor,
         wl_subsurface::WlSubsurface, wl_surface::WlSurface,
     },
-    Connection, Dispatch, EventQueue, Proxy, QueueHandle,
+    Connection, Dispatch, EventQueue, Proxy, QueueHandle, WEnum,
 };
 use wayland_protocols::wp::viewporter::client::{
     wp_viewport::WpViewport, wp_viewporter::WpViewporter,
@@ -33,6 +34,9 @@ pub struct WaylandSubsurfaceManager {
     /// Event queue for handling Wayland events
     event_queue: Mutex<EventQueue<State>>,
 
+    /// Dispatch state kept after construction so output and scale events keep being tracked
+    state: Mutex<State>,
+
     /// Shared compositor
     compositor: WlCompositor,
 
@@ -72,15 +76,24 @@ pub struct WaylandSubsurfaceManager {
     /// The size of the renderable area we provide gstreamer
     source_size: Arc<Mutex<(i32, i32, i32, i32)>>,
 
+    /// Placement (x, y, width, height) of the video within the widget area, for content fit.
+    /// `None` stretches the video over the whole area.
+    video_rect: Arc<Mutex<Option<(i32, i32, i32, i32)>>>,
+
+    /// Whether the video surface is declared opaque to the compositor
+    video_opaque: AtomicBool,
+
     /// Flag indicating we need to update on next parent commit
     needs_update: Arc<AtomicBool>,
 
     /// Shared memory object for creating black buffer
     shm: Option<WlShm>,
 
-    /// Background buffer (black rectangle)
+    /// Background buffer (a rectangle of `background_color`)
     background_buffer: Mutex<Option<WlBuffer>>,
     background_pool: Mutex<Option<WlShmPool>>,
+    /// RGBA color of the background, opaque black by default
+    background_color: Mutex<[u8; 4]>,
 
     /// Subtitle buffer resources
     subtitle_buffer: Mutex<Option<WlBuffer>>,
@@ -108,12 +121,39 @@ impl std::fmt::Debug for WaylandSubsurfaceManager {
     }
 }
 
+/// A bound wl_output and the properties that affect how our surfaces map to pixels
+pub(crate) struct OutputInfo {
+    /// Registry name of the global, matched against `GlobalRemove`
+    pub(crate) name: u32,
+    pub(crate) output: WlOutput,
+    pub(crate) scale: i32,
+    pub(crate) transform: Option<wl_output::Transform>,
+}
+
+/// Globals the subsurfaces cannot work without once bound
+const REQUIRED_GLOBALS: [&str; 4] = [
+    "wl_compositor",
+    "wl_subcompositor",
+    "wp_viewporter",
+    "wl_shm",
+];
+
 /// State for Wayland event dispatching
 pub(crate) struct State {
     pub(crate) globals: Vec<(u32, String, u32)>, // (name, interface, version)
     /// Color management feature flags (populated by wp_color_manager_v1 events)
     pub(crate) cm_supports_set_luminances: bool,
     pub(crate) cm_supports_set_mastering_primaries: bool,
+    /// Outputs currently advertised by the compositor
+    pub(crate) outputs: Vec<OutputInfo>,
+    /// Outputs our surfaces are shown on (wl_surface enter/leave)
+    pub(crate) entered_outputs: Vec<ObjectId>,
+    /// Scale from wl_surface.preferred_buffer_scale (wl_compositor v6), preferred over
+    /// the output scale when the compositor sends it
+    pub(crate) preferred_buffer_scale: Option<i32>,
+    /// Set when the scale or transform the surfaces are shown at changed and the
+    /// geometry needs applying again
+    pub(crate) geometry_changed: bool,
 }
 
 impl State {
@@ -122,8 +162,31 @@ impl State {
             globals: Vec::new(),
             cm_supports_set_luminances: false,
             cm_supports_set_mastering_primaries: false,
+            outputs: Vec::new(),
+            entered_outputs: Vec::new(),
+            preferred_buffer_scale: None,
+            geometry_changed: false,
         }
     }
+
+    /// Integer buffer scale for our surfaces: the compositor's preferred scale if it sent
+    /// one, otherwise the largest scale among the outputs the surfaces are on.
+    pub(crate) fn buffer_scale(&self) -> i32 {
+        self.preferred_buffer_scale
+            .unwrap_or_else(|| {
+                self.outputs
+                    .iter()
+                    .filter(|o| self.entered_outputs.contains(&o.output.id()))
+                    .map(|o| o.scale)
+                    .max()
+                    .unwrap_or(1)
+            })
+            .max(1)
+    }
+
+    fn is_entered(&self, output: &WlOutput) -> bool {
+        self.entered_outputs.contains(&output.id())
+    }
 }
 
 impl WaylandSubsurfaceManager {
@@ -367,6 +430,7 @@ impl WaylandSubsurfaceManager {
                 _connection: connection,
                 integration: integration.clone(),
                 event_queue: Mutex::new(event_queue),
+                state: Mutex::new(state),
                 compositor,
                 video_subsurface,
                 background_subsurface,
@@ -380,10 +444,13 @@ impl WaylandSubsurfaceManager {
                 position: Arc::new(Mutex::new((0, 0))),
                 size: Arc::new(Mutex::new((0, 0))),
                 source_size: Arc::new(Mutex::new((0, 0, 0, 0))),
+                video_rect: Arc::new(Mutex::new(None)),
+                video_opaque: AtomicBool::new(true),
                 needs_update: Arc::new(AtomicBool::new(false)),
                 shm,
                 background_buffer: Mutex::new(None),
                 background_pool: Mutex::new(None),
+                background_color: Mutex::new([0x00, 0x00, 0x00, 0xFF]),
                 subtitle_buffer: Mutex::new(None),
                 subtitle_pool: Mutex::new(None),
                 subtitle_file: Mutex::new(None),
@@ -419,6 +486,7 @@ impl WaylandSubsurfaceManager {
             let position_weak = Arc::downgrade(&subsurface_manager.position);
             let size_weak = Arc::downgrade(&subsurface_manager.size);
             let source_size_weak = Arc::downgrade(&subsurface_manager.source_size);
+            let video_rect_weak = Arc::downgrade(&subsurface_manager.video_rect);
             let subsurface_clone = subsurface_manager.video_subsurface.clone();
             let video_surface_clone = subsurface_manager.video_surface.clone();
             let viewport_clone = subsurface_manager.video_viewport.clone();
@@ -431,22 +499,25 @@ impl WaylandSubsurfaceManager {
 
             integration.register_pre_commit_hook(move || {
                 // Check weak references and bail early if they're gone
-                let (needs_update, position, size, source_size) = match (
+                let (needs_update, position, size, source_size, video_rect) = match (
                     needs_update_weak.upgrade(),
                     position_weak.upgrade(),
                     size_weak.upgrade(),
                     source_size_weak.upgrade(),
+                    video_rect_weak.upgrade(),
                 ) {
-                    (Some(n), Some(p), Some(s), Some(src)) => (n, p, s, src),
+                    (Some(n), Some(p), Some(s), Some(src), Some(r)) => (n, p, s, src, r),
                     _ => return, // Subsurface has been dropped, nothing to do
                 };
 
                 if needs_update.swap(false, Ordering::Relaxed) {
                     let (x, y) = *position.lock();
                     let (dest_w, dest_h) = *size.lock();
+                    let (video_x, video_y, video_w, video_h) =
+                        video_rect.lock().unwrap_or((0, 0, dest_w, dest_h));
 
                     // Update video subsurface position
-                    subsurface_clone.set_position(x, y);
+                    subsurface_clone.set_position(x + video_x, y + video_y);
 
                     // Update background subsurface position and size
                     background_subsurface_clone.set_position(x, y);
@@ -486,16 +557,21 @@ impl WaylandSubsurfaceManager {
 
                     // Update video viewport (if present); otherwise skip to avoid complications
                     if let Some(ref vp) = viewport_clone {
-                        vp.set_destination(dest_w, dest_h);
-                        log::debug!("Updated dest to {}x{}", dest_w, dest_h);
+                        vp.set_destination(video_w.max(1), video_h.max(1));
+                        log::debug!("Updated dest to {}x{}", video_w, video_h);
                         let (x, y, w, h) = *source_size.lock();
-                        vp.set_source(
-                            f64::from(x.max(1)),
-                            f64::from(y.max(1)),
-                            f64::from(w.max(1)),
-                            f64::from(h.max(1)),
-                        );
-                        video_surface_clone.damage(0, 0, dest_w, dest_h);
+                        if w > 0 && h > 0 {
+                            vp.set_source(
+                                f64::from(x.max(0)),
+                                f64::from(y.max(0)),
+                                f64::from(w),
+                                f64::from(h),
+                            );
+                        } else {
+                            // No crop: -1 everywhere unsets the source rectangle
+                            vp.set_source(-1.0, -1.0, -1.0, -1.0);
+                        }
+                        video_surface_clone.damage(0, 0, video_w, video_h);
                     }
                 }
             });
@@ -504,13 +580,16 @@ impl WaylandSubsurfaceManager {
         }
     }
 
-    /// Attach a rendered ARGB32 subtitle frame to the subtitle surface and commit
+    /// Attach a rendered ARGB32 subtitle frame to the subtitle surface and commit.
+    /// `width` and `height` are in buffer pixels, `scale` times the logical size (see
+    /// [`buffer_scale`](Self::buffer_scale)) so subtitles stay sharp on HiDPI outputs.
     pub fn attach_subtitle_frame(
         &self,
         data: &[u8],
         width: i32,
         height: i32,
         stride: i32,
+        scale: i32,
     ) -> Result<()> {
         if self.shm.is_none() {
             return Err(Error::Wayland("No wl_shm for subtitle".into()));
@@ -530,10 +609,11 @@ impl WaylandSubsurfaceManager {
             )));
         }
         log::debug!(
-            "[subs] attach_subtitle_frame called: {}x{} stride={} ({} bytes)",
+            "[subs] attach_subtitle_frame called: {}x{} stride={} scale={} ({} bytes)",
             width,
             height,
             stride,
+            scale,
             needed
         );
 
@@ -590,8 +670,13 @@ impl WaylandSubsurfaceManager {
 
         if let Some(ref buffer) = &*buf_guard {
             log::debug!("[subs] Attaching buffer to subtitle surface and committing");
+            // The viewport destination already fixes the logical size; the buffer scale
+            // keeps the surface consistent for compositors that look at it
+            if self.subtitle_surface.version() >= 3 {
+                self.subtitle_surface.set_buffer_scale(scale.max(1));
+            }
             self.subtitle_surface.attach(Some(buffer), 0, 0);
-            self.subtitle_surface.damage(0, 0, width, height);
+            self.subtitle_surface.damage_buffer(0, 0, width, height);
             self.subtitle_surface.commit();
         } else {
             log::warn!("[subs] Subtitle surface/buffer missing; cannot attach subtitle frame");
@@ -657,7 +742,7 @@ impl WaylandSubsurfaceManager {
             h,
             stride
         );
-        self.attach_subtitle_frame(&data, w, h, stride)
+        self.attach_subtitle_frame(&data, w, h, stride, 1)
     }
 
     /// Set or clear input passthrough on the subtitle surface.
@@ -689,6 +774,7 @@ impl WaylandSubsurfaceManager {
         log::info!("[subs] WaylandSubsurfaceManager::set_size -> {}x{}", w, h);
         *self.size.lock() = (w, h);
 
+        self.update_opaque_regions();
         self.needs_update.store(true, Ordering::Relaxed);
         self.video_surface.commit();
         self.subtitle_surface.commit();
@@ -701,6 +787,21 @@ impl WaylandSubsurfaceManager {
         self.video_surface.commit();
     }
 
+    /// Place the video at `rect` (x, y, width, height) within the area set by
+    /// [`set_size`](Self::set_size); bars around it show the background.
+    pub fn set_video_rect(&self, rect: (i32, i32, i32, i32)) {
+        *self.video_rect.lock() = Some(rect);
+
+        self.update_opaque_regions();
+        self.needs_update.store(true, Ordering::Relaxed);
+        self.video_surface.commit();
+    }
+
+    /// Get the video placement within the area, if one was set
+    pub fn get_video_rect(&self) -> Option<(i32, i32, i32, i32)> {
+        *self.video_rect.lock()
+    }
+
     /// Get the current position
     pub fn get_position(&self) -> (i32, i32) {
         *self.position.lock()
@@ -760,11 +861,47 @@ impl WaylandSubsurfaceManager {
     }
 
     pub fn set_video_surface_opaque_region(&self, x: i32, y: i32, width: i32, height: i32) {
-        let qh = self.event_queue.lock().handle();
-        let region = self.compositor.create_region(&qh, ());
-        region.add(x, y, width, height);
-        self.video_surface.set_opaque_region(Some(&region));
-        region.destroy()
+        self.set_opaque_region(&self.video_surface, Some((x, y, width, height)));
+    }
+
+    /// Declare whether the video covers its extent with opaque pixels (the default), letting
+    /// the compositor skip drawing whatever is underneath. Turn off for video with alpha.
+    pub fn set_video_opaque(&self, opaque: bool) {
+        self.video_opaque.store(opaque, Ordering::Relaxed);
+        self.update_opaque_regions();
+        self.video_surface.commit();
+    }
+
+    /// Mark the video (unless disabled) and the background (unless translucent) opaque over
+    /// their current extents. Opaque regions are double-buffered, so they apply with the next commit.
+    fn update_opaque_regions(&self) {
+        let (area_w, area_h) = *self.size.lock();
+        let (_, _, video_w, video_h) = self.video_rect.lock().unwrap_or((0, 0, area_w, area_h));
+
+        let video_region = self
+            .video_opaque
+            .load(Ordering::Relaxed)
+            .then_some((0, 0, video_w, video_h));
+        self.set_opaque_region(&self.video_surface, video_region);
+        let background_region = self.background_opaque().then_some((0, 0, area_w, area_h));
+        self.set_opaque_region(&self.background_surface, background_region);
+    }
+
+    fn background_opaque(&self) -> bool {
+        self.background_color.lock()[3] == 0xFF
+    }
+
+    fn set_opaque_region(&self, surface: &WlSurface, rect: Option<(i32, i32, i32, i32)>) {
+        match rect {
+            Some((x, y, width, height)) if width > 0 && height > 0 => {
+                let qh = self.event_queue.lock().handle();
+                let region = self.compositor.create_region(&qh, ());
+                region.add(x, y, width, height);
+                surface.set_opaque_region(Some(&region));
+                region.destroy();
+            }
+            _ => surface.set_opaque_region(None),
+        }
     }
 
     /// Get the surface handle for GStreamer waylandsink
@@ -831,9 +968,18 @@ impl WaylandSubsurfaceManager {
         if is_hdr {
             // Take a single lock on the event queue for both the handle and the roundtrip
             let mut eq = self.event_queue.lock();
+            let mut state = self.state.lock();
             let qh = eq.handle();
-            match cm.tag_video_hdr(colorimetry, metadata, &self.video_surface, &qh, &mut eq) {
+            match cm.tag_video_hdr(
+                colorimetry,
+                metadata,
+                &self.video_surface,
+                &qh,
+                &mut eq,
+                &mut state,
+            ) {
                 Ok(()) => {
+                    drop(state);
                     drop(eq);
                     if let Err(e) = self.flush() {
                         log::warn!("[color-mgmt] Flush after HDR tag failed: {e}");
@@ -851,6 +997,33 @@ impl WaylandSubsurfaceManager {
         }
     }
 
+    /// Dispatch events already read from the shared connection, keeping output scale,
+    /// transform and removed globals up to date. Returns `true` when the scale or
+    /// transform the surfaces are shown at changed, in which case the geometry is
+    /// re-applied on the next parent commit.
+    pub fn dispatch_events(&self) -> bool {
+        let mut event_queue = self.event_queue.lock();
+        let mut state = self.state.lock();
+        if let Err(e) = event_queue.dispatch_pending(&mut *state) {
+            log::warn!("Failed to dispatch subsurface events: {}", e);
+        }
+        if !std::mem::take(&mut state.geometry_changed) {
+            return false;
+        }
+        log::info!(
+            "Subsurface scale or transform changed (buffer scale {})",
+            state.buffer_scale()
+        );
+        self.needs_update.store(true, Ordering::Relaxed);
+        true
+    }
+
+    /// Integer scale of the outputs the video is shown on, 1 until the compositor says
+    /// otherwise
+    pub fn buffer_scale(&self) -> i32 {
+        self.state.lock().buffer_scale()
+    }
+
     /// Flush any pending Wayland events
     pub fn flush(&self) -> Result<()> {
         self.event_queue
@@ -877,7 +1050,78 @@ impl WaylandSubsurfaceManager {
         eprintln!("Forced full damage and commit on video surface");
     }
 
-    /// Create or update the black background buffer
+    /// Unmap the video and subtitle surfaces by attaching null buffers, or remap them with a
+    /// full damage and commit. The next frame from waylandsink attaches a buffer again.
+    pub fn set_visible(&self, visible: bool) {
+        if visible {
+            self.force_damage_and_commit();
+        } else {
+            self.video_surface.attach(None, 0, 0);
+            self.video_surface.commit();
+            self.subtitle_surface.attach(None, 0, 0);
+            self.subtitle_surface.commit();
+        }
+        self.integration.trigger_pre_commit_hooks();
+        if let Err(e) = self.flush() {
+            log::debug!("Failed to flush after visibility change: {}", e);
+        }
+    }
+
+    /// Take the subsurfaces off screen for good while keeping their surfaces alive, since
+    /// waylandsink still renders into the video surface until its pipeline stops. Destroying
+    /// the subsurface roles unmaps everything immediately, including waylandsink's own
+    /// surfaces below the video one. `Drop` destroys the remaining objects.
+    pub fn detach(&self) {
+        // The position hook would otherwise keep moving the destroyed subsurfaces
+        self.integration.clear_pre_commit_hooks();
+
+        self.video_surface.attach(None, 0, 0);
+        self.video_surface.commit();
+        self.background_surface.attach(None, 0, 0);
+        self.background_surface.commit();
+        self.subtitle_surface.attach(None, 0, 0);
+        self.subtitle_surface.commit();
+
+        self.video_subsurface.destroy();
+        self.background_subsurface.destroy();
+        self.subtitle_subsurface.destroy();
+
+        if let Err(e) = self.flush() {
+            log::debug!("Failed to flush after detaching subsurfaces: {}", e);
+        }
+    }
+
+    /// Fill the background, and so the bars around the video, with `rgba` instead of black.
+    /// The buffer is recreated in the new color and committed right away.
+    pub fn set_background_color(&self, rgba: [u8; 4]) {
+        if std::mem::replace(&mut *self.background_color.lock(), rgba) == rgba {
+            return;
+        }
+        let old_buffer = self.background_buffer.lock().take();
+        let old_pool = self.background_pool.lock().take();
+        if let Err(e) = self.ensure_background_buffer() {
+            log::error!("Failed to recreate background buffer: {}", e);
+        }
+        // The new buffer is attached, so the old one can go
+        if let Some(buffer) = old_buffer {
+            buffer.destroy();
+        }
+        if let Some(pool) = old_pool {
+            pool.destroy();
+        }
+        self.update_opaque_regions();
+        self.background_surface.commit();
+        if let Err(e) = self.flush() {
+            log::debug!("Failed to flush after background color change: {}", e);
+        }
+    }
+
+    /// The background color set with [`set_background_color`](Self::set_background_color).
+    pub fn background_color(&self) -> [u8; 4] {
+        *self.background_color.lock()
+    }
+
+    /// Create the background buffer, filled with `background_color`
     fn ensure_background_buffer(&self) -> Result<()> {
         if self.shm.is_none() {
             let msg = "No wl_shm available, cannot create background buffer";
@@ -904,14 +1148,12 @@ impl WaylandSubsurfaceManager {
         file.set_len(size as u64)
             .map_err(|e| Error::Wayland(format!("Failed to resize temp file: {}", e)))?;
 
-        // Black
-        let mut buffer = Vec::with_capacity(size);
-        for _ in 0..(width * height) {
-            buffer.push(0x0); // Blue
-            buffer.push(0x0); // Green
-            buffer.push(0x0); // Red
-            buffer.push(0xFF); // Alpha
-        }
+        // wl_shm Format::Argb8888 on little-endian is stored as BGRA bytes, with the color
+        // premultiplied by alpha
+        let [r, g, b, a] = *self.background_color.lock();
+        let premultiply = |c: u8| (c as u16 * a as u16 / 0xFF) as u8;
+        let pixel = [premultiply(b), premultiply(g), premultiply(r), a];
+        let buffer = pixel.repeat((width * height) as usize);
 
         file.write_all(&buffer)
             .map_err(|e| Error::Wayland(format!("Failed to write buffer: {}", e)))?;
@@ -969,14 +1211,11 @@ impl WaylandSubsurfaceManager {
         self.background_subsurface.set_position(x, y);
         log::debug!("Background positioned at ({}, {})", x, y);
 
-        //let qh = self.event_queue.lock().handle();
-        //let bg_region = self.compositor.create_region(&qh, ());
-        //bg_region.add(x, y, width, height);
-        //self.background_surface.set_opaque_region(Some(&bg_region));
+        let region = self.background_opaque().then_some((0, 0, width, height));
+        self.set_opaque_region(&self.background_surface, region);
 
         self.background_surface.damage(0, 0, width, height);
         self.background_surface.commit();
-        //bg_region.destroy();
         log::debug!("Background surface damaged and committed");
     }
 }
@@ -1054,6 +1293,12 @@ impl Drop for WaylandSubsurfaceManager {
         self.background_surface.destroy();
         self.subtitle_surface.destroy();
 
+        for info in self.state.lock().outputs.drain(..) {
+            if info.output.version() >= 3 {
+                info.output.release();
+            }
+        }
+
         eprintln!("[WaylandVideoSubsurface] Cleanup completed");
     }
 }
@@ -1061,21 +1306,33 @@ impl Drop for WaylandSubsurfaceManager {
 // Event dispatch implementation (minimal, as we don't need to handle many events)
 impl Dispatch<WlSurface, ()> for State {
     fn event(
-        _state: &mut Self,
+        state: &mut Self,
         _surface: &WlSurface,
         event: <WlSurface as Proxy>::Event,
         _data: &(),
         _conn: &Connection,
         _qh: &QueueHandle<Self>,
     ) {
-        // For subsurfaces, you usually don't need to handle these events
-        // since they're secondary surfaces. Let Iced handle the main surface events.
+        // All our surfaces cover the same area, so they share one set of entered outputs
         match event {
-            Event::Enter { .. }
-            | Event::Leave { .. }
-            | Event::PreferredBufferScale { .. }
-            | Event::PreferredBufferTransform { .. } => {
-                // No action needed for subsurfaces in most cases
+            Event::Enter { output } => {
+                if !state.entered_outputs.contains(&output.id()) {
+                    state.entered_outputs.push(output.id());
+                    state.geometry_changed = true;
+                }
+            }
+            Event::Leave { output } => {
+                state.entered_outputs.retain(|id| *id != output.id());
+                state.geometry_changed = true;
+            }
+            Event::PreferredBufferScale { factor } => {
+                if state.preferred_buffer_scale != Some(factor) {
+                    state.preferred_buffer_scale = Some(factor);
+                    state.geometry_changed = true;
+                }
+            }
+            Event::PreferredBufferTransform { .. } => {
+                // Buffers come from waylandsink untransformed; the compositor handles it
             }
             _ => {}
         }
@@ -1137,11 +1394,11 @@ impl Dispatch<WlSubcompositor, ()> for State {
 impl Dispatch<WlRegistry, ()> for State {
     fn event(
         state: &mut Self,
-        _proxy: &WlRegistry,
+        proxy: &WlRegistry,
         event: <WlRegistry as Proxy>::Event,
         _data: &(),
         _conn: &Connection,
-        _qhandle: &QueueHandle<Self>,
+        qhandle: &QueueHandle<Self>,
     ) {
         use wayland_client::protocol::wl_registry::Event;
         match event {
@@ -1150,10 +1407,77 @@ impl Dispatch<WlRegistry, ()> for State {
                 interface,
                 version,
             } => {
+                // Outputs come and go at runtime, so they are bound as they appear
+                if interface == "wl_output" {
+                    let output: WlOutput = proxy.bind(name, version.min(4), qhandle, ());
+                    state.outputs.push(OutputInfo {
+                        name,
+                        output,
+                        scale: 1,
+                        transform: None,
+                    });
+                }
                 state.globals.push((name, interface, version));
             }
-            Event::GlobalRemove { name: _ } => {
-                // We don't handle removal during initialization
+            Event::GlobalRemove { name } => {
+                let Some(index) = state.globals.iter().position(|(n, _, _)| *n == name) else {
+                    return;
+                };
+                let (_, interface, _) = state.globals.remove(index);
+                if let Some(index) = state.outputs.iter().position(|o| o.name == name) {
+                    let removed = state.outputs.remove(index);
+                    log::info!("Output {} removed", name);
+                    if state.is_entered(&removed.output) {
+                        state
+                            .entered_outputs
+                            .retain(|id| *id != removed.output.id());
+                        state.geometry_changed = true;
+                    }
+                    if removed.output.version() >= 3 {
+                        removed.output.release();
+                    }
+                } else if REQUIRED_GLOBALS.contains(&interface.as_str()) {
+                    log::error!(
+                        "Compositor removed {} (global {}); subsurface updates will fail until the video is recreated",
+                        interface,
+                        name
+                    );
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+impl Dispatch<WlOutput, ()> for State {
+    fn event(
+        state: &mut Self,
+        proxy: &WlOutput,
+        event: <WlOutput as Proxy>::Event,
+        _data: &(),
+        _conn: &Connection,
+        _qhandle: &QueueHandle<Self>,
+    ) {
+        let entered = state.is_entered(proxy);
+        let Some(info) = state.outputs.iter_mut().find(|o| o.output == *proxy) else {
+            return;
+        };
+        match event {
+            wl_output::Event::Scale { factor } => {
+                if info.scale != factor {
+                    info.scale = factor;
+                    state.geometry_changed |= entered;
+                }
+            }
+            wl_output::Event::Geometry { transform, .. } => {
+                let transform = match transform {
+                    WEnum::Value(transform) => Some(transform),
+                    WEnum::Unknown(_) => None,
+                };
+                if info.transform != transform {
+                    info.transform = transform;
+                    state.geometry_changed |= entered;
+                }
             }
             _ => {}
         }
@@ -75,14 +75,22 @@ impl TextRenderer {
     /// Render `text` onto a `canvas_w × canvas_h` pre-multiplied ARGB8888
     /// buffer.  White text with a dark shadow, centred horizontally, near
     /// the bottom of the canvas.  Multi-line text (split on `\n`) is
-    /// supported.
+    /// supported.  `buffer_scale` is the output's buffer scale: the canvas is that
+    /// many times the logical size, and the minimum font size and margin
+    /// grow with it.
     ///
     /// Performance: two glyph-rasterisation passes (shadow + foreground),
     /// no per-pixel post-processing.  Typically <1 ms for a couple of
     /// subtitle lines at 1080p.
     ///
     /// Returns `None` if the font failed to parse or text is empty.
-    pub fn render(&self, text: &str, canvas_w: usize, canvas_h: usize) -> Option<Vec<u8>> {
+    pub fn render(
+        &self,
+        text: &str,
+        canvas_w: usize,
+        canvas_h: usize,
+        buffer_scale: f32,
+    ) -> Option<Vec<u8>> {
         let font_data = load_font_data()?;
         let font = FontRef::try_from_slice(font_data).ok()?;
 
@@ -95,7 +103,8 @@ impl TextRenderer {
         }
 
         // Scale: ~4.5% of canvas height, clamped to a sane range.
-        let px = (canvas_h as f32 * 0.045).clamp(18.0, 80.0);
+        let buffer_scale = buffer_scale.max(1.0);
+        let px = (canvas_h as f32 * 0.045).clamp(18.0 * buffer_scale, 80.0 * buffer_scale);
         let scale = PxScale::from(px);
         let scaled = font.as_scaled(scale);
 
@@ -128,7 +137,7 @@ impl TextRenderer {
         }
 
         let total_text_h = lines.len() as f32 * line_height;
-        let margin_bottom = (canvas_h as f32 * 0.06).max(12.0);
+        let margin_bottom = (canvas_h as f32 * 0.06).max(12.0 * buffer_scale);
         let block_top = (canvas_h as f32 - margin_bottom - total_text_h).max(0.0);
 
         let stride = canvas_w * 4;
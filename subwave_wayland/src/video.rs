@@ -11,12 +11,21 @@ use crate::{
 use gstreamer as gst;
 use gstreamer::prelude::*;
 use parking_lot::{Mutex as ParkMutex, RwLock};
+use std::num::NonZeroU8;
 use std::result::Result;
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::{mpsc, Arc};
 use std::time::{Duration, Instant};
+use subwave_core::rtsp::RtspConfig;
 use subwave_core::types::PendingState;
-use subwave_core::video::types::{AudioTrack, Position, SubtitleTrack};
+use subwave_core::video::elements::{adopt_clock, lock_base_time, pipeline_clock, query_seekable};
+use subwave_core::video::streams::{parse_stream_collection, StreamIds, StreamInfo};
+use subwave_core::video::thumbnail::Thumbnail;
+use subwave_core::video::types::{
+    apply_pixel_aspect, clamp_speed, next_video_id, video_log_target, AudioTrack, BufferConfig,
+    BufferingAction, DecoderPreference, FrameStats, PlaybackStatus, Position, SubtitleTrack,
+    TrickMode, VideoTrack,
+};
 use subwave_core::video_trait::Video;
 
 // Video is an exterior-facing newtype with a single interior RwLock
@@ -31,20 +40,41 @@ impl Video for SubsurfaceVideo {
 
     fn new(uri: &url::Url) -> Result<Self::Video, subwave_core::Error> {
         // Creating the video object itself can't fail here
+        let id = next_video_id();
         Ok(SubsurfaceVideo(RwLock::new(Internal {
+            id,
+            log_target: video_log_target(id),
             uri: uri.clone(),
             pipeline: None,
             subsurface: None,
+            wayland_init: None,
+            detached_subsurface: None,
             duration: None,
+            duration_unreported: false,
+            streams_unreported: false,
             speed: 1.0,
+            volume: 1.0,
+            muted: false,
+            trick_mode: TrickMode::default(),
             looping: false,
             is_eos: false,
+            eos_unreported: false,
             restart_stream: false,
+            has_error: false,
+            last_error: None,
+            error_unreported: false,
+            seekable: true,
+            hidden: false,
+            video_opaque: true,
+            background_color: [0x00, 0x00, 0x00, 0xFF],
+            error_count: 0,
+            is_reconnecting: false,
             bus_thread: None,
             bus_stop: Arc::new(AtomicBool::new(false)),
             cmd_rx: None,
             startup_async_done: false,
             stream_collection: None,
+            last_framerate: ParkMutex::new(None),
             available_subtitles: Vec::new(),
             current_subtitle_track: None,
             subtitles_enabled: false,
@@ -54,16 +84,23 @@ impl Video for SubsurfaceVideo {
             subtitle_scheduler: None,
             available_audio_tracks: Vec::new(),
             current_audio_track: -1,
+            available_video_tracks: Vec::new(),
+            current_video_track: -1,
             audio_index_to_stream_id: Vec::new(),
+            video_index_to_stream_id: Vec::new(),
             subtitle_index_to_stream_id: Vec::new(),
             selected_stream_ids: Vec::new(),
             is_buffering: false,
             buffering_percent: 100,
-            user_paused: false,
+            user_paused: Arc::new(AtomicBool::new(false)),
             pending_state: None,
             pending_http_headers: None,
+            buffer_config: BufferConfig::default(),
+            rtsp_config: RtspConfig::default(),
+            decoder_preference: None,
             pending_play_after_seek: false,
             pending_start_position: None,
+            clock: None,
             last_position_update: Instant::now(),
         })))
     }
@@ -72,55 +109,65 @@ impl Video for SubsurfaceVideo {
         self.resolution().unwrap_or((0, 0))
     }
 
+    fn natural_size(&self) -> (i32, i32) {
+        self.size()
+    }
+
+    /// waylandsink scales by the pixel aspect ratio itself, so this is only for layout.
+    fn display_size(&self) -> (i32, i32) {
+        apply_pixel_aspect(self.size(), self.pixel_aspect_ratio().unwrap_or((1, 1)))
+    }
+
+    /// Framerate from the negotiated sink caps, else the video stream's caps in the stream
+    /// collection, else the last value found. 0.0 only until a framerate is first known.
     fn framerate(&self) -> f64 {
-        // Query from current caps if available
-        if let Some(p) = self.0.read().pipeline.as_ref() {
-            if let Some(pad) = p
-                .pipeline
+        let state = self.0.read();
+        let sink_caps = state.pipeline.as_ref().and_then(|p| {
+            p.pipeline
                 .by_name("vsink")
                 .and_then(|s| s.static_pad("sink"))
-            {
-                if let Some(caps) = pad.current_caps() {
-                    if let Some(s) = caps.structure(0) {
-                        if let Ok(fr) = s.get::<gst::Fraction>("framerate") {
-                            return fr.numer() as f64 / fr.denom() as f64;
-                        }
-                    }
-                }
-            }
+                .and_then(|pad| pad.current_caps())
+        });
+        let collection_caps = || {
+            state.stream_collection.as_ref().and_then(|collection| {
+                collection
+                    .iter()
+                    .find(|stream| stream.stream_type().contains(gst::StreamType::VIDEO))
+                    .and_then(|stream| stream.caps())
+            })
+        };
+        let found = sink_caps
+            .as_ref()
+            .and_then(|caps| framerate_from_caps(caps))
+            .or_else(|| collection_caps().and_then(|caps| framerate_from_caps(&caps)));
+
+        let mut last = state.last_framerate.lock();
+        if found.is_some() {
+            *last = found;
         }
-        0.0
+        last.unwrap_or(0.0)
     }
 
     fn volume(&self) -> f64 {
-        self.0
-            .read()
-            .pipeline
-            .as_ref()
-            .map(|p| p.pipeline.property::<f64>("volume"))
-            .unwrap_or(0.0)
+        self.0.read().volume
     }
 
     fn set_volume(&mut self, volume: f64) {
-        if let Some(p) = self.0.read().pipeline.as_ref() {
-            p.pipeline.set_property("volume", volume);
-        }
-        // Preserve mute state
-        self.set_muted(self.muted());
+        let _ = SubsurfaceVideo::set_volume(self, volume);
     }
 
     fn muted(&self) -> bool {
-        self.0
-            .read()
-            .pipeline
-            .as_ref()
-            .map(|p| p.pipeline.property::<bool>("mute"))
-            .unwrap_or(false)
+        self.0.read().muted
     }
 
     fn set_muted(&mut self, muted: bool) {
-        if let Some(p) = self.0.read().pipeline.as_ref() {
-            p.pipeline.set_property("mute", muted);
+        let pipeline = {
+            let mut w = self.0.write();
+            w.muted = muted;
+            w.pipeline.clone()
+        };
+        if let Some(p) = pipeline {
+            p.set_muted(muted);
         }
     }
 
@@ -146,6 +193,10 @@ impl Video for SubsurfaceVideo {
             let mut w = self.0.write();
             invalidate_subtitle_state(&mut w);
             w.is_eos = false;
+            w.eos_unreported = false;
+            w.has_error = false;
+            w.last_error = None;
+            w.error_unreported = false;
             w.restart_stream = false;
             Ok(())
         } else {
@@ -164,15 +215,40 @@ impl Video for SubsurfaceVideo {
             .unwrap_or(true)
     }
 
+    fn status(&self) -> PlaybackStatus {
+        let state = self.0.read();
+        if state.has_error {
+            PlaybackStatus::Error
+        } else if state.is_eos {
+            PlaybackStatus::Ended
+        } else if state.is_buffering {
+            PlaybackStatus::Buffering(state.buffering_percent)
+        } else {
+            state
+                .pipeline
+                .as_ref()
+                .map(|p| PlaybackStatus::from_gst_state(p.pipeline.current_state()))
+                .unwrap_or(PlaybackStatus::Idle)
+        }
+    }
+
     fn set_paused(&mut self, paused: bool) {
-        let pipeline = {
-            let mut state = self.0.write();
-            state.user_paused = paused;
-            state.pipeline.clone()
+        let (pipeline, buffering) = {
+            let state = self.0.read();
+            state.user_paused.store(paused, Ordering::SeqCst);
+            (state.pipeline.clone(), state.is_buffering)
         };
 
         if let Some(p) = pipeline {
-            let _ = if paused { p.pause() } else { p.play() };
+            // While buffering the pipeline stays paused; the buffering handler resumes it
+            // once the fill level is back to 100%
+            let _ = if paused {
+                p.pause()
+            } else if buffering {
+                Ok(())
+            } else {
+                p.play()
+            };
         }
     }
 
@@ -183,9 +259,14 @@ impl Video for SubsurfaceVideo {
     fn set_speed(&mut self, speed: f64) -> Result<(), subwave_core::Error> {
         // Update and apply via a flushing seek-rate request. The resulting GStreamer flush events
         // invalidate subtitle state so queued cues are rebuilt for the new playback segment.
+        let speed = clamp_speed(speed)?;
         self.0.write().speed = speed;
-        if let Some(p) = self.0.read().pipeline.clone() {
-            p.set_playback_rate(speed)
+        let (pipeline, trick_mode) = {
+            let r = self.0.read();
+            (r.pipeline.clone(), r.trick_mode)
+        };
+        if let Some(p) = pipeline {
+            p.set_playback_rate(speed, trick_mode)
                 .map_err(|_| subwave_core::Error::InvalidState)
         } else {
             Ok(())
@@ -193,8 +274,13 @@ impl Video for SubsurfaceVideo {
     }
 
     fn position(&self) -> Duration {
-        self.0
-            .read()
+        let inner = self.0.read();
+        // Until queued state is applied the pipeline has not reached it yet; report the
+        // resume position rather than wherever preroll happens to be
+        if let Some(st) = inner.pending_state.as_ref() {
+            return st.position;
+        }
+        inner
             .pipeline
             .as_ref()
             .and_then(|p| p.pipeline.query_position::<gst::ClockTime>())
@@ -207,6 +293,10 @@ impl Video for SubsurfaceVideo {
         position: impl Into<Position>,
         accurate: bool,
     ) -> Result<(), subwave_core::Error> {
+        if !self.0.read().seekable {
+            log::warn!(target: &self.0.read().log_target, "Cannot seek: stream is not seekable");
+            return Err(subwave_core::Error::InvalidState);
+        }
         if let Some(p) = self.0.read().pipeline.clone() {
             p.seek(position, accurate)
                 .map_err(|_| subwave_core::Error::InvalidState)
@@ -305,12 +395,71 @@ impl Video for SubsurfaceVideo {
             .map_err(|_| subwave_core::Error::InvalidState)
     }
 
+    fn frame_stats(&self) -> FrameStats {
+        // waylandsink is a GstBaseSink, whose `stats` structure counts rendered/dropped buffers
+        let stats = self.0.read().pipeline.as_ref().and_then(|p| {
+            p.pipeline
+                .by_name("vsink")
+                .map(|sink| sink.property::<gst::Structure>("stats"))
+        });
+        stats
+            .map(|s| FrameStats {
+                rendered: s.get::<u64>("rendered").unwrap_or(0),
+                dropped: s.get::<u64>("dropped").unwrap_or(0),
+            })
+            .unwrap_or_default()
+    }
+
+    fn is_live(&self) -> bool {
+        self.0
+            .read()
+            .pipeline
+            .as_ref()
+            .map(|p| p.is_live())
+            .unwrap_or(false)
+    }
+
+    fn seekable(&self) -> bool {
+        self.0.read().seekable
+    }
+
+    fn video_tracks(&self) -> Vec<VideoTrack> {
+        self.video_tracks_info()
+    }
+
+    fn current_video_track(&self) -> i32 {
+        SubsurfaceVideo::current_video_track(self)
+    }
+
+    fn select_video_track(&mut self, track_index: i32) -> Result<(), subwave_core::Error> {
+        SubsurfaceVideo::select_video_track(self, track_index)
+            .map_err(|_| subwave_core::Error::InvalidState)
+    }
+
     fn has_video(&self) -> bool {
         self.resolution()
             .map(|(w, h)| w > 0 && h > 0)
             .unwrap_or(false)
     }
 
+    fn has_audio(&self) -> bool {
+        !self.0.read().available_audio_tracks.is_empty()
+    }
+
+    fn has_subtitles(&self) -> bool {
+        !self.0.read().available_subtitles.is_empty()
+    }
+
+    fn generate_thumbnails(
+        &mut self,
+        positions: &[Position],
+        downscale: NonZeroU8,
+    ) -> Result<Vec<Thumbnail>, subwave_core::Error> {
+        // waylandsink gives no pixel access, so decode from a separate pipeline
+        let uri = self.0.read().uri.clone();
+        subwave_core::video::thumbnail::generate_thumbnails(&uri, positions, downscale)
+    }
+
     fn pipeline(&self) -> gst::Pipeline {
         self.0
             .read()
@@ -323,20 +472,41 @@ impl Video for SubsurfaceVideo {
 
 impl SubsurfaceVideo {
     pub fn new(uri: &url::Url) -> Result<Self, Error> {
+        let id = next_video_id();
         let inner = Internal {
+            id,
+            log_target: video_log_target(id),
             uri: uri.clone(),
             pipeline: None,
             subsurface: None,
+            wayland_init: None,
+            detached_subsurface: None,
             duration: None,
+            duration_unreported: false,
+            streams_unreported: false,
             speed: 1.0,
+            volume: 1.0,
+            muted: false,
+            trick_mode: TrickMode::default(),
             looping: false,
             is_eos: false,
+            eos_unreported: false,
             restart_stream: false,
+            has_error: false,
+            last_error: None,
+            error_unreported: false,
+            seekable: true,
+            hidden: false,
+            video_opaque: true,
+            background_color: [0x00, 0x00, 0x00, 0xFF],
+            error_count: 0,
+            is_reconnecting: false,
             bus_thread: None,
             bus_stop: Arc::new(AtomicBool::new(false)),
             cmd_rx: None,
             startup_async_done: false,
             stream_collection: None,
+            last_framerate: ParkMutex::new(None),
             // Subtitle tracking
             available_subtitles: Vec::new(),
             current_subtitle_track: None,
@@ -348,22 +518,64 @@ impl SubsurfaceVideo {
             // Audio track tracking
             available_audio_tracks: Vec::new(),
             current_audio_track: -1,
+            // Video track tracking
+            available_video_tracks: Vec::new(),
+            current_video_track: -1,
             // Indices
             audio_index_to_stream_id: Vec::new(),
+            video_index_to_stream_id: Vec::new(),
             subtitle_index_to_stream_id: Vec::new(),
             selected_stream_ids: Vec::new(),
             is_buffering: false,
             buffering_percent: 100,
-            user_paused: false,
+            user_paused: Arc::new(AtomicBool::new(false)),
             pending_state: None,
             pending_http_headers: None,
+            buffer_config: BufferConfig::default(),
+            rtsp_config: RtspConfig::default(),
+            decoder_preference: None,
             pending_play_after_seek: false,
             pending_start_position: None,
+            clock: None,
             last_position_update: Instant::now(),
         };
         Ok(SubsurfaceVideo(RwLock::new(inner)))
     }
 
+    /// Create a new video with custom network buffering.
+    ///
+    /// Unset fields of `buffering` keep the defaults (6s `buffer-duration`, 512MB
+    /// `ring-buffer-max-size`). The config is applied when the pipeline is built in
+    /// [`init_wayland`](Self::init_wayland); see
+    /// [`set_ring_buffer_max_size`](Self::set_ring_buffer_max_size) to change it later.
+    pub fn new_with_buffering(uri: &url::Url, buffering: BufferConfig) -> Result<Self, Error> {
+        let video = Self::new(uri)?;
+        video.0.write().buffer_config = buffering;
+        Ok(video)
+    }
+
+    /// Create a new video from an `rtsp://` URI with custom `rtspsrc` latency and transports.
+    /// The config is applied when the pipeline is built in [`init_wayland`](Self::init_wayland).
+    pub fn new_with_rtsp(uri: &url::Url, rtsp: RtspConfig) -> Result<Self, Error> {
+        let video = Self::new(uri)?;
+        video.0.write().rtsp_config = rtsp;
+        Ok(video)
+    }
+
+    /// Create a new video whose pipeline, once built in [`init_wayland`](Self::init_wayland),
+    /// picks video decoders per `decoder`.
+    ///
+    /// `vapostproc` is still used for output when present. Other pipelines in the process
+    /// are not affected, see [`DecoderPreference`].
+    pub fn new_with_decoder_preference(
+        uri: &url::Url,
+        decoder: DecoderPreference,
+    ) -> Result<Self, Error> {
+        let video = Self::new(uri)?;
+        video.0.write().decoder_preference = Some(decoder);
+        Ok(video)
+    }
+
     /// Set HTTP headers for HTTP-based sources via GStreamer "http-headers" context.
     /// If the pipeline is not yet initialized, headers are stored and applied during init.
     pub fn set_http_headers(&mut self, headers: &[(impl AsRef<str>, impl AsRef<str>)]) {
@@ -393,12 +605,34 @@ impl SubsurfaceVideo {
         integration: WaylandIntegration,
         bounds: (i32, i32, i32, i32),
     ) -> Result<(), Error> {
-        // Construct subsurface and pipeline (no lock held during external calls)
         let subsurface = WaylandSubsurfaceManager::new(integration.clone())?;
+        // After detach_surface the pipeline kept playing, but waylandsink cannot move to
+        // another surface, so it is rebuilt onto the new one
+        if self.0.read().pipeline.is_some() {
+            return self.rebuild_pipeline(subsurface, integration, bounds);
+        }
+        self.start_pipeline(subsurface, integration, bounds)
+    }
+
+    // Build the playback pipeline onto `subsurface` and spawn its bus thread. Used by
+    // init_wayland with a fresh subsurface and by reload with the existing one.
+    fn start_pipeline(
+        &self,
+        subsurface: Arc<WaylandSubsurfaceManager>,
+        integration: WaylandIntegration,
+        bounds: (i32, i32, i32, i32),
+    ) -> Result<(), Error> {
+        // Construct the pipeline (no lock held during external calls)
         let compositor_has_cm = subsurface.has_color_management();
-        let (uri, active_subtitle_selection) = {
+        let (uri, active_subtitle_selection, buffer_config, rtsp_config, decoder_preference) = {
             let state = self.0.read();
-            (state.uri.clone(), state.active_subtitle_selection.clone())
+            (
+                state.uri.clone(),
+                state.active_subtitle_selection.clone(),
+                state.buffer_config,
+                state.rtsp_config,
+                state.decoder_preference,
+            )
         };
         let (subtitle_tx, subtitle_rx) = mpsc::channel::<SubtitleProbeEvent>();
         let pipeline = Arc::new(SubsurfacePipeline::new(
@@ -409,12 +643,21 @@ impl SubsurfaceVideo {
             compositor_has_cm,
             &active_subtitle_selection,
             subtitle_tx,
+            &buffer_config,
+            &rtsp_config,
+            &log_target,
         )?);
 
         // Apply any pending HTTP headers context before starting message processing
         if let Some(h) = self.0.read().pending_http_headers.clone() {
             subwave_core::http::set_http_headers_on_pipeline(&pipeline.pipeline, h.as_slice());
         }
+        if let Some(clock) = self.0.read().clock.clone() {
+            pipeline.pipeline.use_clock(Some(&clock));
+        }
+        if let Some(decoder) = decoder_preference {
+            decoder.apply_to_playbin(pipeline.pipeline.upcast_ref());
+        }
 
         // Create command channel for bus -> UI updates
         let (tx, rx) = mpsc::channel::<Cmd>();
@@ -423,6 +666,8 @@ impl SubsurfaceVideo {
         let stop = self.0.read().bus_stop.clone();
         if let Some(bus) = pipeline.bus() {
             let gst_pipeline = pipeline.pipeline.clone();
+            let log_target = self.0.read().log_target.clone();
+            let uri = uri.clone();
             let handle = std::thread::Builder::new()
                 .name(format!("gst-bus-{}", self.0.read().uri))
                 .spawn(move || {
@@ -439,7 +684,30 @@ impl SubsurfaceVideo {
                         false
                     }
 
+                    // Reconnection state; the position is sampled every iteration so it is
+                    // still known once an error has torn the stream down
+                    let mut last_position = Duration::ZERO;
+                    let mut error_count = 0u32;
+                    let mut last_error_time: Option<Instant> = None;
+
                     while !stop.load(Ordering::SeqCst) {
+                        if let Some(pos) = gst_pipeline.query_position::<gst::ClockTime>() {
+                            if pos > gst::ClockTime::ZERO {
+                                last_position = Duration::from_nanos(pos.nseconds());
+                            }
+                        }
+                        if error_count > 0
+                            && last_error_time.is_some_and(|t| t.elapsed() >= ERROR_RESET_AFTER)
+                        {
+                            log::debug!(
+                                target: &log_target,
+                                "[reconnect] Playback stable; resetting error count"
+                            );
+                            error_count = 0;
+                            last_error_time = None;
+                            let _ = tx.send(Box::new(|s: &mut Internal| s.error_count = 0));
+                        }
+
                         if let Some(msg) = bus.timed_pop(gst::ClockTime::from_mseconds(250)) {
                             match msg.view() {
                                 MessageView::Eos(_) => {
@@ -449,157 +717,175 @@ impl SubsurfaceVideo {
                                         invalidate_subtitle_state(s);
                                         if s.looping {
                                             s.restart_stream = true;
+                                        } else {
+                                            s.eos_unreported = true;
                                         }
                                     }));
                                 }
                                 MessageView::Error(err) => {
-                                    log::error!("Pipeline error: {:?}", err);
-                                    // Keep the bus thread alive to allow recovery strategies if needed
+                                    log::error!(target: &log_target, "Pipeline error: {:?}", err);
+                                    let error = err.error();
+                                    let network_source =
+                                        subwave_core::is_network_source(err.src(), Some(&uri));
+                                    if !subwave_core::is_network_error(&error, network_source)
+                                        || error_count >= MAX_RECONNECT_ATTEMPTS
+                                    {
+                                        if error_count >= MAX_RECONNECT_ATTEMPTS {
+                                            log::error!(
+                                                target: &log_target,
+                                                "[reconnect] Max retry attempts reached, giving up"
+                                            );
+                                        }
+                                        let _ = tx.send(Box::new(move |s: &mut Internal| {
+                                            s.has_error = true;
+                                            s.last_error = Some(error);
+                                            s.error_unreported = true;
+                                            s.is_reconnecting = false;
+                                        }));
+                                        continue;
+                                    }
+
+                                    error_count += 1;
+                                    last_error_time = Some(Instant::now());
+                                    let attempt = error_count;
+                                    let _ = tx.send(Box::new(move |s: &mut Internal| {
+                                        s.is_reconnecting = true;
+                                        s.error_count = attempt;
+                                    }));
+
+                                    // Exponential backoff: 1s, 2s, 4s, ...
+                                    let backoff = Duration::from_secs(1 << (attempt - 1));
+                                    log::info!(
+                                        target: &log_target,
+                                        "[reconnect] Attempt #{attempt} in {backoff:?}, resuming at {last_position:?}"
+                                    );
+                                    if let Err(err) = gst_pipeline.set_state(gst::State::Ready) {
+                                        log::warn!(
+                                            target: &log_target,
+                                            "[reconnect] Failed to reset pipeline: {err}"
+                                        );
+                                    }
+                                    if !sleep_unless_stopped(&stop, backoff) {
+                                        break;
+                                    }
+                                    // A failed attempt posts another error, which schedules the next one
+                                    match reconnect_pipeline(&gst_pipeline, last_position) {
+                                        Ok(()) => {
+                                            let _ = tx.send(Box::new(|s: &mut Internal| {
+                                                s.is_reconnecting = false;
+                                                if !s.user_paused.load(Ordering::SeqCst) {
+                                                    if let Some(p) = s.pipeline.clone() {
+                                                        if let Err(err) = p.play() {
+                                                            log::warn!(
+                                                                target: &s.log_target,
+                                                                "[reconnect] Failed to resume playback: {err:?}"
+                                                            );
+                                                        }
+                                                    }
+                                                }
+                                            }));
+                                            log::info!(
+                                                target: &log_target,
+                                                "[reconnect] Attempt #{attempt} succeeded"
+                                            );
+                                        }
+                                        Err(err) => {
+                                            log::warn!(
+                                                target: &log_target,
+                                                "[reconnect] Attempt #{attempt} failed: {err}"
+                                            );
+                                        }
+                                    }
                                 }
                                 MessageView::DurationChanged(_) => {
                                     let dur = gst_pipeline
                                         .query_duration::<gst::ClockTime>()
                                         .map(|d| Duration::from_nanos(d.nseconds()));
-                                    if tx.send(Box::new(move |s: &mut Internal| s.duration = dur)).is_err() {
-                                        log::debug!("[bus] receiver dropped; exiting bus thread");
+                                    if tx
+                                        .send(Box::new(move |s: &mut Internal| {
+                                            s.duration_unreported |= dur.is_some() && s.duration != dur;
+                                            s.duration = dur;
+                                        }))
+                                        .is_err()
+                                    {
+                                        log::debug!(
+                                            target: &log_target,
+                                            "[bus] receiver dropped; exiting bus thread"
+                                        );
                                         break;
                                     }
                                 }
                                 MessageView::Buffering(buffering) => {
                                     let percent = buffering.percent();
-                                    log::debug!("[buffering] {}%", percent);
+                                    log::debug!(target: &log_target, "[buffering] {}%", percent);
                                     let tx_buffer = tx.clone();
                                     if tx_buffer
                                         .send(Box::new(move |state: &mut Internal| {
                                             let was_buffering = state.is_buffering;
-                                            let buffering_now = percent < 100;
-                                            state.is_buffering = buffering_now;
+                                            state.is_buffering = percent < 100;
                                             state.buffering_percent = percent;
 
-                                            if let Some(pipeline) = state.pipeline.clone() {
-                                                if buffering_now && !was_buffering && !state.user_paused {
-                                                    if let Err(err) = pipeline.pause() {
-                                                        log::warn!(
-                                                            "Failed to pause pipeline during buffering: {err:?}"
-                                                        );
-                                                    }
-                                                } else if !buffering_now
-                                                    && was_buffering
-                                                    && !state.user_paused
-                                                {
-                                                    if let Err(err) = pipeline.play() {
-                                                        log::warn!(
-                                                            "Failed to resume pipeline after buffering: {err:?}"
-                                                        );
-                                                    }
+                                            // Read the pause intent now rather than when the
+                                            // message arrived, so a pause issued in between wins
+                                            let action = BufferingAction::from_percent(
+                                                was_buffering,
+                                                percent,
+                                                state.user_paused.load(Ordering::SeqCst),
+                                            );
+                                            if let (Some(action), Some(pipeline)) =
+                                                (action, state.pipeline.clone())
+                                            {
+                                                let result = match action {
+                                                    BufferingAction::Pause => pipeline.pause(),
+                                                    BufferingAction::Resume => pipeline.play(),
+                                                };
+                                                if let Err(err) = result {
+                                                    log::warn!(
+                                                        target: &state.log_target,
+                                                        "Failed to {action:?} pipeline for buffering: {err:?}"
+                                                    );
                                                 }
                                             }
                                         }))
                                         .is_err()
                                     {
-                                        log::debug!("[bus] receiver dropped; exiting bus thread");
+                                        log::debug!(
+                                            target: &log_target,
+                                            "[bus] receiver dropped; exiting bus thread"
+                                        );
                                         break;
                                     }
                                 }
                                 MessageView::StreamCollection(msg) => {
                                     let collection = msg.stream_collection();
                                     let n = collection.len();
-                                    log::info!("[streams] StreamCollection received: {} streams", n);
-
-                                    // Track lists and id mappings
-                                    let mut audio_tracks: Vec<AudioTrack> = Vec::new();
-                                    let mut subtitle_tracks: Vec<SubtitleTrack> = Vec::new();
-                                    let mut audio_ids: Vec<String> = Vec::new();
-                                    let mut subtitle_ids: Vec<String> = Vec::new();
-                                    let mut first_video_id: Option<String> = None;
-                                    let mut pgs_ids: Vec<String> = Vec::new();
-
-                                    for i in 0..n {
-                                        if let Some(stream) = collection.stream(i as u32) {
-                                            let stype = stream.stream_type();
-                                            let sid = stream
-                                                .stream_id()
-                                                .unwrap_or_else(|| "<no-id>".into());
-                                            let caps = stream.caps();
-
-                                            if stype.contains(gst::StreamType::VIDEO) {
-                                                if first_video_id.is_none() {
-                                                    first_video_id = Some(sid.to_string());
-                                                }
-                                            } else if stype.contains(gst::StreamType::AUDIO) {
-                                                // Extract audio info
-                                                let mut language: Option<String> = None;
-                                                let mut title: Option<String> = None;
-                                                let mut codec: Option<String> = None;
-                                                let mut channels: Option<i32> = None;
-                                                let mut sample_rate: Option<i32> = None;
-
-                                                if let Some(tags) = stream.tags() {
-                                                    if let Some(v) = tags.get::<gst::tags::LanguageCode>() {
-                                                        language = Some(v.get().to_string());
-                                                    } else if let Some(v) = tags.get::<gst::tags::LanguageName>() {
-                                                        language = Some(v.get().to_string());
-                                                    }
-                                                    if let Some(v) = tags.get::<gst::tags::Title>() {
-                                                        title = Some(v.get().to_string());
-                                                    }
-                                                    if let Some(v) = tags.get::<gst::tags::Codec>() {
-                                                        codec = Some(v.get().to_string());
-                                                    }
-                                                }
-                                                if let Some(c) = caps.as_ref().and_then(|c| c.structure(0)) {
-                                                    if let Ok(ch) = c.get::<i32>("channels") { channels = Some(ch); }
-                                                    if let Ok(sr) = c.get::<i32>("rate") { sample_rate = Some(sr); }
-                                                    if codec.is_none() { codec = Some(c.name().to_string()); }
-                                                }
-
-                                                let idx = audio_tracks.len() as i32;
-                                                audio_tracks.push(AudioTrack { index: idx, language, title, codec, channels, sample_rate });
-                                                audio_ids.push(sid.to_string());
-                                            } else if stype.contains(gst::StreamType::TEXT) {
-                                                // Extract subtitle info
-                                                let mut language: Option<String> = None;
-                                                let mut title: Option<String> = None;
-                                                let mut codec: Option<String> = None;
-                                                if let Some(tags) = stream.tags() {
-                                                    if let Some(v) = tags.get::<gst::tags::LanguageCode>() {
-                                                        language = Some(v.get().to_string());
-                                                    } else if let Some(v) = tags.get::<gst::tags::LanguageName>() {
-                                                        language = Some(v.get().to_string());
-                                                    }
-                                                    if let Some(v) = tags.get::<gst::tags::Title>() {
-                                                        title = Some(v.get().to_string());
-                                                    }
-                                                    if let Some(v) = tags.get::<gst::tags::Codec>() {
-                                                        codec = Some(v.get().to_string());
-                                                    }
-                                                }
-                                                let mut is_text_subtitle = false;
-                                                let mut is_bitmap_subtitle = false;
-                                                if let Some(c) = caps.as_ref().and_then(|c| c.structure(0)) {
-                                                    if codec.is_none() { codec = Some(c.name().to_string()); }
-                                                    let caps_name = c.name().as_str();
-                                                    is_text_subtitle = caps_name.starts_with("text/");
-                                                    is_bitmap_subtitle = caps_name == "subpicture/x-pgs"
-                                                        || caps_name == "subpicture/x-dvd";
-                                                }
-
-                                                if is_bitmap_subtitle {
-                                                    pgs_ids.push(sid.to_string());
-                                                }
+                                    log::info!(
+                                        target: &log_target,
+                                        "[streams] StreamCollection received: {} streams",
+                                        n
+                                    );
 
-                                                if is_text_subtitle || is_bitmap_subtitle {
-                                                    let idx = subtitle_tracks.len() as i32;
-                                                    subtitle_tracks.push(SubtitleTrack { index: idx, language, title, codec });
-                                                    subtitle_ids.push(sid.to_string());
-                                                } else {
-                                                    log::info!(
-                                                        "[streams] Skipping unsupported subtitle format {sid}: codec={codec:?}"
-                                                    );
-                                                }
-                                            }
-                                        }
+                                    let StreamInfo {
+                                        audio: audio_tracks,
+                                        subtitle: subtitle_tracks,
+                                        video: video_tracks,
+                                        ids,
+                                        skipped_subtitle,
+                                    } = parse_stream_collection(&collection);
+                                    for (sid, codec) in &skipped_subtitle {
+                                        log::info!(
+                                            target: &log_target,
+                                            "[streams] Skipping unsupported subtitle format {sid}: codec={codec:?}"
+                                        );
                                     }
+                                    let StreamIds {
+                                        audio: audio_ids,
+                                        subtitle: subtitle_ids,
+                                        video: video_ids,
+                                        bitmap_subtitle: pgs_ids,
+                                        ..
+                                    } = ids;
+                                    let first_video_id = video_ids.first().cloned();
 
                                     // Track current playbin selection without forcing extra
                                     // reconfigure churn. Keep this aligned with feat/subs: use
@@ -646,11 +932,15 @@ impl SubsurfaceVideo {
                                     if !selected_ids.is_empty() {
                                         if send_select_streams_preferring_pipeline(&gst_pipeline, &selected_ids) {
                                             log::info!(
+                                                target: &log_target,
                                                 "[streams] Sent SelectStreams with {} ids",
                                                 selected_ids.len()
                                             );
                                         } else {
-                                            log::warn!("[streams] Failed to send SelectStreams event");
+                                            log::warn!(
+                                                target: &log_target,
+                                                "[streams] Failed to send SelectStreams event"
+                                            );
                                         }
                                     }
 
@@ -658,18 +948,23 @@ impl SubsurfaceVideo {
                                     let coll_clone = collection.clone();
                                     let tx_tracks = tx.clone();
                                     let ids_for_state = selected_ids.clone();
+                                    let current_video_index = if video_ids.is_empty() { -1 } else { 0 };
                                     if tx_tracks
                                         .send(Box::new(move |s: &mut Internal| {
                                             s.stream_collection = Some(coll_clone);
                                             s.available_audio_tracks = audio_tracks;
+                                            s.available_video_tracks = video_tracks;
                                             s.available_subtitles = subtitle_tracks;
                                             s.audio_index_to_stream_id = audio_ids;
+                                            s.video_index_to_stream_id = video_ids;
                                             s.subtitle_index_to_stream_id = subtitle_ids;
                                             s.pgs_stream_ids = pgs_ids;
                                             s.selected_stream_ids = ids_for_state;
                                             s.current_audio_track = current_audio_index;
+                                            s.current_video_track = current_video_index;
                                             s.current_subtitle_track = current_sub_index;
                                             s.subtitles_enabled = subtitles_enabled;
+                                            s.streams_unreported = true;
                                             s.subtitle_scheduler = None;
                                             s.active_subtitle_selection.lock().set_stream(None);
                                             if let Some(subsurface) = s.subsurface.as_ref() {
@@ -678,24 +973,79 @@ impl SubsurfaceVideo {
                                         }))
                                         .is_err()
                                     {
-                                        log::debug!("[bus] receiver dropped; exiting bus thread");
+                                        log::debug!(
+                                            target: &log_target,
+                                            "[bus] receiver dropped; exiting bus thread"
+                                        );
                                         break;
                                     }
 
                                 }
                                 MessageView::StreamsSelected(sel) => {
-                                    let collection = sel.stream_collection();
-                                    let mut _n_audio = 0;
-                                    let mut _n_subtitle = 0;
-                                for i in 0..collection.len() {
-                                    if let Some(stream) = collection.stream(i as u32) {
-                                        let st = stream.stream_type();
-                                        if st.contains(gst::StreamType::AUDIO) { _n_audio += 1; }
-                                        if st.contains(gst::StreamType::TEXT) { _n_subtitle += 1; }
+                                    // Reflect what playbin3 actually chose, which can differ
+                                    // from the last SelectStreams after an automatic change
+                                    let selected: Vec<String> = sel
+                                        .streams()
+                                        .into_iter()
+                                        .filter_map(|stream| stream.stream_id().map(|id| id.to_string()))
+                                        .collect();
+                                    log::debug!(
+                                        target: &log_target,
+                                        "[streams] StreamsSelected: {:?}",
+                                        selected
+                                    );
+                                    if tx
+                                        .send(Box::new(move |s: &mut Internal| {
+                                            let (audio, subtitle) = selected_track_indices(
+                                                &selected,
+                                                &s.audio_index_to_stream_id,
+                                                &s.subtitle_index_to_stream_id,
+                                            );
+                                            s.current_audio_track = audio.unwrap_or(-1);
+                                            if let Some(index) = s
+                                                .video_index_to_stream_id
+                                                .iter()
+                                                .position(|id| selected.contains(id))
+                                            {
+                                                s.current_video_track = index as i32;
+                                            }
+                                            // Subtitles are normally rendered out-of-band and
+                                            // never part of the selection, so only a subtitle
+                                            // stream playbin3 picked itself changes the track
+                                            if let Some(index) = subtitle {
+                                                s.current_subtitle_track = Some(index);
+                                                s.subtitles_enabled = true;
+                                            }
+                                            s.selected_stream_ids = selected_stream_ids_without_subtitles(
+                                                &selected,
+                                                &s.subtitle_index_to_stream_id,
+                                            );
+                                        }))
+                                        .is_err()
+                                    {
+                                        log::debug!(
+                                            target: &log_target,
+                                            "[bus] receiver dropped; exiting bus thread"
+                                        );
+                                        break;
                                     }
                                 }
+                                MessageView::StateChanged(state_changed) => {
+                                    let from_pipeline = state_changed
+                                        .src()
+                                        .is_some_and(|src| src == gst_pipeline.upcast_ref::<gst::Object>());
+                                    if from_pipeline {
+                                        if let Some(seekable) = query_seekable(&gst_pipeline) {
+                                            if tx.send(Box::new(move |s: &mut Internal| s.seekable = seekable)).is_err() {
+                                                log::debug!(
+                                                    target: &log_target,
+                                                    "[bus] receiver dropped; exiting bus thread"
+                                                );
+                                                break;
+                                            }
+                                        }
+                                    }
                                 }
-                                MessageView::StateChanged(_state_changed) => {}
                                 MessageView::AsyncDone(_) => {
                                     // ── Detect HDR and update color management ──
                                     // After a state transition completes (PAUSED→PLAYING,
@@ -762,6 +1112,7 @@ impl SubsurfaceVideo {
                                                                     );
                                                                 } else {
                                                                     log::info!(
+                                                                        target: &state.log_target,
                                                                         "[color-mgmt] Pixel format {pixel_fmt_owned} is SDR; \
                                                                          NOT tagging surface as HDR despite PQ colorimetry"
                                                                     );
@@ -774,6 +1125,7 @@ impl SubsurfaceVideo {
                                                         }));
 
                                                         log::info!(
+                                                            target: &log_target,
                                                             "[color-mgmt] Detected colorimetry={colorimetry} format={pixel_format} hdr_capable={format_ok} mastering={mastering:?} cll={cll:?}"
                                                         );
                                                     }
@@ -795,11 +1147,13 @@ impl SubsurfaceVideo {
                                                 let ids = state.selected_stream_ids.clone();
                                                 if p.send_select_streams(&ids) {
                                                     log::info!(
+                                                        target: &state.log_target,
                                                         "[streams] Re-sent SelectStreams ({} ids) after AsyncDone",
                                                         ids.len()
                                                     );
                                                 } else {
                                                     log::warn!(
+                                                        target: &state.log_target,
                                                         "[streams] Failed to re-send SelectStreams after AsyncDone"
                                                     );
                                                 }
@@ -817,28 +1171,35 @@ impl SubsurfaceVideo {
                                                 let tolerance = Duration::from_secs(2);
                                                 if pos.abs_diff(target) > tolerance {
                                                     log::debug!(
+                                                        target: &state.log_target,
                                                         "[seek] Ignoring AsyncDone at {pos:?}; waiting for resume target {target:?}"
                                                     );
                                                     return;
                                                 }
                                                 log::info!(
+                                                    target: &state.log_target,
                                                     "[seek] AsyncDone reached resume target {target:?} at {pos:?}; resuming playback"
                                                 );
                                             } else if let Some(pos) = position {
-                                                log::debug!("[seek] AsyncDone at {pos:?}");
+                                                log::debug!(
+                                                    target: &state.log_target,
+                                                    "[seek] AsyncDone at {pos:?}"
+                                                );
                                             }
 
                                             // Only auto-play if user hasn't requested pause.
-                                            if !state.user_paused {
+                                            if !state.user_paused.load(Ordering::SeqCst) {
                                                 if let Some(p) = state.pipeline.clone() {
                                                     if let Err(err) = p.play() {
                                                         log::warn!(
+                                                            target: &state.log_target,
                                                             "[seek] Failed to resume playback after seek: {err}"
                                                         );
                                                     }
                                                 }
                                             } else {
                                                 log::debug!(
+                                                    target: &state.log_target,
                                                     "Autoplay gated by user pause; remaining paused"
                                                 );
                                             }
@@ -846,20 +1207,17 @@ impl SubsurfaceVideo {
                                             state.pending_start_position = None;
                                         }
                                     }));
-                                    //// Refresh seekable on AsyncDone as well
-                                    //let seekable = {
-                                    //    let mut q = gst::query::Seeking::new//(gst::Format::Time);
-                                    //    if gst_pipeline.query(q.query_mut()) {
-                                    //        let (seekable, _, _) = q.result();
-                                    //        Some(seekable)
-                                    //    } else { None }
-                                    //};
-                                    //if let Some(seek) = seekable {
-                                    //    if tx.send(Box::new(move |s: &mut Internal//| s.video_props.seekable = seek)).is_err() //{
-                                    //        log::debug!("[bus] receiver dropped; //exiting bus thread");
-                                    //        break;
-                                    //    }
-                                    //}
+
+                                    // Refresh seekable on AsyncDone as well
+                                    if let Some(seekable) = query_seekable(&gst_pipeline) {
+                                        if tx.send(Box::new(move |s: &mut Internal| s.seekable = seekable)).is_err() {
+                                            log::debug!(
+                                                target: &log_target,
+                                                "[bus] receiver dropped; exiting bus thread"
+                                            );
+                                            break;
+                                        }
+                                    }
                                 }
                                 _ => {}
                             }
@@ -875,7 +1233,17 @@ impl SubsurfaceVideo {
         // Commit subsurface, pipeline, and receiver into Internal
         {
             let mut w = self.0.write();
+            let _ = pipeline.set_volume(w.volume);
+            pipeline.set_muted(w.muted);
+            if w.hidden {
+                pipeline.set_video_hidden(true);
+            }
+            if !w.video_opaque {
+                subsurface.set_video_opaque(false);
+            }
+            subsurface.set_background_color(w.background_color);
             w.subsurface = Some(subsurface);
+            w.wayland_init = Some((integration, bounds));
             w.pipeline = Some(pipeline);
             w.cmd_rx = Some(rx);
             w.subtitle_event_rx = Some(subtitle_rx);
@@ -912,6 +1280,7 @@ impl SubsurfaceVideo {
                     if p.seek(Position::Time(Duration::ZERO), true).is_ok() {
                         let _ = p.play();
                         w.is_eos = false;
+                        w.eos_unreported = false;
                         w.restart_stream = false;
                     }
                 }
@@ -972,42 +1341,48 @@ impl SubsurfaceVideo {
                         video_height,
                     } => {
                         let (surface_width, surface_height) = subsurface.get_size();
+                        let scale = subsurface.buffer_scale();
                         if let Some(bitmap) = compose_pgs_bitmap(
                             &frames,
                             video_width,
                             video_height,
-                            surface_width,
-                            surface_height,
+                            surface_width * scale,
+                            surface_height * scale,
                         ) {
                             let _ = subsurface.attach_subtitle_frame(
                                 &bitmap.data,
                                 bitmap.width,
                                 bitmap.height,
                                 bitmap.stride,
+                                scale,
                             );
                         } else {
                             let _ = subsurface.clear_subtitle();
                         }
                     }
                     WaylandSubtitlePayload::Text(text) => {
+                        // Render at buffer resolution so text is sharp on HiDPI outputs
                         let (width, height) = subsurface.get_size();
-                        let width = width.max(1) as usize;
-                        let height = height.max(1) as usize;
-                        match crate::text_renderer::TextRenderer::new()
-                            .and_then(|renderer| renderer.render(&text, width, height))
-                        {
+                        let scale = subsurface.buffer_scale();
+                        let width = (width.max(1) * scale) as usize;
+                        let height = (height.max(1) * scale) as usize;
+                        match crate::text_renderer::TextRenderer::new().and_then(|renderer| {
+                            renderer.render(&text, width, height, scale as f32)
+                        }) {
                             Some(argb) => {
                                 let _ = subsurface.attach_subtitle_frame(
                                     &argb,
                                     width as i32,
                                     height as i32,
                                     (width * 4) as i32,
+                                    scale,
                                 );
                             }
                             None => {
                                 log::warn!(
-                                        "[text-probe] No font available or text empty; clearing subtitle"
-                                    );
+                                    target: &self.0.read().log_target,
+                                    "[text-probe] No font available or text empty; clearing subtitle"
+                                );
                                 let _ = subsurface.clear_subtitle();
                             }
                         }
@@ -1022,7 +1397,7 @@ impl SubsurfaceVideo {
         // Respect explicit user pause intent: do not auto-start if user paused
         let (user_paused, p) = {
             let r = self.0.read();
-            (r.user_paused, r.pipeline.clone())
+            (r.user_paused.load(Ordering::SeqCst), r.pipeline.clone())
         };
         if user_paused {
             // Silently succeed; caller wanted to play but user has paused explicitly
@@ -1070,6 +1445,104 @@ impl SubsurfaceVideo {
         Ok(())
     }
 
+    /// Tear down the pipeline and build a new one for the same URI onto the existing
+    /// subsurface, e.g. to recover from a fatal error without replacing the video the widget
+    /// is bound to.
+    ///
+    /// Position, paused state, volume, mute, speed, tracks and the subtitle URL are restored
+    /// through the pending state once the new pipeline has prerolled; looping, HTTP headers
+    /// and buffering settings carry over.
+    pub fn reload(&mut self) -> Result<(), Error> {
+        let (subsurface, init) = {
+            let r = self.0.read();
+            (r.subsurface.clone(), r.wayland_init.clone())
+        };
+        let (Some(subsurface), Some((integration, bounds))) = (subsurface, init) else {
+            return Err(Error::Pipeline("Video not initialized".into()));
+        };
+        self.rebuild_pipeline(subsurface, integration, bounds)
+    }
+
+    // Stop the current pipeline and start a new one onto `subsurface`, restoring the
+    // playback state once it has prerolled
+    fn rebuild_pipeline(
+        &self,
+        subsurface: Arc<WaylandSubsurfaceManager>,
+        integration: WaylandIntegration,
+        bounds: (i32, i32, i32, i32),
+    ) -> Result<(), Error> {
+        let state = self.pending_state().unwrap_or_else(|| PendingState {
+            paused: self.0.read().user_paused.load(Ordering::SeqCst),
+            position: self.position(),
+            speed: self.speed(),
+            volume: self.volume(),
+            muted: self.muted(),
+            audio_track: self.current_audio_track(),
+            subtitle_track: self.current_subtitle_track(),
+            subtitles_enabled: self.subtitles_enabled(),
+            subtitle_url: self.subtitle_url(),
+        });
+        log::info!(
+            target: &self.0.read().log_target,
+            "[reload] Rebuilding pipeline at {:?}",
+            state.position
+        );
+
+        let handle = {
+            let mut w = self.0.write();
+            w.bus_stop.store(true, Ordering::SeqCst);
+            w.bus_thread.take()
+        };
+        if let Some(h) = handle {
+            let _ = h.join();
+        }
+        let _ = subsurface.clear_subtitle();
+
+        // Forget everything learned from the old pipeline
+        let old = {
+            let mut w = self.0.write();
+            w.bus_stop = Arc::new(AtomicBool::new(false));
+            w.cmd_rx = None;
+            w.subtitle_event_rx = None;
+            w.subtitle_scheduler = None;
+            w.duration = None;
+            w.is_eos = false;
+            w.eos_unreported = false;
+            w.restart_stream = false;
+            w.has_error = false;
+            w.last_error = None;
+            w.error_unreported = false;
+            w.error_count = 0;
+            w.is_reconnecting = false;
+            w.is_buffering = false;
+            w.buffering_percent = 100;
+            w.startup_async_done = false;
+            w.stream_collection = None;
+            w.available_subtitles.clear();
+            w.available_audio_tracks.clear();
+            w.audio_index_to_stream_id.clear();
+            w.available_video_tracks.clear();
+            w.video_index_to_stream_id.clear();
+            w.current_video_track = -1;
+            w.subtitle_index_to_stream_id.clear();
+            w.selected_stream_ids.clear();
+            w.pgs_stream_ids.clear();
+            w.pending_play_after_seek = false;
+            w.pending_start_position = None;
+            w.pending_state = None;
+            w.pipeline.take()
+        };
+        if let Some(p) = old {
+            let _ = p.stop();
+        }
+        // waylandsink has let go of the old video surface now
+        self.0.write().detached_subsurface = None;
+
+        self.start_pipeline(subsurface, integration, bounds)?;
+        self.queue_pending_state(state);
+        Ok(())
+    }
+
     pub fn toggle_play(&self) -> Result<(), Error> {
         if self.is_playing() {
             self.pause()
@@ -1119,7 +1592,7 @@ impl SubsurfaceVideo {
         if !st.paused {
             let mut w = self.0.write();
             w.pending_play_after_seek = true;
-            w.user_paused = false;
+            w.user_paused.store(false, Ordering::SeqCst);
         }
         let _ = self.pause();
         Ok(())
@@ -1130,6 +1603,12 @@ impl SubsurfaceVideo {
         w.pending_state = Some(st);
     }
 
+    /// State queued with [`queue_pending_state`](Self::queue_pending_state) that has not
+    /// been applied to the pipeline yet.
+    pub fn pending_state(&self) -> Option<PendingState> {
+        self.0.read().pending_state.clone()
+    }
+
     /// Record the resume target. The autoplay gate is armed after the actual
     /// pending-state seek is issued, so an initial startup AsyncDone cannot
     /// accidentally consume it before the resume seek runs.
@@ -1156,6 +1635,10 @@ impl SubsurfaceVideo {
     }
 
     pub fn seek(&self, position: impl Into<Position>, accurate: bool) -> Result<(), Error> {
+        if !self.0.read().seekable {
+            log::warn!(target: &self.0.read().log_target, "Cannot seek: stream is not seekable");
+            return Err(Error::InvalidState);
+        }
         if let Some(p) = self.0.read().pipeline.clone() {
             p.seek(position, accurate)
         } else {
@@ -1186,6 +1669,31 @@ impl SubsurfaceVideo {
         }
     }
 
+    /// Lay the video out in an `area` sized widget: `source` is the visible part of the
+    /// frame (x, y, width, height) in video pixels, `dest` where it lands within the area.
+    pub fn set_video_geometry(
+        &self,
+        area: (i32, i32),
+        source: (i32, i32, i32, i32),
+        dest: (i32, i32, i32, i32),
+    ) {
+        let (pipeline, subsurface) = {
+            let guard = self.0.read();
+            (guard.pipeline.clone(), guard.subsurface.clone())
+        };
+
+        // The render rectangle is relative to the video surface, which moves to `dest`
+        if let Some(p) = pipeline {
+            p.set_render_rectangle(0, 0, dest.2, dest.3);
+        }
+
+        if let Some(s) = subsurface {
+            s.set_size(area.0, area.1);
+            s.set_source_size(source);
+            s.set_video_rect(dest);
+        }
+    }
+
     pub fn set_video_size_position(&self, x_offset: i32, y_offset: i32, width: i32, height: i32) {
         let (pipeline, subsurface) = {
             let guard = self.0.read();
@@ -1202,6 +1710,12 @@ impl SubsurfaceVideo {
     }
 
     // Resolution helpers: query directly from vsink caps for current stream
+    /// Id of this video, unique across both backends. Log messages about it use the target
+    /// `subwave[id]`.
+    pub fn id(&self) -> u64 {
+        self.0.read().id
+    }
+
     pub fn resolution(&self) -> Option<(i32, i32)> {
         let p = self.0.read().pipeline.clone()?;
         let video_pad = p
@@ -1215,6 +1729,31 @@ impl SubsurfaceVideo {
         Some((w, h))
     }
 
+    /// Whether the sink has had its first frame long enough for it to be on screen. The widget
+    /// keeps the video surface back until then so the area never flashes black.
+    pub fn first_frame_rendered(&self) -> bool {
+        self.0
+            .read()
+            .pipeline
+            .as_ref()
+            .is_some_and(|p| p.first_frame_rendered())
+    }
+
+    /// Pixel aspect ratio from the negotiated sink caps, if they carry one.
+    pub fn pixel_aspect_ratio(&self) -> Option<(i32, i32)> {
+        let p = self.0.read().pipeline.clone()?;
+        let video_pad = p
+            .pipeline
+            .by_name("vsink")
+            .and_then(|sink| sink.static_pad("sink"))?;
+        let caps = video_pad.current_caps()?;
+        let par = caps
+            .structure(0)?
+            .get::<gst::Fraction>("pixel-aspect-ratio")
+            .ok()?;
+        Some((par.numer(), par.denom()))
+    }
+
     pub fn width(&self) -> Option<i32> {
         self.resolution().map(|(w, _)| w)
     }
@@ -1222,9 +1761,51 @@ impl SubsurfaceVideo {
         self.resolution().map(|(_, h)| h)
     }
 
+    // Network buffering
+
+    /// Cap playbin3's download ring buffer (`ring-buffer-max-size`), 512MB by default, e.g. to
+    /// 32MB on memory-constrained devices. 0 disables the ring buffer.
+    ///
+    /// playbin3 hands the limit to the buffer it creates for each source, so while playing it
+    /// applies from the next source on (a reload, reconnect or track change). Set it before
+    /// [`init_wayland`](Self::init_wayland), or use
+    /// [`new_with_buffering`](Self::new_with_buffering), to cover the first one.
+    pub fn set_ring_buffer_max_size(&self, bytes: u64) {
+        let mut w = self.0.write();
+        w.buffer_config.ring_buffer_max = Some(bytes);
+        if let Some(p) = &w.pipeline {
+            p.pipeline.set_property("ring-buffer-max-size", bytes);
+        }
+    }
+
+    /// Set how much media playbin3 buffers ahead (`buffer-duration`), 6s by default. Like
+    /// [`set_ring_buffer_max_size`](Self::set_ring_buffer_max_size), a running pipeline
+    /// picks it up with its next source.
+    pub fn set_buffer_duration(&self, duration: Duration) {
+        let mut w = self.0.write();
+        w.buffer_config.duration = Some(duration);
+        if let Some(p) = &w.pipeline {
+            let nanos = i64::try_from(duration.as_nanos()).unwrap_or(i64::MAX);
+            p.pipeline.set_property("buffer-duration", nanos);
+        }
+    }
+
+    /// The buffering configuration the pipeline is (re)built with, including changes made
+    /// with `set_ring_buffer_max_size` and `set_buffer_duration`.
+    pub fn buffer_config(&self) -> BufferConfig {
+        self.0.read().buffer_config
+    }
+
     // Audio/volume/rate
+    /// Set the volume without touching mute. The pipeline plays with `SOFT_VOLUME`, so both
+    /// live on playsink's own volume element and stay independent.
     pub fn set_volume(&self, volume: f64) -> Result<(), Error> {
-        if let Some(p) = self.0.read().pipeline.clone() {
+        let pipeline = {
+            let mut w = self.0.write();
+            w.volume = volume;
+            w.pipeline.clone()
+        };
+        if let Some(p) = pipeline {
             p.set_volume(volume)
         } else {
             Ok(())
@@ -1232,14 +1813,89 @@ impl SubsurfaceVideo {
     }
 
     pub fn set_playback_rate(&self, rate: f64) -> Result<(), Error> {
-        if let Some(p) = self.0.read().pipeline.clone() {
-            p.set_playback_rate(rate)?;
+        let (pipeline, trick_mode) = {
+            let r = self.0.read();
+            (r.pipeline.clone(), r.trick_mode)
+        };
+        if let Some(p) = pipeline {
+            p.set_playback_rate(rate, trick_mode)?;
+            Ok(())
+        } else {
             Ok(())
+        }
+    }
+
+    /// Set the playback speed without flushing, for continuous adjustment such as a speed
+    /// slider. Falls back to a flushing seek when the direction changes or the pipeline
+    /// cannot change rate instantly.
+    pub fn set_speed_instant(&mut self, speed: f64) -> Result<(), Error> {
+        let speed = clamp_speed(speed)?;
+        let current = std::mem::replace(&mut self.0.write().speed, speed);
+        let (pipeline, trick_mode) = {
+            let r = self.0.read();
+            (r.pipeline.clone(), r.trick_mode)
+        };
+        if let Some(p) = pipeline {
+            p.set_playback_rate_instant(current, speed, trick_mode)
         } else {
             Ok(())
         }
     }
 
+    /// Seek with trick-mode flags on subsequent speed changes away from 1.0, e.g. keyframes
+    /// only and no audio so 8x or 16x scanning does not stall decoding every frame. Takes
+    /// effect on the next speed change; the default uses no trick mode.
+    pub fn set_trick_mode(&mut self, mode: TrickMode) {
+        self.0.write().trick_mode = mode;
+    }
+
+    /// The trick mode set with [`set_trick_mode`](Self::set_trick_mode).
+    pub fn trick_mode(&self) -> TrickMode {
+        self.0.read().trick_mode
+    }
+
+    /// Run playback on `clock`, e.g. an external audio engine's, instead of the pipeline's own.
+    ///
+    /// Best called before [`init_wayland`](Self::init_wayland), so the pipeline starts on
+    /// it; a playing pipeline pauses briefly to adopt the clock. Several videos on one clock
+    /// with the same [base time](Self::set_base_time) play in lockstep.
+    pub fn set_clock(&mut self, clock: &gst::Clock) -> Result<(), Error> {
+        let pipeline = {
+            let mut w = self.0.write();
+            w.clock = Some(clock.clone());
+            w.pipeline.clone()
+        };
+        if let Some(p) = pipeline {
+            adopt_clock(&p.pipeline, clock)?;
+        }
+        Ok(())
+    }
+
+    /// The clock playback runs on, for other pipelines or an audio engine to follow. Before
+    /// the pipeline exists this is the clock set with [`set_clock`](Self::set_clock), if any,
+    /// else the system clock.
+    pub fn use_pipeline_clock(&self) -> gst::Clock {
+        let r = self.0.read();
+        match (&r.pipeline, &r.clock) {
+            (Some(p), _) => pipeline_clock(&p.pipeline),
+            (None, Some(clock)) => clock.clone(),
+            (None, None) => gst::SystemClock::obtain(),
+        }
+    }
+
+    /// Fix the base time instead of letting the pipeline choose one whenever it starts
+    /// playing. Requires an initialized pipeline.
+    ///
+    /// The base time stays put across pauses, so running time keeps counting while paused
+    /// and playback resumes that much further on. Add the paused duration to the base time
+    /// and set it again before resuming to pick up where playback stopped.
+    pub fn set_base_time(&mut self, base_time: gst::ClockTime) -> Result<(), Error> {
+        let pipeline = self.0.read().pipeline.clone();
+        let p = pipeline.ok_or(Error::InvalidState)?;
+        lock_base_time(&p.pipeline, base_time);
+        Ok(())
+    }
+
     pub fn current_audio_track(&self) -> i32 {
         let w = self.0.read();
         if w.current_audio_track >= 0 {
@@ -1309,6 +1965,53 @@ impl SubsurfaceVideo {
         }
     }
 
+    pub fn current_video_track(&self) -> i32 {
+        self.0.read().current_video_track
+    }
+
+    pub fn video_tracks_info(&self) -> Vec<VideoTrack> {
+        self.0.read().available_video_tracks.clone()
+    }
+
+    pub fn select_video_track(&self, index: i32) -> Result<(), Error> {
+        let (p, mut new_ids, video_ids) = {
+            let r = self.0.read();
+            let p = r.pipeline.clone();
+            if index < 0 || (index as usize) >= r.video_index_to_stream_id.len() {
+                return Err(Error::Pipeline(format!(
+                    "Invalid video track index: {}",
+                    index
+                )));
+            }
+            let mut ids = r.selected_stream_ids.clone();
+            ids.retain(|id| !r.video_index_to_stream_id.iter().any(|vid| vid == id));
+            (p, ids, r.video_index_to_stream_id.clone())
+        };
+
+        let Some(p) = p else {
+            return Err(Error::Pipeline("Video not initialized".into()));
+        };
+        // Keep the video stream first, matching the initial selection
+        new_ids.insert(0, video_ids[index as usize].clone());
+        dedup_in_place(&mut new_ids);
+
+        if new_ids == self.0.read().selected_stream_ids {
+            self.0.write().current_video_track = index;
+            return Ok(());
+        }
+
+        if p.send_select_streams(&new_ids) {
+            let mut w = self.0.write();
+            w.selected_stream_ids = new_ids;
+            w.current_video_track = index;
+            Ok(())
+        } else {
+            Err(Error::Pipeline(
+                "Failed to send SelectStreams for video".into(),
+            ))
+        }
+    }
+
     pub fn select_subtitle_track(&self, index: Option<i32>) -> Result<(), Error> {
         let (sub_ids, pgs_ids, subsurface) = {
             let r = self.0.read();
@@ -1366,6 +2069,7 @@ impl SubsurfaceVideo {
                     let _ = subsurface.clear_subtitle();
                 }
                 log::info!(
+                    target: &self.0.read().log_target,
                     "[subs] Selected out-of-band subtitle index={i}, stream={stream_id}, pgs={is_pgs}"
                 );
                 Ok(())
@@ -1394,7 +2098,10 @@ impl SubsurfaceVideo {
                     let _ = subsurface.clear_subtitle();
                 }
                 if should_log {
-                    log::info!("[subs] Disabled out-of-band subtitles");
+                    log::info!(
+                        target: &self.0.read().log_target,
+                        "[subs] Disabled out-of-band subtitles"
+                    );
                 }
                 Ok(())
             }
@@ -1436,10 +2143,140 @@ impl SubsurfaceVideo {
         self.0.read().subsurface.clone()
     }
 
+    /// Show or hide the video while keeping the pipeline PLAYING, e.g. when the window is
+    /// minimized or the widget is scrolled out of view. Audio continues while hidden.
+    ///
+    /// Hiding detaches the video surface's buffer and stops feeding waylandsink, which would
+    /// otherwise stall waiting on frame callbacks the compositor no longer sends. Showing
+    /// again re-commits with full damage and redraws the current frame.
+    pub fn set_visible(&self, visible: bool) {
+        let (pipeline, subsurface) = {
+            let mut w = self.0.write();
+            w.hidden = !visible;
+            (w.pipeline.clone(), w.subsurface.clone())
+        };
+        if let Some(pipeline) = &pipeline {
+            pipeline.set_video_hidden(!visible);
+        }
+        if let Some(subsurface) = subsurface {
+            subsurface.set_visible(visible);
+        }
+    }
+
+    /// Take the video off screen, e.g. when the widget leaves the view, while the pipeline
+    /// keeps playing audio. The subsurfaces are unmapped and torn down as when the video
+    /// drops, and the next [`init_wayland`](Self::init_wayland), which the widget calls once
+    /// it is drawn again, creates new ones and rebuilds the pipeline onto them at the current
+    /// position.
+    pub fn detach_surface(&self) {
+        let (pipeline, subsurface) = {
+            let mut w = self.0.write();
+            let Some(subsurface) = w.subsurface.take() else {
+                return;
+            };
+            w.detached_subsurface = Some(Arc::clone(&subsurface));
+            (w.pipeline.clone(), subsurface)
+        };
+        if let Some(pipeline) = &pipeline {
+            // waylandsink would otherwise stall on frame callbacks that never come
+            pipeline.set_video_hidden(true);
+        }
+        subsurface.detach();
+    }
+
+    /// Declare whether the video is fully opaque (the default). The video surface is then
+    /// marked opaque over its extent so the compositor can skip what lies beneath; turn
+    /// this off for content with an alpha channel.
+    pub fn set_video_opaque(&self, opaque: bool) {
+        let subsurface = {
+            let mut w = self.0.write();
+            w.video_opaque = opaque;
+            w.subsurface.clone()
+        };
+        if let Some(subsurface) = subsurface {
+            subsurface.set_video_opaque(opaque);
+        }
+    }
+
+    /// Color (RGBA) of the area around the video, e.g. the letterbox bars, to match the app's
+    /// background. Opaque black by default; kept when the subsurfaces are recreated.
+    pub fn set_background_color(&self, rgba: [u8; 4]) {
+        let subsurface = {
+            let mut w = self.0.write();
+            w.background_color = rgba;
+            w.subsurface.clone()
+        };
+        if let Some(subsurface) = subsurface {
+            subsurface.set_background_color(rgba);
+        }
+    }
+
+    /// Whether the video was hidden with [`set_visible`](Self::set_visible).
+    pub fn is_visible(&self) -> bool {
+        !self.0.read().hidden
+    }
+
+    /// Number of consecutive network errors the bus thread is recovering from. Drops back to
+    /// zero once playback has run for a while without errors.
+    pub fn error_count(&self) -> u32 {
+        self.0.read().error_count
+    }
+
+    /// Whether the pipeline is being reconnected after a network error, e.g. to show
+    /// "reconnecting…" in the UI.
+    pub fn is_reconnecting(&self) -> bool {
+        self.0.read().is_reconnecting
+    }
+
+    /// Returns true once after the stream ends without looping, so the widget can publish
+    /// its end-of-stream message. The EOS itself is picked up by [`tick`](Self::tick).
+    pub fn take_end_of_stream(&self) -> bool {
+        std::mem::take(&mut self.0.write().eos_unreported)
+    }
+
+    /// The error that stopped playback, if any, until the stream is restarted or reloaded.
+    pub fn last_error(&self) -> Option<gst::glib::Error> {
+        self.0.read().last_error.clone()
+    }
+
+    /// Returns the error once after playback failed for good (not a network error being
+    /// retried), so the widget can publish its error message.
+    pub fn take_error(&self) -> Option<gst::glib::Error> {
+        let mut w = self.0.write();
+        if std::mem::take(&mut w.error_unreported) {
+            w.last_error.clone()
+        } else {
+            None
+        }
+    }
+
+    /// Returns the duration once after the bus reported a new one, so the widget can publish
+    /// its duration-changed message. Like the EOS, it is picked up by [`tick`](Self::tick).
+    pub fn take_duration_change(&self) -> Option<Duration> {
+        let mut w = self.0.write();
+        if std::mem::take(&mut w.duration_unreported) {
+            w.duration
+        } else {
+            None
+        }
+    }
+
+    /// Returns true once after a stream collection updated the track lists, so the widget
+    /// can publish its streams-changed message.
+    pub fn take_streams_changed(&self) -> bool {
+        std::mem::take(&mut self.0.write().streams_unreported)
+    }
+
     // Widget-friendly helper for throttled frame notifications
     pub fn should_emit_on_new_frame(&self, interval: Duration) -> bool {
         let now = Instant::now();
+        // Called on every redraw: only contend with the bus thread for the write lock when a
+        // notification is due
+        if now.duration_since(self.0.read().last_position_update) < interval {
+            return false;
+        }
         let mut w = self.0.write();
+        // Re-checked, another caller may have emitted between the two locks
         if now.duration_since(w.last_position_update) >= interval {
             w.last_position_update = now;
             true
@@ -1449,6 +2286,38 @@ impl SubsurfaceVideo {
     }
 }
 
+/// Network errors tolerated before the bus thread gives up and reports the error.
+const MAX_RECONNECT_ATTEMPTS: u32 = 5;
+/// Error-free playback time after which the error count is reset.
+const ERROR_RESET_AFTER: Duration = Duration::from_secs(30);
+
+/// Sleep for `duration` in short steps so a stopping bus thread is not held up.
+/// Returns false if `stop` was raised in the meantime.
+fn sleep_unless_stopped(stop: &AtomicBool, duration: Duration) -> bool {
+    let deadline = Instant::now() + duration;
+    while Instant::now() < deadline {
+        if stop.load(Ordering::SeqCst) {
+            return false;
+        }
+        std::thread::sleep(Duration::from_millis(50).min(deadline - Instant::now()));
+    }
+    !stop.load(Ordering::SeqCst)
+}
+
+/// Bring a pipeline that was reset to READY back to PAUSED and seek to where playback
+/// stopped. Resuming to PLAYING is left to the UI thread so user pause is respected.
+fn reconnect_pipeline(pipeline: &gst::Pipeline, position: Duration) -> Result<(), Error> {
+    pipeline.set_state(gst::State::Paused)?;
+    pipeline.state(gst::ClockTime::from_seconds(10)).0?;
+    if position > Duration::ZERO {
+        pipeline.seek_simple(
+            gst::SeekFlags::FLUSH | gst::SeekFlags::KEY_UNIT,
+            gst::ClockTime::from_nseconds(position.as_nanos() as u64),
+        )?;
+    }
+    Ok(())
+}
+
 fn drain_subtitle_probe_events(state: &mut Internal) {
     loop {
         let event = match state.subtitle_event_rx.as_ref() {
@@ -1525,6 +2394,21 @@ fn selected_stream_ids_without_subtitles(
     ids
 }
 
+/// Map the stream ids in a StreamsSelected message back to audio and subtitle track indices.
+fn selected_track_indices(
+    selected_stream_ids: &[String],
+    audio_stream_ids: &[String],
+    subtitle_stream_ids: &[String],
+) -> (Option<i32>, Option<i32>) {
+    let index_of = |ids: &[String]| {
+        selected_stream_ids
+            .iter()
+            .find_map(|selected| ids.iter().position(|id| id == selected))
+            .map(|index| index as i32)
+    };
+    (index_of(audio_stream_ids), index_of(subtitle_stream_ids))
+}
+
 fn dedup_in_place(v: &mut Vec<String>) {
     let mut seen = std::collections::HashSet::<String>::new();
     v.retain(|s| seen.insert(s.clone()));
@@ -1547,9 +2431,22 @@ impl Drop for SubsurfaceVideo {
     }
 }
 
+/// Frames per second from the `framerate` field of video caps. Variable framerate streams
+/// advertise 0/1, which counts as unknown.
+fn framerate_from_caps(caps: &gst::CapsRef) -> Option<f64> {
+    let framerate = caps.structure(0)?.get::<gst::Fraction>("framerate").ok()?;
+    (framerate.numer() > 0 && framerate.denom() > 0)
+        .then(|| framerate.numer() as f64 / framerate.denom() as f64)
+}
+
 #[cfg(test)]
 mod tests {
-    use super::selected_stream_ids_without_subtitles;
+    use super::{
+        framerate_from_caps, selected_stream_ids_without_subtitles, selected_track_indices, Arc,
+        SubsurfacePipeline, SubsurfaceVideo,
+    };
+    use gstreamer::prelude::*;
+    use subwave_core::video_trait::Video;
 
     fn strings(values: &[&str]) -> Vec<String> {
         values.iter().map(|value| value.to_string()).collect()
@@ -1576,4 +2473,71 @@ mod tests {
             strings(&["video/0", "audio/0"])
         );
     }
+
+    #[test]
+    fn streams_selected_maps_back_to_track_indices() {
+        let audio_ids = strings(&["audio/en", "audio/de"]);
+        let subtitle_ids = strings(&["subtitle/en", "subtitle/es"]);
+
+        assert_eq!(
+            selected_track_indices(
+                &strings(&["video/0", "audio/de"]),
+                &audio_ids,
+                &subtitle_ids
+            ),
+            (Some(1), None)
+        );
+        assert_eq!(
+            selected_track_indices(
+                &strings(&["video/0", "subtitle/es", "audio/en"]),
+                &audio_ids,
+                &subtitle_ids
+            ),
+            (Some(0), Some(1))
+        );
+        assert_eq!(
+            selected_track_indices(&strings(&["video/0"]), &audio_ids, &subtitle_ids),
+            (None, None)
+        );
+    }
+
+    #[test]
+    fn variable_framerate_caps_have_no_framerate() {
+        gstreamer::init().unwrap();
+        let caps = |framerate: gstreamer::Fraction| {
+            gstreamer::Caps::builder("video/x-raw")
+                .field("framerate", framerate)
+                .build()
+        };
+        assert_eq!(
+            framerate_from_caps(&caps(gstreamer::Fraction::new(30000, 1001))),
+            Some(30000.0 / 1001.0)
+        );
+        assert_eq!(
+            framerate_from_caps(&caps(gstreamer::Fraction::new(0, 1))),
+            None
+        );
+        assert_eq!(
+            framerate_from_caps(&gstreamer::Caps::builder("video/x-raw").build()),
+            None
+        );
+    }
+
+    #[test]
+    fn volume_changes_leave_mute_alone() {
+        let uri = url::Url::parse("file:///dev/null").unwrap();
+        let mut video = SubsurfaceVideo::new(&uri).unwrap();
+        let pipeline = Arc::new(SubsurfacePipeline::without_surface());
+        video.0.write().pipeline = Some(Arc::clone(&pipeline));
+
+        Video::set_volume(&mut video, 0.5);
+        video.set_muted(true);
+        Video::set_volume(&mut video, 0.8);
+
+        // Read back from playbin3, not the cached settings
+        assert!(pipeline.pipeline.property::<bool>("mute"));
+        assert_eq!(pipeline.pipeline.property::<f64>("volume"), 0.8);
+        assert!(video.muted());
+        assert_eq!(video.volume(), 0.8);
+    }
 }
@@ -1,7 +1,9 @@
 use crate::SubsurfaceVideo;
 use gstreamer::glib;
+use subwave_core::video::types::video_log_target;
 
 type OnError<'a, Message> = Box<dyn Fn(&glib::Error) -> Message + 'a>;
+type OnDurationChanged<'a, Message> = Box<dyn Fn(Duration) -> Message + 'a>;
 use iced::{
     advanced::{self, layout, widget::Widget},
     ContentFit, Element, Event, Length, Rectangle, Size,
@@ -9,6 +11,7 @@ use iced::{
 use std::cell::RefCell;
 use std::marker::PhantomData;
 use std::rc::Rc;
+use std::time::Duration;
 
 pub type VideoHandle = Rc<RefCell<Option<Box<SubsurfaceVideo>>>>;
 
@@ -18,12 +21,15 @@ pub type VideoHandle = Rc<RefCell<Option<Box<SubsurfaceVideo>>>>;
 /// Note: This widget requires the wgpu renderer and Wayland platform
 pub struct VideoPlayer<'a, Message, Theme = iced::Theme> {
     video: &'a VideoHandle,
-    _content_fit: ContentFit,
+    content_fit: ContentFit,
     width: Length,
     height: Length,
-    _on_end_of_stream: Option<Message>,
-    _on_error: Option<OnError<'a, Message>>,
+    on_end_of_stream: Option<Message>,
+    on_error: Option<OnError<'a, Message>>,
+    on_duration_changed: Option<OnDurationChanged<'a, Message>>,
+    on_streams_changed: Option<Message>,
     on_new_frame: Option<Message>,
+    on_new_frame_interval: Duration,
     _phantom: PhantomData<Theme>,
 }
 
@@ -32,12 +38,15 @@ impl<'a, Message, Theme> VideoPlayer<'a, Message, Theme> {
     pub fn new(video: &'a VideoHandle) -> Self {
         Self {
             video,
-            _content_fit: ContentFit::Contain,
+            content_fit: ContentFit::Contain,
             width: Length::Fill,
             height: Length::Fill,
-            _on_end_of_stream: None,
-            _on_error: None,
+            on_end_of_stream: None,
+            on_error: None,
+            on_duration_changed: None,
+            on_streams_changed: None,
             on_new_frame: None,
+            on_new_frame_interval: Duration::from_millis(100),
             _phantom: PhantomData,
         }
     }
@@ -66,7 +75,7 @@ impl<'a, Message, Theme> VideoPlayer<'a, Message, Theme> {
     /// Set the content fit mode
     pub fn content_fit(self, content_fit: ContentFit) -> Self {
         VideoPlayer {
-            _content_fit: content_fit,
+            content_fit,
             ..self
         }
     }
@@ -74,17 +83,40 @@ impl<'a, Message, Theme> VideoPlayer<'a, Message, Theme> {
     /// Set a message to emit when the video reaches end of stream
     pub fn on_end_of_stream(self, on_end_of_stream: Message) -> Self {
         VideoPlayer {
-            _on_end_of_stream: Some(on_end_of_stream),
+            on_end_of_stream: Some(on_end_of_stream),
             ..self
         }
     }
 
+    /// Set a message to emit when playback fails with an error it cannot recover from.
+    /// Network errors are retried first and only reported once reconnecting gives up
     pub fn on_error<F>(self, on_error: F) -> Self
     where
         F: 'a + Fn(&glib::Error) -> Message,
     {
         VideoPlayer {
-            _on_error: Some(Box::new(on_error)),
+            on_error: Some(Box::new(on_error)),
+            ..self
+        }
+    }
+
+    /// Set a message to emit when the media duration becomes known or changes, which for
+    /// network streams often happens after playback has started
+    pub fn on_duration_changed<F>(self, on_duration_changed: F) -> Self
+    where
+        F: 'a + Fn(Duration) -> Message,
+    {
+        VideoPlayer {
+            on_duration_changed: Some(Box::new(on_duration_changed)),
+            ..self
+        }
+    }
+
+    /// Set a message to emit when a stream collection arrives, i.e. once the audio and
+    /// subtitle track lists are populated and again whenever they change
+    pub fn on_streams_changed(self, on_streams_changed: Message) -> Self {
+        VideoPlayer {
+            on_streams_changed: Some(on_streams_changed),
             ..self
         }
     }
@@ -97,6 +129,14 @@ impl<'a, Message, Theme> VideoPlayer<'a, Message, Theme> {
             ..self
         }
     }
+
+    /// Set the minimum time between `on_new_frame` messages. Defaults to 100ms.
+    pub fn on_new_frame_interval(self, interval: Duration) -> Self {
+        VideoPlayer {
+            on_new_frame_interval: interval,
+            ..self
+        }
+    }
 }
 
 impl<'a, Message, Theme> Widget<Message, Theme, iced_wgpu::Renderer>
@@ -138,12 +178,39 @@ where
             // Check if video is available and process position updates
             if let Ok(guard) = self.video.try_borrow() {
                 if let Some(video) = guard.as_ref() {
+                    // The bus EOS is applied by tick() during draw; report it on the
+                    // following redraw (the pipeline stays in PLAYING after EOS)
+                    if video.take_end_of_stream() {
+                        if let Some(on_end_of_stream) = self.on_end_of_stream.clone() {
+                            shell.publish(on_end_of_stream);
+                        }
+                    }
+                    if let Some(error) = video.take_error() {
+                        if let Some(on_error) = &self.on_error {
+                            shell.publish(on_error(&error));
+                        }
+                    }
+                    if video.take_streams_changed() {
+                        if let Some(on_streams_changed) = self.on_streams_changed.clone() {
+                            shell.publish(on_streams_changed);
+                        }
+                    }
+                    if let Some(duration) = video.take_duration_change() {
+                        if let Some(on_duration_changed) = &self.on_duration_changed {
+                            shell.publish(on_duration_changed(duration));
+                        }
+                    }
+
+                    // Keep drawing until the first frame is shown so the video gets revealed
+                    if video.resolution().is_some() && !video.first_frame_rendered() {
+                        shell.request_redraw();
+                    }
+
                     // Only emit new frame message if the video is playing
-                    // and enough time has passed since last update (100ms throttling)
+                    // and enough time has passed since last update
                     if video.is_playing() {
-                        // Check if 100ms has passed since last position update
                         let should_update =
-                            video.should_emit_on_new_frame(std::time::Duration::from_millis(1000));
+                            video.should_emit_on_new_frame(self.on_new_frame_interval);
 
                         // Emit new frame message if configured and timing is right
                         // This allows the player to update position/duration
@@ -172,26 +239,17 @@ where
         _cursor: advanced::mouse::Cursor,
         _viewport: &Rectangle,
     ) {
-        let video_available = if let Ok(guard) = self.video.try_borrow() {
-            guard.is_some()
-        } else {
-            false
-        };
-
-        if !video_available {
+        // An empty (or busy) handle has no video whose target the skip could be logged under
+        let Some((log_target, initialized)) = self.video.try_borrow().ok().and_then(|guard| {
+            // A subsurface indicates the video has been initialized
+            let video = guard.as_ref()?;
+            Some((
+                video_log_target(video.id()),
+                video.get_subsurface().is_some(),
+            ))
+        }) else {
             log::debug!("Video not yet available, skipping draw");
             return;
-        }
-
-        let initialized = if let Ok(guard) = self.video.try_borrow() {
-            if let Some(video) = guard.as_ref() {
-                // Check if the video has a subsurface (indicates it's initialized)
-                video.get_subsurface().is_some()
-            } else {
-                false
-            }
-        } else {
-            false
         };
 
         // Get the layout bounds before the closure
@@ -199,7 +257,10 @@ where
 
         // Only initialize if not already initialized
         if !initialized {
-            log::debug!("Video not yet initialized with Wayland, initializing...");
+            log::debug!(
+                target: &log_target,
+                "Video not yet initialized with Wayland, initializing..."
+            );
 
             let integration_result = initialize(self, window_bounds);
 
@@ -211,14 +272,13 @@ where
             }
         }
 
-        // TODO: Calculate and pass the correct aspect ratio to the video player pipeline seemlessly
-        // We should probably add the element to the pipeline on demand if the user changes the default fit mode
         if let Ok(guard) = self.video.try_borrow() {
             if let Some(video) = guard.as_ref() {
                 if let Some(resolution) = video.resolution() {
                     // Validate video dimensions - must be reasonable
                     if resolution.0 < 2 || resolution.1 < 2 {
                         log::debug!(
+                            target: &log_target,
                             "WARNING: Invalid video dimensions detected: {}x{}, skipping render",
                             resolution.0,
                             resolution.1
@@ -226,33 +286,53 @@ where
                         return; // Skip this draw call until we have valid dimensions
                     }
 
-                    let _video_width = resolution.0;
-                    let _video_height = resolution.1;
-                    //let video_aspect = video_width / video_height;
-
-                    let widget_width = window_bounds.width;
-                    let widget_height = window_bounds.height;
-                    //let widget_aspect = widget_width / widget_height;
+                    let area = (
+                        window_bounds.width.round() as i32,
+                        window_bounds.height.round() as i32,
+                    );
+                    let geometry = fit_video(self.content_fit, resolution, area);
 
                     // Apply the calculated viewport
                     if let Some(subsurface) = video.get_subsurface() {
-                        let current_size = subsurface.get_size();
-                        let new_width = widget_width.round() as i32;
-                        let new_height = widget_height.round() as i32;
+                        // Output scale or transform changes need the geometry applied again
+                        let rescaled = subsurface.dispatch_events();
+                        let applied = (
+                            subsurface.get_size(),
+                            subsurface.get_source_size(),
+                            subsurface.get_video_rect(),
+                        );
 
-                        if current_size != (new_width, new_height)
-                            && new_width > 0
-                            && new_height > 0
+                        if !video.first_frame_rendered() {
+                            // Sizing the video surface before waylandsink has a frame on it
+                            // flashes black, so only the background covers the area for now
+                            if area.0 > 0 && area.1 > 0 {
+                                subsurface.update_background(area.0, area.1);
+                                subsurface.integration.trigger_pre_commit_hooks();
+                                if let Err(e) = subsurface.flush() {
+                                    log::debug!(target: &log_target, "Error: {:#?}", e);
+                                }
+                            }
+                        } else if (rescaled
+                            || applied != (area, geometry.source, Some(geometry.dest)))
+                            && area.0 > 0
+                            && area.1 > 0
                         {
-                            log::info!("Setting new size to {}, {}", new_width, new_height);
-                            subsurface.update_background(new_width, new_height);
-                            subsurface.set_size(new_width, new_height);
-                            video.set_video_size_position(0, 0, new_width, new_height);
+                            log::info!(
+                                target: &log_target,
+                                "Setting new size to {}, {} (video at {:?}, source {:?})",
+                                area.0,
+                                area.1,
+                                geometry.dest,
+                                geometry.source
+                            );
+                            // The background spans the whole widget and shows through as bars
+                            subsurface.update_background(area.0, area.1);
+                            video.set_video_geometry(area, geometry.source, geometry.dest);
                             subsurface.integration.trigger_pre_commit_hooks();
                             subsurface.force_damage_and_commit();
                             match subsurface.flush() {
                                 Ok(_) => (),
-                                Err(e) => log::debug!("Error: {:#?}", e),
+                                Err(e) => log::debug!(target: &log_target, "Error: {:#?}", e),
                             }
                         }
 
@@ -271,6 +351,56 @@ where
     }
 }
 
+/// Where the video goes for a given [`ContentFit`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct VideoGeometry {
+    /// Visible part of the frame (x, y, width, height) in video pixels.
+    source: (i32, i32, i32, i32),
+    /// Where that part is shown (x, y, width, height) within the widget.
+    dest: (i32, i32, i32, i32),
+}
+
+/// Map a `video` sized frame into an `area` sized widget. `Cover` and `None` crop the
+/// source symmetrically, `Contain` and `ScaleDown` center a smaller destination.
+fn fit_video(content_fit: ContentFit, video: (i32, i32), area: (i32, i32)) -> VideoGeometry {
+    let (video_w, video_h) = (video.0.max(1) as f32, video.1.max(1) as f32);
+    let (area_w, area_h) = (area.0.max(1) as f32, area.1.max(1) as f32);
+    let contain = (area_w / video_w).min(area_h / video_h);
+    let scale = match content_fit {
+        ContentFit::Fill => {
+            return VideoGeometry {
+                source: (0, 0, video.0, video.1),
+                dest: (0, 0, area.0, area.1),
+            }
+        }
+        ContentFit::Contain => contain,
+        ContentFit::Cover => (area_w / video_w).max(area_h / video_h),
+        ContentFit::ScaleDown => contain.min(1.0),
+        ContentFit::None => 1.0,
+    };
+
+    // Scaled frame size, then whatever of it fits in the area
+    let (scaled_w, scaled_h) = (video_w * scale, video_h * scale);
+    let (dest_w, dest_h) = (scaled_w.min(area_w), scaled_h.min(area_h));
+    let (source_w, source_h) = (dest_w / scale, dest_h / scale);
+
+    let centered = |outer: f32, inner: f32| ((outer - inner) / 2.0).round() as i32;
+    VideoGeometry {
+        source: (
+            centered(video_w, source_w),
+            centered(video_h, source_h),
+            source_w.round() as i32,
+            source_h.round() as i32,
+        ),
+        dest: (
+            centered(area_w, dest_w),
+            centered(area_h, dest_h),
+            dest_w.round() as i32,
+            dest_h.round() as i32,
+        ),
+    }
+}
+
 fn initialize<'a, Message, Theme>(
     video_player: &VideoPlayer<'a, Message, Theme>,
     window_bounds: Rectangle,
@@ -356,3 +486,40 @@ where
         Self::new(video_player)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn contain_letterboxes_wide_video() {
+        let geometry = fit_video(ContentFit::Contain, (1920, 1080), (1000, 1000));
+        assert_eq!(geometry.source, (0, 0, 1920, 1080));
+        assert_eq!(geometry.dest, (0, 219, 1000, 563));
+    }
+
+    #[test]
+    fn cover_crops_source_to_area_aspect() {
+        let geometry = fit_video(ContentFit::Cover, (1920, 1080), (1000, 1000));
+        assert_eq!(geometry.source, (420, 0, 1080, 1080));
+        assert_eq!(geometry.dest, (0, 0, 1000, 1000));
+    }
+
+    #[test]
+    fn none_keeps_native_size() {
+        let small = fit_video(ContentFit::None, (640, 360), (1280, 720));
+        assert_eq!(small.source, (0, 0, 640, 360));
+        assert_eq!(small.dest, (320, 180, 640, 360));
+
+        let large = fit_video(ContentFit::None, (1920, 1080), (1280, 720));
+        assert_eq!(large.source, (320, 180, 1280, 720));
+        assert_eq!(large.dest, (0, 0, 1280, 720));
+    }
+
+    #[test]
+    fn fill_stretches() {
+        let geometry = fit_video(ContentFit::Fill, (640, 480), (1280, 720));
+        assert_eq!(geometry.source, (0, 0, 640, 480));
+        assert_eq!(geometry.dest, (0, 0, 1280, 720));
+    }
+}